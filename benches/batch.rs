@@ -0,0 +1,81 @@
+//! Compares `StatBatch` with a `Vec< CharStat >` ticked every frame.
+//! Run with `cargo bench --bench batch`.
+
+use std::hint::black_box;
+use std::time::{ Duration, Instant };
+
+use char_stat::{ StatBatch, CharStat, BaseConf, UpgradeConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Helpers
+
+const STATS: u64 = 50_000;
+const FRAMES: u64 = 200;
+
+/// every 100th stat has a buff expiring during the run
+fn stats () -> Vec< CharStat > {
+	let bounds = Bounds::new_const( 0.0, 1000.0 ).unwrap();
+	let common = ModCommon::new( 0.1, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+	
+	( 0..STATS ).map( |i| {
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, RoundingHelper::new_none() ).unwrap();
+		let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+		let mut cs = CharStat::new( base, Some( upgrade ), Some( mod_conf ), None, None, None );
+		
+		if i % 100 == 0 {
+			cs.append_modifier( Modifier::new_expiring( common, i % FRAMES + 1 ) ).unwrap();
+		}
+		
+		cs.flush();
+		cs
+	} ).collect()
+}
+
+fn run_vec () -> Duration {
+	let mut stats = stats();
+	let start = Instant::now();
+	
+	for ts in 0..FRAMES {
+		let mut sum = 0.0;
+		
+		for cs in &mut stats {
+			cs.set_ts( ts ).unwrap();
+			sum += cs.value();
+		}// for
+		
+		black_box( sum );
+	}// for
+	
+	start.elapsed()
+}
+
+fn run_batch () -> Duration {
+	let mut batch = StatBatch::from_stats( stats() );
+	let start = Instant::now();
+	
+	for ts in 0..FRAMES {
+		batch.set_ts( ts ).unwrap();
+		black_box( batch.values().iter().sum::< f64 >() );
+	}// for
+	
+	start.elapsed()
+}
+
+// --Helpers
+//------------------------------------------------------------------------------
+// --Main
+
+fn main () {
+	println!( "{STATS} stats, {FRAMES} frames of set_ts + sum of values" );
+	
+	let vec = run_vec();
+	let batch = run_batch();
+	
+	println!( "Vec< CharStat >: {vec:>12?}" );
+	println!( "     StatBatch: {batch:>12?}" );
+}
+
+// --Main
+//------------------------------------------------------------------------------
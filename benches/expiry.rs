@@ -0,0 +1,91 @@
+//! Compares `CharStat::set_ts` with the previous linear scan of modifiers.
+//! Run with `cargo bench --bench expiry`.
+
+use std::hint::black_box;
+use std::time::{ Duration, Instant };
+
+use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Helpers
+
+const MODIFIERS: u64 = 1_000;
+const FRAMES: u64 = 10_000;
+
+/// modifiers expire every `spacing` frames
+fn modifiers ( spacing: u64 ) -> Vec< Modifier > {
+	let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+	
+	( 1..=MODIFIERS ).map( |i| Modifier::new_expiring( common, i * spacing ) ).collect()
+}
+
+fn char_stat ( spacing: u64 ) -> CharStat {
+	let bounds = Bounds::unbounded();
+	let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	
+	for modifier in modifiers( spacing ) {
+		cs.append_modifier( modifier ).unwrap();
+	}
+	
+	cs.flush();
+	
+	cs
+}
+
+/// implementation used before the expiry index
+fn naive_remove_expired ( mod_vec: &mut Vec< Modifier >, ts: u64 ) {
+	for i in ( 0..mod_vec.len() ).rev() {
+		if mod_vec[ i ].has_expired( ts ) {
+			mod_vec.remove( i );
+		}
+	}// for
+}
+
+fn run_naive ( spacing: u64 ) -> Duration {
+	let mut mod_vec = modifiers( spacing );
+	let start = Instant::now();
+	
+	for ts in 0..FRAMES {
+		naive_remove_expired( black_box( &mut mod_vec ), ts );
+	}// for
+	
+	start.elapsed()
+}
+
+fn run_indexed ( spacing: u64 ) -> Duration {
+	let mut cs = char_stat( spacing );
+	let start = Instant::now();
+	
+	for ts in 0..FRAMES {
+		black_box( &mut cs ).set_ts( ts ).unwrap();
+	}// for
+	
+	cs.flush();
+	black_box( cs.value() );
+	
+	start.elapsed()
+}
+
+// --Helpers
+//------------------------------------------------------------------------------
+// --Main
+
+fn main () {
+	println!( "{MODIFIERS} modifiers, {FRAMES} frames" );
+	
+	// nothing expires during the run, e.g. long buffs
+	// one modifier expires every frame
+	// one modifier expires every 10th frame
+	for ( name, spacing ) in [ ( "no expiration", FRAMES ), ( "every frame", 1 ), ( "every 10 frames", 10 ) ] {
+		let naive = run_naive( spacing );
+		let indexed = run_indexed( spacing );
+		
+		println!( "{name:>16}: naive {naive:>12?} | indexed {indexed:>12?}" );
+	}// for
+}
+
+// --Main
+//------------------------------------------------------------------------------
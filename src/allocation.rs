@@ -0,0 +1,322 @@
+use std::cmp::Ordering;
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStat, CharStatError, CsInvalidValue, CsLogicIssue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - CostTable
+
+/// Point-buy cost of each allowed score. Scores missing from the table cannot be bought.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub struct CostTable {
+	costs: Vec< ( f64, u32 ) >,
+}
+
+impl CostTable {
+	/// `costs` is a list of ( score, cost ) pairs, order does not matter.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any score is `f64::NAN` <br>
+	/// `CsInvalidValue::NotAllowed` when a score is listed more than once <br>
+	#[inline]
+	pub fn new ( mut costs: Vec< ( f64, u32 ) > ) -> Result< Self, CharStatError > {
+		if costs.iter().any( |( score, _ )| score.is_nan() ) {
+			return Err( CsInvalidValue::Nan( "score".to_string() ).into() )
+		}
+		
+		costs.sort_by( |a, b| a.0.total_cmp( &b.0 ) );
+		
+		if costs.windows( 2 ).any( |w| w[ 0 ].0.total_cmp( &w[ 1 ].0 ) == Ordering::Equal ) {
+			return Err( CsInvalidValue::NotAllowed( "score".to_string() ).into() )
+		}
+		
+		Ok( CostTable { costs } )
+	}
+	
+	/// DnD 5e point-buy: scores 8 to 15, 14 and 15 cost 2 points each.
+	#[inline]
+	pub fn dnd_5e () -> Self {
+		CostTable {
+			costs: vec![
+				( 8.0, 0 ), ( 9.0, 1 ), ( 10.0, 2 ), ( 11.0, 3 ),
+				( 12.0, 4 ), ( 13.0, 5 ), ( 14.0, 7 ), ( 15.0, 9 ),
+			],
+		}
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `score` is outside of the table <br>
+	/// `CsInvalidValue::NotAllowed` when `score` is within the table range, but not listed <br>
+	#[inline]
+	pub fn cost ( &self, score: f64 ) -> Result< u32, CharStatError > {
+		if let Some( ( _, cost ) ) = self.costs.iter().find( |( s, _ )| s.total_cmp( &score ) == Ordering::Equal ) {
+			return Ok( *cost )
+		}
+		
+		match ( self.costs.first(), self.costs.last() ) {
+			( Some( ( min, _ ) ), _ ) if score < *min => Err( CsInvalidValue::BelowMinimum( "score".to_string() ).into() ),
+			( _, Some( ( max, _ ) ) ) if score > *max => Err( CsInvalidValue::AboveMaximum( "score".to_string() ).into() ),
+			_ => Err( CsInvalidValue::NotAllowed( "score".to_string() ).into() ),
+		}
+	}
+}
+
+// struct - CostTable
+//------------------------------------------------------------------------------
+// enum - AllocationRule
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub enum AllocationRule {
+	/// every score is bought from `table`, the sum of costs cannot exceed `budget`
+	PointBuy{ table: CostTable, budget: u32 },
+	/// scores must be a permutation of the array
+	FixedArray( Vec< f64 > ),
+}
+
+// enum - AllocationRule
+//------------------------------------------------------------------------------
+// struct - Allocation
+
+/// Validates and applies base values chosen during character creation.
+/// 
+/// Proposed values are matched by index with the `CharStat`s they are applied to.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ Allocation, CharStat, BaseConf, Bounds, RoundingHelper };
+/// 
+/// let bounds = Bounds::new_const( 3.0, 18.0 ).unwrap();
+/// let base = BaseConf::new( 8.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+/// let mut stats = vec![ CharStat::new_minimal( base ); 6 ];
+/// 
+/// let point_buy = Allocation::dnd_5e_point_buy();
+/// 
+/// // 9 + 9 + 5 + 2 + 1 + 0 = 26 out of 27 points
+/// let remaining = point_buy.apply( &mut stats, &[ 15.0, 15.0, 13.0, 10.0, 9.0, 8.0 ] ).unwrap();
+/// 
+/// assert_eq!( remaining, 1 );
+/// assert_eq!( stats[ 0 ].value(), 15.0 );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub struct Allocation {
+	rule: AllocationRule,
+}
+
+impl Allocation {
+	#[inline]
+	pub fn new_point_buy ( table: CostTable, budget: u32 ) -> Self {
+		Allocation {
+			rule: AllocationRule::PointBuy { table, budget },
+		}
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any value is `f64::NAN` <br>
+	#[inline]
+	pub fn new_fixed_array ( values: Vec< f64 > ) -> Result< Self, CharStatError > {
+		if values.iter().any( |v| v.is_nan() ) {
+			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
+		}
+		
+		Ok( Allocation {
+			rule: AllocationRule::FixedArray( values ),
+		})
+	}
+	
+	/// `CostTable::dnd_5e` with a budget of 27 points
+	#[inline]
+	pub fn dnd_5e_point_buy () -> Self {
+		Allocation::new_point_buy( CostTable::dnd_5e(), 27 )
+	}
+	
+	/// 15, 14, 13, 12, 10, 8
+	#[inline]
+	pub fn dnd_5e_standard_array () -> Self {
+		Allocation {
+			rule: AllocationRule::FixedArray( vec![ 15.0, 14.0, 13.0, 12.0, 10.0, 8.0 ] ),
+		}
+	}
+	
+	#[inline]
+	pub fn rule ( &self ) -> &AllocationRule {
+		&self.rule
+	}
+	
+	/// returns the budget, fixed arrays have none
+	#[inline]
+	pub fn budget ( &self ) -> Option< u32 > {
+		if let AllocationRule::PointBuy { budget, .. } = self.rule {
+			return Some( budget )
+		}
+		
+		None
+	}
+	
+	/// Returns the points left after buying `values`, always 0 for fixed arrays.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::AboveMaximum( "cost" )` when `values` cost more than the budget <br>
+	/// `CsInvalidValue::NotAllowed( "values" )` when `values` are not a permutation of the fixed array <br>
+	/// errors from `CostTable::cost` <br>
+	#[inline]
+	pub fn remaining ( &self, values: &[ f64 ] ) -> Result< u32, CharStatError > {
+		match &self.rule {
+			AllocationRule::PointBuy { table, budget } => {
+				let mut spent: u32 = 0;
+				
+				for val in values {
+					spent = spent.saturating_add( table.cost( *val )? );
+				}
+				
+				budget.checked_sub( spent ).ok_or_else( || CsInvalidValue::AboveMaximum( "cost".to_string() ).into() )
+			},
+			AllocationRule::FixedArray( array ) => {
+				let mut expected = array.clone();
+				let mut found = values.to_vec();
+				
+				expected.sort_by( f64::total_cmp );
+				found.sort_by( f64::total_cmp );
+				
+				let is_permutation = expected.len() == found.len()
+					&& expected.iter().zip( &found ).all( |( a, b )| a.total_cmp( b ) == Ordering::Equal );
+				
+				if !is_permutation {
+					return Err( CsInvalidValue::NotAllowed( "values".to_string() ).into() )
+				}
+				
+				Ok( 0 )
+			},
+		}
+	}
+	
+	/// Checks `values` against the rule and base bounds of each stat, without applying them.
+	/// Returns remaining points.
+	/// 
+	/// # Errors
+	/// `CsLogicIssue::LengthMismatch` when `values` and `stats` differ in length <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when a value is not within its' stat base bounds <br>
+	/// errors from `remaining` <br>
+	#[inline]
+	pub fn validate ( &self, stats: &[ CharStat ], values: &[ f64 ] ) -> Result< u32, CharStatError > {
+		if stats.len() != values.len() {
+			return Err( CsLogicIssue::LengthMismatch( values.len(), stats.len() ).into() )
+		}
+		
+		for ( stat, val ) in stats.iter().zip( values ) {
+			if *val < stat.base_bounds_min() {
+				return Err( CsInvalidValue::BelowMinimum( "value".to_string() ).into() )
+			}
+			
+			if *val > stat.base_bounds_max() {
+				return Err( CsInvalidValue::AboveMaximum( "value".to_string() ).into() )
+			}
+		}
+		
+		self.remaining( values )
+	}
+	
+	/// Validates `values` and sets them as base values of `stats` via `CharStat::set_base_value`.
+	/// Either every value is applied or none. Returns remaining points.
+	/// 
+	/// # Errors
+	/// errors from `validate` <br>
+	/// errors from `CharStat::set_base_value`, e.g. `CsLogicIssue::FieldIsConst` <br>
+	#[inline]
+	pub fn apply ( &self, stats: &mut [ CharStat ], values: &[ f64 ] ) -> Result< u32, CharStatError > {
+		let remaining = self.validate( stats, values )?;
+		
+		let mut updated = stats.to_vec();
+		
+		for ( stat, val ) in updated.iter_mut().zip( values ) {
+			stat.set_base_value( *val )?;
+		}
+		
+		stats.clone_from_slice( &updated );
+		
+		Ok( remaining )
+	}
+}
+
+// struct - Allocation
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	use crate::{ BaseConf, Bounds, RoundingHelper };
+	
+	fn stats( n: usize, min: f64, max: f64 ) -> Vec< CharStat > {
+		let bounds = Bounds::new_const( min, max ).unwrap();
+		let base = BaseConf::new( min, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		
+		vec![ CharStat::new_minimal( base ); n ]
+	}
+	
+	#[test]
+	fn point_buy() {
+		let alloc = Allocation::dnd_5e_point_buy();
+		let mut cs = stats( 3, 3.0, 18.0 );
+		
+		assert_eq!( alloc.remaining( &[ 15.0, 15.0, 15.0 ] ), Ok( 0 ) );
+		assert_eq!( alloc.apply( &mut cs, &[ 14.0, 12.0, 8.0 ] ), Ok( 16 ) );
+		assert_eq!( cs[ 1 ].value(), 12.0 );
+		
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "cost".to_string() ).into();
+		let over_budget = [ 15.0, 15.0, 15.0, 9.0 ];
+		assert_eq!( alloc.remaining( &over_budget ), Err( expected ) );
+		
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "score".to_string() ).into();
+		assert_eq!( alloc.apply( &mut cs, &[ 16.0, 8.0, 8.0 ] ), Err( expected ) );
+		
+		let expected: CharStatError = CsLogicIssue::LengthMismatch( 2, 3 ).into();
+		assert_eq!( alloc.apply( &mut cs, &[ 8.0, 8.0 ] ), Err( expected ) );
+		
+		// failed attempts leave stats untouched
+		assert_eq!( cs[ 0 ].value(), 14.0 );
+		
+		let table = CostTable::new( vec![ ( 10.0, 0 ), ( 12.0, 1 ) ] ).unwrap();
+		let expected: CharStatError = CsInvalidValue::NotAllowed( "score".to_string() ).into();
+		assert_eq!( table.cost( 11.0 ), Err( expected ) );
+	}
+	
+	#[test]
+	fn bounds_and_arrays() {
+		let alloc = Allocation::dnd_5e_standard_array();
+		let mut cs = stats( 6, 8.0, 14.0 );
+		
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "value".to_string() ).into();
+		assert_eq!( alloc.apply( &mut cs, &[ 15.0, 14.0, 13.0, 12.0, 10.0, 8.0 ] ), Err( expected ) );
+		assert_eq!( cs[ 0 ].value(), 8.0 );
+		
+		let expected: CharStatError = CsInvalidValue::NotAllowed( "values".to_string() ).into();
+		assert_eq!( alloc.remaining( &[ 14.0, 14.0, 13.0, 12.0, 10.0, 8.0 ] ), Err( expected ) );
+		
+		let mut cs = stats( 6, 3.0, 18.0 );
+		assert_eq!( alloc.apply( &mut cs, &[ 8.0, 10.0, 12.0, 13.0, 14.0, 15.0 ] ), Ok( 0 ) );
+		assert_eq!( cs[ 5 ].value(), 15.0 );
+		
+		// const base is refused without modifying any stat
+		let bounds = Bounds::new_const( 3.0, 18.0 ).unwrap();
+		let base = BaseConf::new( 3.0, false, bounds, RoundingHelper::new_none(), None ).unwrap();
+		cs[ 5 ] = CharStat::new_minimal( base );
+		
+		assert_eq!( alloc.apply( &mut cs, &[ 15.0, 14.0, 13.0, 12.0, 10.0, 8.0 ] ), Err( CsLogicIssue::FieldIsConst.into() ) );
+		assert_eq!( cs[ 0 ].value(), 8.0 );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -5,7 +5,7 @@ use serde::{ Serialize, Deserialize };
 //------------------------------------------------------------------------------
 // --Modules
 
-use crate::{ Bounds, BaseMultConf, RoundingHelper, CharStatError, CsInvalidValue, CsLogicIssue, CsMissingComponent };
+use crate::{ Shared, Bounds, BaseMultConf, RoundingHelper, CharStatError, CsInvalidValue, CsLogicIssue, CsMissingComponent };
 
 // --Modules
 //------------------------------------------------------------------------------
@@ -17,9 +17,9 @@ use crate::{ Bounds, BaseMultConf, RoundingHelper, CharStatError, CsInvalidValue
 pub struct BaseConf {
 	value: f64,
 	is_mut: bool,
-	bounds: Bounds,
-	rounding_fn: RoundingHelper,
-	mult: Option< Box< BaseMultConf > >,
+	bounds: Shared< Bounds >,
+	rounding_fn: Shared< RoundingHelper >,
+	mult: Option< Shared< BaseMultConf > >,
 }
 
 impl BaseConf {
@@ -27,9 +27,13 @@ impl BaseConf {
 	/// 
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `bounds` <br>
 	#[inline]
-	pub fn new ( value: f64, is_mut: bool, bounds: Bounds, rounding_fn: RoundingHelper, mult: Option< BaseMultConf > ) -> Result< Self, CharStatError > {
+	pub fn new ( value: f64, is_mut: bool, bounds: impl Into< Shared< Bounds > >, rounding_fn: impl Into< Shared< RoundingHelper > >, mult: Option< BaseMultConf > ) -> Result< Self, CharStatError > {
+		let bounds: Shared< Bounds > = bounds.into();
+		let rounding_fn: Shared< RoundingHelper > = rounding_fn.into();
+		
 		BaseConf::check_inval( value, &bounds )?;
 		
 		Ok( BaseConf {
@@ -37,7 +41,7 @@ impl BaseConf {
 			is_mut,
 			bounds,
 			rounding_fn,
-			mult: mult.map( Box::new ),
+			mult: mult.map( Shared::new ),
 		})
 	}
 	
@@ -46,8 +50,12 @@ impl BaseConf {
 	/// 
 	/// # Errors
 	/// CsInvalidValue::Nan when `value` is `f64::NAN` <br>
+	/// CsInvalidValue::Infinite when `value` is infinite <br>
 	#[inline]
-	pub fn new_clamping ( value: f64, is_mut: bool, bounds: Bounds, rounding_fn: RoundingHelper, mult: Option< BaseMultConf > ) -> Result< Self, CharStatError > {
+	pub fn new_clamping ( value: f64, is_mut: bool, bounds: impl Into< Shared< Bounds > >, rounding_fn: impl Into< Shared< RoundingHelper > >, mult: Option< BaseMultConf > ) -> Result< Self, CharStatError > {
+		let bounds: Shared< Bounds > = bounds.into();
+		let rounding_fn: Shared< RoundingHelper > = rounding_fn.into();
+		
 		BaseConf::check_nan( value )?;
 		
 		Ok( BaseConf {
@@ -55,12 +63,13 @@ impl BaseConf {
 			is_mut,
 			bounds,
 			rounding_fn,
-			mult: mult.map( Box::new ),
+			mult: mult.map( Shared::new ),
 		})
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `self.bounds` <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_mut` is false <br>
 	#[inline]
@@ -80,6 +89,7 @@ impl BaseConf {
 	/// 
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_mut` is false <br>
 	#[inline]
 	pub fn set_value_clamping ( &mut self, value: f64 ) -> Result<(), CharStatError > {
@@ -105,6 +115,32 @@ impl BaseConf {
 		self.rounding_fn.do_rounding( out )
 	}
 	
+	/// stored value, without multiplier and rounding
+	#[inline]
+	pub fn stored_value ( &self ) -> f64 {
+		self.value
+	}
+	
+	#[inline]
+	pub fn is_mut ( &self ) -> bool {
+		self.is_mut
+	}
+	
+	#[inline]
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	#[inline]
+	pub fn mult ( &self ) -> Option< &BaseMultConf > {
+		self.mult.as_deref()
+	}
+	
 	/// disables mutability of `self.value`
 	#[inline]
 	pub fn set_value_const ( &mut self ) {
@@ -112,8 +148,17 @@ impl BaseConf {
 	}
 	
 	#[inline]
-	pub fn set_rounding ( &mut self, new_val: RoundingHelper ) {
-		self.rounding_fn = new_val;
+	pub fn set_rounding ( &mut self, new_val: impl Into< Shared< RoundingHelper > > ) {
+		self.rounding_fn = new_val.into();
+	}
+	
+	/// Sets ( or removes with `None` ) multiplier shared with other components, changing the exponent copies it.
+	#[inline]
+	#[must_use]
+	pub fn with_shared_mult ( mut self, mult: Option< Shared< BaseMultConf > > ) -> Self {
+		self.mult = mult;
+		
+		self
 	}
 }
 
@@ -125,7 +170,7 @@ impl BaseConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_min( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_min( new_val ) )?;
 		
 		Ok(())
 	}
@@ -136,19 +181,19 @@ impl BaseConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_max( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_max( new_val ) )?;
 		
 		Ok(())
 	}
 	
 	#[inline]
 	pub fn set_bounds_min_const ( &mut self ) {
-		self.bounds.set_min_const();
+		self.bounds.update( Bounds::set_min_const );
 	}
 	
 	#[inline]
 	pub fn set_bounds_max_const ( &mut self ) {
-		self.bounds.set_max_const();
+		self.bounds.update( Bounds::set_max_const );
 	}
 	
 	#[inline]
@@ -166,12 +211,14 @@ impl BaseConf {
 impl BaseConf {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `new_val` is not within `self.bounds` <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
 	pub fn set_mult_base ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		if let Some( mlt ) = &mut self.mult {
-			mlt.set_base( new_val )?;
+			mlt.try_update( |tmp| tmp.set_base( new_val ) )?;
 			
 			return Ok(())
 		}
@@ -181,12 +228,14 @@ impl BaseConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `new_val` is not within `self.bounds` <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
 	pub fn set_mult_exponent ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		if let Some( mlt ) = &mut self.mult {
-			mlt.set_exponent( new_val )?;
+			mlt.try_update( |tmp| tmp.set_exponent( new_val ) )?;
 			
 			return Ok(())
 		}
@@ -196,11 +245,13 @@ impl BaseConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
 	pub fn set_mult_base_clamping ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		if let Some( mlt ) = &mut self.mult {
-			mlt.set_base_clamping( new_val )?;
+			mlt.try_update( |tmp| tmp.set_base_clamping( new_val ) )?;
 			
 			return Ok(())
 		}
@@ -210,11 +261,13 @@ impl BaseConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
 	pub fn set_mult_exponent_clamping ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		if let Some( mlt ) = &mut self.mult {
-			mlt.set_exponent_clamping( new_val )?;
+			mlt.try_update( |tmp| tmp.set_exponent_clamping( new_val ) )?;
 			
 			return Ok(())
 		}
@@ -223,15 +276,32 @@ impl BaseConf {
 	}
 }// mult
 
-//priv
+// pub-crate
 impl BaseConf {
+	/// constructor for a value already checked by `check_inval`, without rounding and multiplier
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn new_unchecked ( value: f64, is_mut: bool, bounds: Bounds ) -> Self {
+		BaseConf {
+			value,
+			is_mut,
+			bounds: Shared::new( bounds ),
+			rounding_fn: Shared::new( RoundingHelper::new_none() ),
+			mult: None,
+		}
+	}
+	
 	#[inline( always )]
 	#[doc( hidden )]
-	fn check_inval( value: f64, bounds: &Bounds ) -> Result<(), CharStatError > {
+	pub( crate ) fn check_inval( value: f64, bounds: &Bounds ) -> Result<(), CharStatError > {
 		if value.is_nan() {
 			
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
 		if value < bounds.min() {
 			
 			//return Err( CsInvalidValue::BelowMinimum( "value".to_string() ).into() )
@@ -247,15 +317,19 @@ impl BaseConf {
 	
 	#[inline( always )]
 	#[doc( hidden )]
-	fn check_nan( value: f64 ) -> Result<(), CharStatError > {
+	pub( crate ) fn check_nan( value: f64 ) -> Result<(), CharStatError > {
 		if value.is_nan() {
 			
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
 		
 		Ok(())
 	}
-}// priv
+}// pub-crate
 
 // struct - BaseConf
 //------------------------------------------------------------------------------
@@ -263,6 +337,8 @@ impl BaseConf {
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use crate::{ BaseConf, Bounds, RoundingFnEnum, RoundingHelper, CharStatError, CsInvalidValue };
 	
 	#[test]
@@ -291,9 +367,9 @@ mod tests {
 		let expected: CharStatError = CsInvalidValue::Nan( "value".to_string() ).into();
 		let bounds = Bounds::new_const( 0.0, 1.0 ).unwrap();
 		
-		let base = BaseConf::new( f64::NAN, true, bounds.clone(), RoundingHelper::default(), None );
+		let base = BaseConf::new( f64::NAN, true, bounds, RoundingHelper::default(), None );
 		assert_eq!( base, Err( expected.clone() ) );
-		let base = BaseConf::new_clamping( f64::NAN, true, bounds.clone(), RoundingHelper::default(), None );
+		let base = BaseConf::new_clamping( f64::NAN, true, bounds, RoundingHelper::default(), None );
 		assert_eq!( base, Err( expected ) );
 	}
 }
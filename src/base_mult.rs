@@ -28,13 +28,15 @@ pub struct BaseMultConf {
 impl BaseMultConf {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when either `base` or `exponent` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when either `base` or `exponent` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when either `base` or `exponent` is not within its' `bounds` <br>
 	#[inline]
 	pub fn new ( base: f64, exponent: f64, bounds_base: Bounds, bounds_exp: Bounds, rounding_fn: RoundingHelper ) -> Result< Self, CharStatError > {
 		BaseMultConf::check_inval( base, &bounds_base, "base".to_string() )?;
 		BaseMultConf::check_inval( exponent, &bounds_exp, "exponent".to_string() )?;
 		
-		let multiplier = base.powf( exponent );
+		let multiplier = BaseMultConf::calc_multiplier( base, exponent, &rounding_fn )?;
 		
 		Ok( BaseMultConf {
 			base,
@@ -48,6 +50,8 @@ impl BaseMultConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when either `base` or `exponent` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when either `base` or `exponent` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	#[inline]
 	pub fn new_clamping ( mut base: f64, mut exponent: f64, bounds_base: Bounds, bounds_exp: Bounds, rounding_fn: RoundingHelper ) -> Result< Self, CharStatError > {
 		BaseMultConf::check_nan( base, "base".to_string() )?;
@@ -56,7 +60,7 @@ impl BaseMultConf {
 		base = base.clamp( bounds_base.min(), bounds_base.max() );
 		exponent = exponent.clamp( bounds_exp.min(), bounds_exp.max() );
 		
-		let multiplier = base.powf( exponent );
+		let multiplier = BaseMultConf::calc_multiplier( base, exponent, &rounding_fn )?;
 		
 		Ok( BaseMultConf {
 			base,
@@ -73,27 +77,64 @@ impl BaseMultConf {
 		value * self.multiplier
 	}
 	
+	#[inline]
+	pub fn base ( &self ) -> f64 {
+		self.base
+	}
+	
+	#[inline]
+	pub fn exponent ( &self ) -> f64 {
+		self.exponent
+	}
+	
+	/// `base` raised to `exponent`, rounded
+	#[inline]
+	pub fn multiplier ( &self ) -> f64 {
+		self.multiplier
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	#[inline]
+	pub fn bounds_base ( &self ) -> &Bounds {
+		&self.bounds_base
+	}
+	
+	#[inline]
+	pub fn bounds_exp ( &self ) -> &Bounds {
+		&self.bounds_exp
+	}
+	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `base` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `base` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `base` is not within `self.bounds_base` <br>
 	#[inline]
 	pub fn set_base ( &mut self, base: f64 ) -> Result<(), CharStatError > {
 		BaseMultConf::check_inval( base, &self.bounds_base, "base".to_string() )?;
 		
+		self.multiplier = BaseMultConf::calc_multiplier( base, self.exponent, &self.rounding_fn )?;
 		self.base = base;
-		self.update();
 		
 		Ok(())
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `base` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `base` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	#[inline]
 	pub fn set_base_clamping ( &mut self, base: f64 ) -> Result<(), CharStatError > {
 		BaseMultConf::check_nan( base, "base".to_string() )?;
 		
-		self.base = base.clamp( self.bounds_base.min(), self.bounds_base.max() );
-		self.update();
+		let base = base.clamp( self.bounds_base.min(), self.bounds_base.max() );
+		
+		self.multiplier = BaseMultConf::calc_multiplier( base, self.exponent, &self.rounding_fn )?;
+		self.base = base;
 		
 		Ok(())
 	}
@@ -118,25 +159,31 @@ impl BaseMultConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `exponent` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `exponent` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `exponent` is not within `self.bounds_exponent` <br>
 	#[inline]
 	pub fn set_exponent ( &mut self, exponent: f64 ) -> Result<(), CharStatError > {
 		BaseMultConf::check_inval( exponent, &self.bounds_exp, "exponent".to_string() )?;
 		
+		self.multiplier = BaseMultConf::calc_multiplier( self.base, exponent, &self.rounding_fn )?;
 		self.exponent = exponent;
-		self.update();
 		
 		Ok(())
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `exponent` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `exponent` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	#[inline]
 	pub fn set_exponent_clamping ( &mut self, exponent: f64 ) -> Result<(), CharStatError > {
 		BaseMultConf::check_nan( exponent, "exponent".to_string() )?;
 		
-		self.exponent = exponent.clamp( self.bounds_exp.min(), self.bounds_exp.max() );
-		self.update();
+		let exponent = exponent.clamp( self.bounds_exp.min(), self.bounds_exp.max() );
+		
+		self.multiplier = BaseMultConf::calc_multiplier( self.base, exponent, &self.rounding_fn )?;
+		self.exponent = exponent;
 		
 		Ok(())
 	}
@@ -174,6 +221,10 @@ impl BaseMultConf {
 			
 			return Err( CsInvalidValue::Nan( name  ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( name  ).into() )
+		}
 		if value < bounds.min() {
 			
 			return Err( CsInvalidValue::BelowMinimum( name  ).into() )
@@ -193,17 +244,30 @@ impl BaseMultConf {
 			
 			return Err( CsInvalidValue::Nan( name  ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( name  ).into() )
+		}
 		
 		Ok(())
 	}
 	
 	#[inline(always)]
 	#[doc(hidden)]
-	fn update ( &mut self ) {
-		let val = self.base.powf( self.exponent );
-		let mlt = self.rounding_fn.do_rounding( val );
+	fn calc_multiplier ( base: f64, exponent: f64, rounding_fn: &RoundingHelper ) -> Result< f64, CharStatError > {
+		let val = base.powf( exponent );
 		
-		self.multiplier = mlt;
+		if !val.is_finite() {
+			
+			return Err( CsInvalidValue::Infinite( "multiplier".to_string() ).into() )
+		}
+		// underflow
+		if val == 0.0 && base != 0.0 {
+			
+			return Err( CsInvalidValue::CannotBeZero( "multiplier".to_string() ).into() )
+		}
+		
+		Ok( rounding_fn.do_rounding( val ) )
 	}
 }// priv
 
@@ -213,6 +277,8 @@ impl BaseMultConf {
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use crate::{ BaseConf, BaseMultConf, Bounds, CharStatError, CsInvalidValue, RoundingHelper, RoundingFnEnum };
 	
 	#[test]
@@ -221,30 +287,30 @@ mod tests {
 		let rnd_hlp = RoundingHelper::new_none();
 		
 		// new
-		let bad = BaseMultConf::new( -10.0, 0.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let bad = BaseMultConf::new( -10.0, 0.0, bounds, bounds, rnd_hlp.clone() );
 		let expected: CharStatError = CsInvalidValue::BelowMinimum( "base".to_string() ).into();
 		assert_eq!( bad, Err( expected ) );
 		
-		let bad = BaseMultConf::new( 10.0, 0.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let bad = BaseMultConf::new( 10.0, 0.0, bounds, bounds, rnd_hlp.clone() );
 		let expected: CharStatError = CsInvalidValue::AboveMaximum( "base".to_string() ).into();
 		assert_eq!( bad, Err( expected ) );
 		
-		let bad = BaseMultConf::new( 0.0, -10.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let bad = BaseMultConf::new( 0.0, -10.0, bounds, bounds, rnd_hlp.clone() );
 		let expected: CharStatError = CsInvalidValue::BelowMinimum( "exponent".to_string() ).into();
 		assert_eq!( bad, Err( expected ) );
 		
-		let bad = BaseMultConf::new( 0.0, 10.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let bad = BaseMultConf::new( 0.0, 10.0, bounds, bounds, rnd_hlp.clone() );
 		let expected: CharStatError = CsInvalidValue::AboveMaximum( "exponent".to_string() ).into();
 		assert_eq!( bad, Err( expected ) );
 		
 		// new_clamping
-		let correct_min = BaseMultConf::new( 0.0, 0.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() ).unwrap();
-		let correct_max = BaseMultConf::new( 1.0, 1.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() ).unwrap();
+		let correct_min = BaseMultConf::new( 0.0, 0.0, bounds, bounds, rnd_hlp.clone() ).unwrap();
+		let correct_max = BaseMultConf::new( 1.0, 1.0, bounds, bounds, rnd_hlp.clone() ).unwrap();
 		
-		let clamped = BaseMultConf::new_clamping( -10.0, -10.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let clamped = BaseMultConf::new_clamping( -10.0, -10.0, bounds, bounds, rnd_hlp.clone() );
 		assert_eq!( clamped, Ok( correct_min ) );
 		
-		let clamped = BaseMultConf::new_clamping( 10.0, 10.0, bounds.clone(), bounds.clone(), rnd_hlp.clone() );
+		let clamped = BaseMultConf::new_clamping( 10.0, 10.0, bounds, bounds, rnd_hlp.clone() );
 		assert_eq!( clamped, Ok( correct_max ) );
 	}
 	
@@ -260,10 +326,10 @@ mod tests {
 		let expected = [ 500.0, 550.0, 605.0, 665.0, 732.0, 805.0, 885.0, 974.0, 1071.0, 1178.0, 1296.0 ];
 		
 		let mut i = 1.0;
-		for idx in 1..=10 {
+		for exp in expected.iter().skip( 1 ) {
 			base.set_mult_exponent( i ).unwrap();
 			
-			assert_eq!( base.value(), expected[ idx ] );
+			assert_eq!( base.value(), *exp );
 			
 			i += 1.0;
 		}
@@ -274,15 +340,36 @@ mod tests {
 		let bounds = Bounds::new_const( 0.0, 1.0 ).unwrap();
 		
 		// base
-		let base_mult = BaseMultConf::new( f64::NAN, 1.0, bounds.clone(), bounds.clone(), RoundingHelper::default() );
+		let base_mult = BaseMultConf::new( f64::NAN, 1.0, bounds, bounds, RoundingHelper::default() );
 		let expected: CharStatError = CsInvalidValue::Nan( "base".to_string() ).into();
 		assert_eq!( base_mult, Err( expected ) );
 		
 		// exponent
-		let base_mult = BaseMultConf::new( 1.0, f64::NAN, bounds.clone(), bounds.clone(), RoundingHelper::default() );
+		let base_mult = BaseMultConf::new( 1.0, f64::NAN, bounds, bounds, RoundingHelper::default() );
 		let expected: CharStatError = CsInvalidValue::Nan( "exponent".to_string() ).into();
 		assert_eq!( base_mult, Err( expected ) );
 	}
+	
+	#[test]
+	fn infinity_handling() {
+		let bounds = Bounds::unbounded();
+		
+		let base_mult = BaseMultConf::new( f64::INFINITY, 1.0, bounds, bounds, RoundingHelper::default() );
+		let expected: CharStatError = CsInvalidValue::Infinite( "base".to_string() ).into();
+		assert_eq!( base_mult, Err( expected ) );
+		
+		// overflow
+		let base_mult = BaseMultConf::new( 10.0, 400.0, bounds, bounds, RoundingHelper::default() );
+		let expected: CharStatError = CsInvalidValue::Infinite( "multiplier".to_string() ).into();
+		assert_eq!( base_mult, Err( expected.clone() ) );
+		
+		// underflow
+		let mut base_mult = BaseMultConf::new( 10.0, 1.0, bounds, bounds, RoundingHelper::default() ).unwrap();
+		let expected_zero: CharStatError = CsInvalidValue::CannotBeZero( "multiplier".to_string() ).into();
+		assert_eq!( base_mult.set_exponent( -400.0 ), Err( expected_zero ) );
+		assert_eq!( base_mult.set_exponent_clamping( 400.0 ), Err( expected ) );
+		assert_eq!( base_mult.calculate( 2.0 ), 20.0 );
+	}
 }
 
 // --Tests
@@ -0,0 +1,587 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStat, BaseConf, UpgradeConf, Modifier, StatReport, CharStatError, CsLogicIssue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - StageRow
+
+/// Stage values of a flushed `CharStat`, `StatBatch` keeps each of them in its own column.
+#[derive( Debug, Clone, Copy, PartialEq, Default )]
+pub( crate ) struct StageRow {
+	pub( crate ) value: f64,
+	pub( crate ) base: f64,
+	pub( crate ) base_mod: f64,
+	pub( crate ) upgrade: f64,
+	pub( crate ) upgrade_mod: f64,
+	pub( crate ) base_plus_upgrade_mod: f64,
+	pub( crate ) mod_mult: f64,
+}
+
+/// columns of `StageRow`
+#[derive( Debug, Clone, PartialEq, Default )]
+struct StageColumns {
+	value: Vec< f64 >,
+	base: Vec< f64 >,
+	base_mod: Vec< f64 >,
+	upgrade: Vec< f64 >,
+	upgrade_mod: Vec< f64 >,
+	base_plus_upgrade_mod: Vec< f64 >,
+	mod_mult: Vec< f64 >,
+}
+
+impl StageColumns {
+	#[inline]
+	fn with_capacity ( capacity: usize ) -> Self {
+		StageColumns {
+			value: Vec::with_capacity( capacity ),
+			base: Vec::with_capacity( capacity ),
+			base_mod: Vec::with_capacity( capacity ),
+			upgrade: Vec::with_capacity( capacity ),
+			upgrade_mod: Vec::with_capacity( capacity ),
+			base_plus_upgrade_mod: Vec::with_capacity( capacity ),
+			mod_mult: Vec::with_capacity( capacity ),
+		}
+	}
+	
+	#[inline]
+	fn get ( &self, idx: usize ) -> StageRow {
+		StageRow {
+			value: self.value[ idx ],
+			base: self.base[ idx ],
+			base_mod: self.base_mod[ idx ],
+			upgrade: self.upgrade[ idx ],
+			upgrade_mod: self.upgrade_mod[ idx ],
+			base_plus_upgrade_mod: self.base_plus_upgrade_mod[ idx ],
+			mod_mult: self.mod_mult[ idx ],
+		}
+	}
+	
+	#[inline]
+	fn set ( &mut self, idx: usize, row: StageRow ) {
+		self.value[ idx ] = row.value;
+		self.base[ idx ] = row.base;
+		self.base_mod[ idx ] = row.base_mod;
+		self.upgrade[ idx ] = row.upgrade;
+		self.upgrade_mod[ idx ] = row.upgrade_mod;
+		self.base_plus_upgrade_mod[ idx ] = row.base_plus_upgrade_mod;
+		self.mod_mult[ idx ] = row.mod_mult;
+	}
+	
+	#[inline]
+	fn push ( &mut self, row: StageRow ) {
+		self.value.push( row.value );
+		self.base.push( row.base );
+		self.base_mod.push( row.base_mod );
+		self.upgrade.push( row.upgrade );
+		self.upgrade_mod.push( row.upgrade_mod );
+		self.base_plus_upgrade_mod.push( row.base_plus_upgrade_mod );
+		self.mod_mult.push( row.mod_mult );
+	}
+	
+	#[inline]
+	fn swap_remove ( &mut self, idx: usize ) -> StageRow {
+		StageRow {
+			value: self.value.swap_remove( idx ),
+			base: self.base.swap_remove( idx ),
+			base_mod: self.base_mod.swap_remove( idx ),
+			upgrade: self.upgrade.swap_remove( idx ),
+			upgrade_mod: self.upgrade_mod.swap_remove( idx ),
+			base_plus_upgrade_mod: self.base_plus_upgrade_mod.swap_remove( idx ),
+			mod_mult: self.mod_mult.swap_remove( idx ),
+		}
+	}
+}
+
+// struct - StageRow
+//------------------------------------------------------------------------------
+// struct - StatBatch
+
+/// Storage for a large number of `CharStat` sharing one timestamp, e.g. stats of every NPC.
+/// 
+/// Base, upgrade, stage values, current values and the lowest expiration timestamp of each stat are stored in columns.
+/// `set_ts` scans the expiration column and touches only stats with an expiring modifier,
+/// `values`, `base_values` and `upgrade_values` return a slice without reading any `CharStat`. <br>
+/// Modifiers and the remaining configuration stay in a `CharStat` per stat, base, upgrade and stage values
+/// are moved back into it only while it is recalculated.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ StatBatch, CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+/// 
+/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+/// let stats = ( 0..3 ).map( |i| {
+///     let base = BaseConf::new( 10.0 * f64::from( i ), false, bounds, RoundingHelper::new_none(), None ).unwrap();
+///     let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+///     CharStat::new( base, None, Some( mod_conf ), None, None, None )
+/// } ).collect();
+/// 
+/// let mut batch = StatBatch::from_stats( stats );
+/// assert_eq!( batch.values(), &[ 0.0, 10.0, 20.0 ] );
+/// 
+/// // +5 for 10 ticks
+/// let common = ModCommon::new( 5.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+/// assert_eq!( batch.append_modifier( &Modifier::new_expiring( common, 10 ) ), 3 );
+/// assert_eq!( batch.values(), &[ 5.0, 15.0, 25.0 ] );
+/// assert_eq!( batch.base_values(), &[ 0.0, 10.0, 20.0 ] );
+/// 
+/// batch.set_ts( 10 ).unwrap();
+/// assert_eq!( batch.values(), &[ 0.0, 10.0, 20.0 ] );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[cfg_attr( feature = "serde", serde( try_from = "StatBatchRepr", into = "StatBatchRepr" ) )]
+#[derive( Debug, Clone, Default )]
+pub struct StatBatch {
+	time_stamp: u64,
+	/// `u64::MAX` when no modifier expires
+	next_expiration: Vec< u64 >,
+	base: Vec< BaseConf >,
+	upgrade: Vec< Option< UpgradeConf > >,
+	stages: StageColumns,
+	/// stats without base, upgrade and stage values, see `join` and `split`
+	stats: Vec< CharStat >,
+}
+
+impl StatBatch {
+	#[inline]
+	pub fn new () -> Self {
+		StatBatch::default()
+	}
+	
+	#[inline]
+	pub fn with_capacity ( capacity: usize ) -> Self {
+		StatBatch {
+			time_stamp: 0,
+			next_expiration: Vec::with_capacity( capacity ),
+			base: Vec::with_capacity( capacity ),
+			upgrade: Vec::with_capacity( capacity ),
+			stages: StageColumns::with_capacity( capacity ),
+			stats: Vec::with_capacity( capacity ),
+		}
+	}
+	
+	/// Creates a batch with the highest timestamp of `stats`, other stats are moved forward to it.
+	#[inline]
+	pub fn from_stats ( stats: Vec< CharStat > ) -> Self {
+		let mut out = StatBatch::with_capacity( stats.len() );
+		out.time_stamp = stats.iter().map( CharStat::ts ).max().unwrap_or( 0 );
+		
+		for cs in stats {
+			out.push_unchecked( cs );
+		}// for
+		
+		out
+	}
+	
+	/// Appends `cs` and returns its' index. A stat with a lower timestamp is moved forward to the timestamp of the batch.
+	/// 
+	/// # Errors
+	/// `CsLogicIssue::TimeTravel` when timestamp of `cs` is higher than the timestamp of the batch <br>
+	#[inline]
+	pub fn push ( &mut self, cs: CharStat ) -> Result< usize, CharStatError > {
+		if cs.ts() > self.time_stamp {
+			return Err( CsLogicIssue::TimeTravel.into() )
+		}
+		
+		Ok( self.push_unchecked( cs ) )
+	}
+	
+	/// Sets the timestamp of every stat, only stats with an expired modifier are recalculated.
+	/// Returns index and `StatReport` of every recalculated stat, see `CharStat::set_ts`.
+	/// 
+	/// # Errors
+	/// `CsLogicIssue::TimeTravel` when `new_val` < `self.time_stamp` <br>
+	#[inline]
+	pub fn set_ts ( &mut self, new_val: u64 ) -> Result< Vec< ( usize, StatReport ) >, CharStatError > {
+		if new_val < self.time_stamp {
+			return Err( CsLogicIssue::TimeTravel.into() )
+		}
+		
+		self.time_stamp = new_val;
+		
+		let mut out = Vec::new();
+		
+		for idx in 0..self.next_expiration.len() {
+			if self.next_expiration[ idx ] <= new_val {
+				out.push( ( idx, self.join( idx ).set_ts_unchecked( new_val ) ) );
+				self.split( idx );
+			}
+		}// for
+		
+		Ok( out )
+	}
+	
+	#[inline]
+	pub fn ts ( &self ) -> u64 {
+		self.time_stamp
+	}
+	
+	/// Appends `modifier` to every stat, returns the number of stats which accepted it. <br>
+	/// Stats without the component of `modifier.calc_stage()` are skipped.
+	#[inline]
+	pub fn append_modifier ( &mut self, modifier: &Modifier ) -> usize {
+		let mut count = 0;
+		
+		for idx in 0..self.stats.len() {
+			if self.join( idx ).append_modifier( modifier.clone() ).is_ok() {
+				count += 1;
+			}
+			
+			self.split( idx );
+		}// for
+		
+		count
+	}
+	
+	/// current values of all stats, ordered by index
+	#[inline]
+	pub fn values ( &self ) -> &[ f64 ] {
+		&self.stages.value
+	}
+	
+	#[inline]
+	pub fn value ( &self, idx: usize ) -> Option< f64 > {
+		self.stages.value.get( idx ).copied()
+	}
+	
+	/// "raw" base values of all stats, see `CharStat::base_raw`
+	#[inline]
+	pub fn base_values ( &self ) -> &[ f64 ] {
+		&self.stages.base
+	}
+	
+	/// "raw" upgrade values of all stats, 0.0 for stats without `UpgradeConf`, see `CharStat::upgrade_raw`
+	#[inline]
+	pub fn upgrade_values ( &self ) -> &[ f64 ] {
+		&self.stages.upgrade
+	}
+	
+	#[inline]
+	pub fn base_conf ( &self, idx: usize ) -> Option< &BaseConf > {
+		self.base.get( idx )
+	}
+	
+	#[inline]
+	pub fn upgrade_conf ( &self, idx: usize ) -> Option< &UpgradeConf > {
+		self.upgrade.get( idx )?.as_ref()
+	}
+	
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.stats.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.stats.is_empty()
+	}
+	
+	/// Calls `f` with the stat at `idx` and recalculates its' columns, returns `None` when `idx` is out of range. <br>
+	/// The stat keeps the timestamp of the batch, a timestamp set by `f` is reverted, see `StatBatch::set_ts`.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ StatBatch, CharStat, BaseConf, Bounds, RoundingHelper, StatReport };
+	/// 
+	/// let base = BaseConf::new( 10.0, true, Bounds::new_const( 0.0, 100.0 ).unwrap(), RoundingHelper::new_none(), None ).unwrap();
+	/// let mut batch = StatBatch::from_stats( vec![ CharStat::new_minimal( base ) ] );
+	/// 
+	/// assert_eq!( batch.with_stat_mut( 0, |cs| cs.set_base_value( 20.0 ) ), Some( Ok( StatReport::default() ) ) );
+	/// assert_eq!( batch.value( 0 ), Some( 20.0 ) );
+	/// assert_eq!( batch.with_stat_mut( 1, |cs| cs.set_base_value( 20.0 ) ), None );
+	/// ```
+	#[inline]
+	pub fn with_stat_mut < R > ( &mut self, idx: usize, f: impl FnOnce( &mut CharStat ) -> R ) -> Option< R > {
+		if idx >= self.stats.len() {
+			return None
+		}
+		
+		let ts = self.time_stamp;
+		let cs = self.join( idx );
+		cs.set_ts_unchecked( ts );
+		
+		let out = f( cs );
+		
+		if cs.ts() != ts {
+			cs.set_ts_unchecked( ts );
+		}
+		
+		self.split( idx );
+		
+		Some( out )
+	}
+	
+	/// returns a copy of the stat at `idx` with the timestamp of the batch
+	#[inline]
+	pub fn get ( &self, idx: usize ) -> Option< CharStat > {
+		let mut out = self.stats.get( idx )?.clone();
+		let mut base = self.base[ idx ].clone();
+		let mut upgrade = self.upgrade[ idx ].clone();
+		let mut row = self.stages.get( idx );
+		
+		out.swap_parts( &mut base, &mut upgrade, &mut row );
+		out.set_ts_unchecked( self.time_stamp );
+		
+		Some( out )
+	}
+	
+	/// Removes the stat at `idx`, the last stat takes its' place.
+	#[inline]
+	pub fn swap_remove ( &mut self, idx: usize ) -> Option< CharStat > {
+		if idx >= self.stats.len() {
+			return None
+		}
+		
+		self.next_expiration.swap_remove( idx );
+		let mut base = self.base.swap_remove( idx );
+		let mut upgrade = self.upgrade.swap_remove( idx );
+		let mut row = self.stages.swap_remove( idx );
+		
+		let mut out = self.stats.swap_remove( idx );
+		out.swap_parts( &mut base, &mut upgrade, &mut row );
+		out.set_ts_unchecked( self.time_stamp );
+		
+		Some( out )
+	}
+	
+	/// returns all stats with the timestamp of the batch
+	#[inline]
+	pub fn into_stats ( self ) -> Vec< CharStat > {
+		let StatBatch { time_stamp, base, upgrade, stages, stats, .. } = self;
+		
+		stats.into_iter().zip( base ).zip( upgrade ).enumerate().map( |( idx, ( ( mut cs, mut base ), mut upgrade ) )| {
+			let mut row = stages.get( idx );
+			cs.swap_parts( &mut base, &mut upgrade, &mut row );
+			cs.set_ts_unchecked( time_stamp );
+			
+			cs
+		} ).collect()
+	}
+}
+
+// priv
+impl StatBatch {
+	#[inline]
+	#[doc( hidden )]
+	fn push_unchecked ( &mut self, mut cs: CharStat ) -> usize {
+		cs.set_ts_unchecked( self.time_stamp );
+		
+		// the stat keeps a copy of its' base until `split` swaps it with the column
+		self.next_expiration.push( u64::MAX );
+		self.base.push( cs.base_conf().clone() );
+		self.upgrade.push( None );
+		self.stages.push( StageRow::default() );
+		self.stats.push( cs );
+		
+		let idx = self.stats.len() - 1;
+		self.split( idx );
+		
+		idx
+	}
+	
+	/// moves base, upgrade and stage values of the stat at `idx` back into it
+	#[inline]
+	#[doc( hidden )]
+	fn join ( &mut self, idx: usize ) -> &mut CharStat {
+		let mut row = self.stages.get( idx );
+		let cs = &mut self.stats[ idx ];
+		cs.swap_parts( &mut self.base[ idx ], &mut self.upgrade[ idx ], &mut row );
+		
+		cs
+	}
+	
+	/// recalculates the stat at `idx` and moves its' base, upgrade and stage values into the columns
+	#[inline]
+	#[doc( hidden )]
+	fn split ( &mut self, idx: usize ) {
+		let cs = &mut self.stats[ idx ];
+		cs.flush();
+		
+		let mut row = StageRow::default();
+		cs.swap_parts( &mut self.base[ idx ], &mut self.upgrade[ idx ], &mut row );
+		
+		self.stages.set( idx, row );
+		self.next_expiration[ idx ] = cs.next_expiration().unwrap_or( u64::MAX );
+	}
+}// priv
+
+/// Compares the timestamp and every stat returned by `get`, stats keep stale copies of their base while stored.
+impl PartialEq for StatBatch {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		self.time_stamp == other.time_stamp &&
+		self.len() == other.len() &&
+		( 0..self.len() ).all( |idx| self.get( idx ) == other.get( idx ) )
+	}
+}
+
+impl From< Vec< CharStat > > for StatBatch {
+	#[inline]
+	fn from( stats: Vec< CharStat > ) -> Self {
+		StatBatch::from_stats( stats )
+	}
+}
+
+impl From< StatBatch > for Vec< CharStat > {
+	#[inline]
+	fn from( batch: StatBatch ) -> Self {
+		batch.into_stats()
+	}
+}
+
+/// serialized as whole stats, columns are rebuilt by deserialization
+#[cfg( feature = "serde" )]
+#[derive( Serialize, Deserialize )]
+struct StatBatchRepr {
+	time_stamp: u64,
+	stats: Vec< CharStat >,
+}
+
+#[cfg( feature = "serde" )]
+impl From< StatBatch > for StatBatchRepr {
+	#[inline]
+	fn from( batch: StatBatch ) -> Self {
+		StatBatchRepr { time_stamp: batch.time_stamp, stats: batch.into_stats() }
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl TryFrom< StatBatchRepr > for StatBatch {
+	type Error = CharStatError;
+	
+	#[inline]
+	fn try_from( repr: StatBatchRepr ) -> Result< Self, Self::Error > {
+		let mut out = StatBatch::with_capacity( repr.stats.len() );
+		out.time_stamp = repr.time_stamp;
+		
+		for cs in repr.stats {
+			out.push( cs )?;
+		}// for
+		
+		Ok( out )
+	}
+}
+// struct - StatBatch
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use crate::{ StatBatch, CharStat, BaseConf, UpgradeConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper, CsLogicIssue, StatReport };
+	
+	fn stat ( base: f64, ts: u64 ) -> CharStat {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let base = BaseConf::new( base, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+		let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+		cs.set_ts( ts ).unwrap();
+		
+		cs
+	}
+	
+	#[test]
+	fn basic_functional() {
+		let mut batch = StatBatch::from_stats( vec![ stat( 1.0, 5 ), stat( 2.0, 10 ) ] );
+		assert_eq!( batch.ts(), 10 );
+		assert_eq!( batch.values(), &[ 1.0, 2.0 ] );
+		
+		assert_eq!( batch.push( stat( 3.0, 11 ) ), Err( CsLogicIssue::TimeTravel.into() ) );
+		assert_eq!( batch.push( stat( 3.0, 0 ) ), Ok( 2 ) );
+		
+		// stats without the stage are skipped
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let base = BaseConf::new( 4.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 0.0, bounds, RoundingHelper::new_none() ).unwrap();
+		assert_eq!( batch.push( CharStat::new_no_mod( base, Some( upgrade ) ) ), Ok( 3 ) );
+		
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		assert_eq!( batch.append_modifier( &Modifier::new_expiring( common, 20 ) ), 3 );
+		assert_eq!( batch.append_modifier( &Modifier::new_expiring( common, 30 ) ), 3 );
+		assert_eq!( batch.values(), &[ 3.0, 4.0, 5.0, 4.0 ] );
+		
+		// only stats with an expired modifier are reported
+		let reports = batch.set_ts( 20 ).unwrap();
+		assert_eq!( reports.iter().map( |( idx, report )| ( *idx, report.delta, report.expired.len() ) ).collect::< Vec<_> >(), vec![ ( 0, -1.0, 1 ), ( 1, -1.0, 1 ), ( 2, -1.0, 1 ) ] );
+		assert_eq!( batch.values(), &[ 2.0, 3.0, 4.0, 4.0 ] );
+		assert_eq!( batch.set_ts( 19 ), Err( CsLogicIssue::TimeTravel.into() ) );
+		
+		assert_eq!( batch.with_stat_mut( 3, |cs| cs.set_upgrade_value( 6.0 ) ), Some( Ok( StatReport::default() ) ) );
+		assert_eq!( batch.value( 3 ), Some( 10.0 ) );
+		
+		// stats are returned with the timestamp of the batch
+		assert!( batch.set_ts( 25 ).unwrap().is_empty() );
+		let mut removed = batch.swap_remove( 0 ).unwrap();
+		assert_eq!( removed.ts(), 25 );
+		assert_eq!( removed.set_ts( 24 ), Err( CsLogicIssue::TimeTravel.into() ) );
+		assert_eq!( batch.values(), &[ 10.0, 3.0, 4.0 ] );
+		
+		let stats: Vec< CharStat > = batch.clone().into();
+		assert_eq!( stats.len(), 3 );
+		assert_eq!( stats.iter().map( CharStat::value ).collect::< Vec<_> >(), batch.values() );
+		assert_eq!( batch.get( 2 ).unwrap().value(), 4.0 );
+		
+		batch.set_ts( 30 ).unwrap();
+		assert_eq!( batch.values(), &[ 10.0, 2.0, 3.0 ] );
+	}
+	
+	#[test]
+	fn columns() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let base = BaseConf::new( 4.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, RoundingHelper::new_none() ).unwrap();
+		let mut batch = StatBatch::from_stats( vec![ stat( 1.0, 0 ), CharStat::new_no_mod( base, Some( upgrade.clone() ) ) ] );
+		
+		assert_eq!( batch.base_values(), &[ 1.0, 4.0 ] );
+		assert_eq!( batch.upgrade_values(), &[ 0.0, 2.0 ] );
+		assert_eq!( batch.upgrade_conf( 0 ), None );
+		assert_eq!( batch.upgrade_conf( 1 ), Some( &upgrade ) );
+		assert_eq!( batch.base_conf( 1 ).unwrap().value(), 4.0 );
+		
+		// components changed through `with_stat_mut` end up in the columns
+		batch.with_stat_mut( 0, |cs| cs.set_base_value( 5.0 ) ).unwrap().unwrap();
+		assert_eq!( batch.base_values(), &[ 5.0, 4.0 ] );
+		assert_eq!( batch.base_conf( 0 ).unwrap().value(), 5.0 );
+		assert_eq!( batch.get( 0 ).unwrap(), stat( 5.0, 0 ) );
+		
+		// timestamp set by the closure is reverted, modifiers it removed stay removed
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		batch.append_modifier( &Modifier::new_expiring( common, 10 ) );
+		batch.with_stat_mut( 0, |cs| cs.set_ts( 20 ) ).unwrap().unwrap();
+		assert_eq!( batch.get( 0 ).unwrap().ts(), 0 );
+		assert_eq!( batch.values(), &[ 5.0, 6.0 ] );
+		assert_eq!( batch.set_ts( 5 ).unwrap(), vec![] );
+		
+		let stats = batch.clone().into_stats();
+		assert_eq!( stats[ 0 ].base(), 5.0 );
+		assert_eq!( stats[ 1 ].upgrade_conf(), Some( &upgrade ) );
+		assert_eq!( StatBatch::from_stats( stats ), batch );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn serde() {
+		let mut batch = StatBatch::from_stats( vec![ stat( 1.0, 0 ), stat( 2.0, 0 ) ] );
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		batch.append_modifier( &Modifier::new_expiring( common, 10 ) );
+		batch.set_ts( 5 ).unwrap();
+		
+		let json = serde_json::to_string( &batch ).unwrap();
+		let mut loaded: StatBatch = serde_json::from_str( &json ).unwrap();
+		assert_eq!( loaded, batch );
+		assert_eq!( loaded.ts(), 5 );
+		assert_eq!( loaded.values(), &[ 2.0, 3.0 ] );
+		
+		loaded.set_ts( 10 ).unwrap();
+		assert_eq!( loaded.values(), &[ 1.0, 2.0 ] );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,405 @@
+use std::fmt::{ Display, Formatter };
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicU64, Ordering };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize, Serializer, Deserializer };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::shared::{ register_any, registered_any, handle_eq };
+
+#[cfg( feature = "serde" )]
+use crate::CsInvalidValue;
+
+// --Modules
+//------------------------------------------------------------------------------
+// enum - StageRef
+
+/// Reference to a value calculated by `CharStat`, see `StageValues`.
+/// - Stage => running total after the named stage of `Pipeline`
+/// 
+/// The total value cannot be referenced, a bound of it would feed back into itself.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub enum StageRef {
+	Base,
+	Upgrade,
+	BasePlusUpgrade,
+	ModOfBase,
+	ModOfUpgrade,
+	ModOfBasePlusUpgrade,
+	Stage( String ),
+}
+
+impl StageRef {
+	#[inline]
+	pub fn stage ( name: &str ) -> Self {
+		StageRef::Stage( name.to_string() )
+	}
+}
+
+impl Display for StageRef {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let tmp = match self {
+			Self::Base => "Base",
+			Self::Upgrade => "Upgrade",
+			Self::BasePlusUpgrade => "BasePlusUpgrade",
+			Self::ModOfBase => "ModOfBase",
+			Self::ModOfUpgrade => "ModOfUpgrade",
+			Self::ModOfBasePlusUpgrade => "ModOfBasePlusUpgrade",
+			Self::Stage( name ) => return write!( f, "Stage( {name} )" ),
+		};
+		
+		tmp.fmt(f)
+	}
+}
+
+// enum - StageRef
+//------------------------------------------------------------------------------
+// struct - StageValues
+
+/// Values used to resolve `BoundSpec`.
+/// Stages are calculated in order, so a stage which was not recalculated yet holds the previous value. <br>
+/// Pipeline stages hold only the ones calculated so far, other names resolve to NaN, which is ignored by bounds and thresholds.
+#[derive( Debug, Clone, PartialEq, Default )]
+pub struct StageValues {
+	pub( crate ) base: f64,
+	pub( crate ) upgrade: f64,
+	pub( crate ) mod_of_base: f64,
+	pub( crate ) mod_of_upgrade: f64,
+	pub( crate ) mod_of_base_plus_upgrade: f64,
+	pub( crate ) stages: Vec< ( String, f64 ) >,
+}
+
+impl StageValues {
+	#[inline]
+	pub fn get ( &self, stage: &StageRef ) -> f64 {
+		match stage {
+			StageRef::Base => self.base,
+			StageRef::Upgrade => self.upgrade,
+			StageRef::BasePlusUpgrade => self.base + self.upgrade,
+			StageRef::ModOfBase => self.mod_of_base,
+			StageRef::ModOfUpgrade => self.mod_of_upgrade,
+			StageRef::ModOfBasePlusUpgrade => self.mod_of_base_plus_upgrade,
+			StageRef::Stage( name ) => self.stages.iter().find( |tmp| tmp.0 == *name ).map_or( f64::NAN, |tmp| tmp.1 ),
+		}
+	}
+}
+
+// struct - StageValues
+//------------------------------------------------------------------------------
+// trait - BoundSource
+
+/// Callback providing value of a bound, e.g. a value of another stat.
+pub trait BoundSource: std::fmt::Debug + Send + Sync {
+	fn value ( &self, ctx: &StageValues ) -> f64;
+}
+
+/// Thread safe cell implementing `BoundSource`, clones share the value.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ SharedValue, BoundSource, StageValues };
+/// 
+/// let level = SharedValue::new( 3.0 );
+/// let source = level.clone();
+/// 
+/// level.set( 4.0 );
+/// assert_eq!( source.value( &StageValues::default() ), 4.0 );
+/// ```
+#[derive( Debug, Clone, Default )]
+pub struct SharedValue( Arc< AtomicU64 > );
+
+impl SharedValue {
+	#[inline]
+	pub fn new ( value: f64 ) -> Self {
+		SharedValue( Arc::new( AtomicU64::new( value.to_bits() ) ) )
+	}
+	
+	#[inline]
+	pub fn set ( &self, value: f64 ) {
+		self.0.store( value.to_bits(), Ordering::Relaxed );
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> f64 {
+		f64::from_bits( self.0.load( Ordering::Relaxed ) )
+	}
+}
+
+impl BoundSource for SharedValue {
+	#[inline]
+	fn value ( &self, _ctx: &StageValues ) -> f64 {
+		self.get()
+	}
+}
+
+/// Shared handle to a `BoundSource`, compared by pointer or registry id.
+/// 
+/// With serde a registered source is stored as `{ "source": id }`, serialization of a source which is not registered fails.
+/// Deserialization requires registering the source with the same id first.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ SharedSource, SharedValue };
+/// 
+/// let level = SharedValue::new( 3.0 );
+/// let source = SharedSource::register( "bound_spec_doc_level", level );
+/// assert_eq!( source.id(), Some( "bound_spec_doc_level" ) );
+/// assert_eq!( SharedSource::registered( "bound_spec_doc_level" ), Some( source ) );
+/// ```
+#[derive( Debug, Clone )]
+pub struct SharedSource {
+	id: Option< String >,
+	source: Arc< dyn BoundSource >,
+}
+
+impl SharedSource {
+	/// source which is not registered, cannot be serialized
+	#[inline]
+	pub fn new ( source: impl BoundSource + 'static ) -> Self {
+		SharedSource { id: None, source: Arc::new( source ) }
+	}
+	
+	/// Registers `source` under `id` and returns a handle to it.
+	/// Registering the same id again replaces the source for new handles, existing handles keep the old one.
+	#[inline]
+	pub fn register ( id: &str, source: impl BoundSource + 'static ) -> Self {
+		let out = SharedSource { id: Some( id.to_string() ), source: Arc::new( source ) };
+		register_any( id, out.clone() );
+		
+		out
+	}
+	
+	/// returns a handle to the source registered under `id`
+	#[inline]
+	pub fn registered ( id: &str ) -> Option< Self > {
+		registered_any( id )
+	}
+	
+	/// id of a registered source
+	#[inline]
+	pub fn id ( &self ) -> Option< &str > {
+		self.id.as_deref()
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> &dyn BoundSource {
+		self.source.as_ref()
+	}
+}
+
+impl PartialEq for SharedSource {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		handle_eq( &self.source, self.id(), &other.source, other.id() )
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Serialize, Deserialize )]
+struct SharedSourceRepr {
+	source: String,
+}
+
+#[cfg( feature = "serde" )]
+impl Serialize for SharedSource {
+	#[inline]
+	fn serialize< S: Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error > {
+		match &self.id {
+			Some( id ) => SharedSourceRepr { source: id.clone() }.serialize( serializer ),
+			None => Err( serde::ser::Error::custom( CsInvalidValue::NotAllowed( "source".to_string() ) ) ),
+		}
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl< 'de > Deserialize< 'de > for SharedSource {
+	#[inline]
+	fn deserialize< D: Deserializer< 'de > >( deserializer: D ) -> Result< Self, D::Error > {
+		let repr = SharedSourceRepr::deserialize( deserializer )?;
+		
+		SharedSource::registered( &repr.source ).ok_or_else( || {
+			serde::de::Error::custom( CsInvalidValue::NotAllowed( "source".to_string() ) )
+		})
+	}
+}
+
+// trait - BoundSource
+//------------------------------------------------------------------------------
+// enum - BoundSpec
+
+/// Dynamic min or max, resolved every time `CharStat` recalculates the value.
+/// - Const => constant value
+/// - PercentOf => value of the stage multiplied by percent ( 1.0 = 100% )
+/// - Source => value returned by `BoundSource`, only a registered source can be serialized, see `SharedSource`
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub enum BoundSpec {
+	Const( f64 ),
+	PercentOf{ stage: StageRef, percent: f64 },
+	Source( SharedSource ),
+}
+
+impl BoundSpec {
+	#[inline]
+	pub fn percent_of ( stage: StageRef, percent: f64 ) -> Self {
+		BoundSpec::PercentOf{ stage, percent }
+	}
+	
+	#[inline]
+	pub fn source ( source: impl BoundSource + 'static ) -> Self {
+		BoundSpec::Source( SharedSource::new( source ) )
+	}
+	
+	#[inline]
+	pub fn resolve ( &self, ctx: &StageValues ) -> f64 {
+		match self {
+			BoundSpec::Const( val ) => *val,
+			BoundSpec::PercentOf{ stage, percent } => ctx.get( stage ) * percent,
+			BoundSpec::Source( source ) => source.get().value( ctx ),
+		}
+	}
+}
+
+// enum - BoundSpec
+//------------------------------------------------------------------------------
+// struct - DynBounds
+
+/// Dynamic bounds of `UpgradeConf` or `ModConf`, applied after their constant bounds.
+/// When resolved min is greater than max, max takes precedence. NaN is ignored.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ DynBounds, BoundSpec, StageRef, SharedValue, StageValues };
+/// 
+/// // upgrade max = 2 x character level
+/// #[derive( Debug )]
+/// struct TwiceLevel( SharedValue );
+/// 
+/// impl char_stat::BoundSource for TwiceLevel {
+///     fn value ( &self, _ctx: &StageValues ) -> f64 {
+///         self.0.get() * 2.0
+///     }
+/// }
+/// 
+/// let level = SharedValue::new( 3.0 );
+/// let bounds = DynBounds::new( None, Some( BoundSpec::source( TwiceLevel( level.clone() ) ) ) );
+/// assert_eq!( bounds.apply( 10.0, &StageValues::default() ), 6.0 );
+/// 
+/// level.set( 4.0 );
+/// assert_eq!( bounds.apply( 10.0, &StageValues::default() ), 8.0 );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub struct DynBounds {
+	min: Option< BoundSpec >,
+	max: Option< BoundSpec >,
+}
+
+impl DynBounds {
+	#[inline]
+	pub fn new ( min: Option< BoundSpec >, max: Option< BoundSpec > ) -> Self {
+		DynBounds {
+			min,
+			max,
+		}
+	}
+	
+	#[inline]
+	pub fn min ( &self ) -> Option< &BoundSpec > {
+		self.min.as_ref()
+	}
+	
+	#[inline]
+	pub fn max ( &self ) -> Option< &BoundSpec > {
+		self.max.as_ref()
+	}
+	
+	#[inline]
+	pub fn apply ( &self, mut value: f64, ctx: &StageValues ) -> f64 {
+		if let Some( min ) = &self.min {
+			let tmp = min.resolve( ctx );
+			
+			if value < tmp {
+				value = tmp;
+			}
+		}
+		
+		if let Some( max ) = &self.max {
+			let tmp = max.resolve( ctx );
+			
+			if value > tmp {
+				value = tmp;
+			}
+		}
+		
+		value
+	}
+}
+
+// struct - DynBounds
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	
+	#[test]
+	fn basic_functional() {
+		let ctx = StageValues {
+			base: 10.0,
+			upgrade: 4.0,
+			mod_of_base: 2.0,
+			mod_of_upgrade: 0.0,
+			mod_of_base_plus_upgrade: 0.0,
+			stages: vec![ ( "gear".to_string(), 16.0 ) ],
+		};
+		
+		assert_eq!( BoundSpec::Const( 3.0 ).resolve( &ctx ), 3.0 );
+		assert_eq!( BoundSpec::percent_of( StageRef::BasePlusUpgrade, 0.5 ).resolve( &ctx ), 7.0 );
+		assert!( BoundSpec::percent_of( StageRef::stage( "caps" ), 0.5 ).resolve( &ctx ).is_nan() );
+		
+		let bounds = DynBounds::new( Some( BoundSpec::Const( 1.0 ) ), Some( BoundSpec::percent_of( StageRef::stage( "gear" ), 0.5 ) ) );
+		assert_eq!( bounds.apply( 0.0, &ctx ), 1.0 );
+		assert_eq!( bounds.apply( 5.0, &ctx ), 5.0 );
+		assert_eq!( bounds.apply( 10.0, &ctx ), 8.0 );
+		
+		// max wins
+		let bounds = DynBounds::new( Some( BoundSpec::Const( 10.0 ) ), Some( BoundSpec::Const( 5.0 ) ) );
+		assert_eq!( bounds.apply( 0.0, &ctx ), 5.0 );
+		
+		// stage which was not calculated yet is ignored
+		let bounds = DynBounds::new( None, Some( BoundSpec::percent_of( StageRef::stage( "caps" ), 0.5 ) ) );
+		assert_eq!( bounds.apply( 10.0, &ctx ), 10.0 );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let level = SharedValue::new( 3.0 );
+		let registered = BoundSpec::Source( SharedSource::register( "bound_spec_tests_level", level.clone() ) );
+		
+		let json = serde_json::to_string( &registered ).unwrap();
+		assert_eq!( json, r#"{"Source":{"source":"bound_spec_tests_level"}}"# );
+		
+		let out: BoundSpec = serde_json::from_str( &json ).unwrap();
+		assert_eq!( out, registered );
+		level.set( 4.0 );
+		assert_eq!( out.resolve( &StageValues::default() ), 4.0 );
+		
+		// source which is not registered fails instead of being skipped
+		assert!( serde_json::to_string( &BoundSpec::source( level ) ).is_err() );
+		assert!( serde_json::from_str::< BoundSpec >( r#"{"Source":{"source":"missing"}}"# ).is_err() );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
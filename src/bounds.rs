@@ -14,11 +14,25 @@ use crate::{ CharStatError, CsLogicIssue, CsInvalidValue };
 // struct - Bounds
 
 /// Manages the allowed min/max values and whether they are mutable.
+/// A missing min/max ( or `f64::NEG_INFINITY` / `f64::INFINITY` passed to a constructor or setter ) means no bound.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::Bounds;
+/// 
+/// let bounds = Bounds::at_least( 0.0 ).unwrap();
+/// assert_eq!( bounds.max_opt(), None );
+/// assert_eq!( bounds.max(), f64::INFINITY );
+/// assert_eq!( bounds.clamp( -5.0 ), 0.0 );
+/// assert_eq!( bounds.clamp( f64::MAX ), f64::MAX );
+/// ```
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
 #[derive( Debug, Clone, Copy, PartialEq,  )]
 pub struct Bounds {
-	v_min: f64,
-	v_max: f64,
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ) )]
+	v_min: Option< f64 >,
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ) )]
+	v_max: Option< f64 >,
 	is_min_mut: bool,
 	is_max_mut: bool,
 }
@@ -26,16 +40,17 @@ pub struct Bounds {
 impl Bounds {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when either `v_min` or `v_max` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `v_min` is `f64::INFINITY` or `v_max` is `f64::NEG_INFINITY` <br>
 	/// `CsLogicIssue::MinGreaterThanMax` when `v_min` > `v_max` <br>
 	#[inline]
 	pub fn new ( v_min: f64, v_max: f64, is_min_mut: bool, is_max_mut: bool ) -> Result< Self, CharStatError > {
-		Bounds::check_nan( v_min, "v_min".to_string() )?;
-		Bounds::check_nan( v_max, "v_max".to_string() )?;
+		Bounds::check_min( v_min, "v_min".to_string() )?;
+		Bounds::check_max( v_max, "v_max".to_string() )?;
 		Bounds::check_values( v_min, v_max )?;
 		
 		Ok( Bounds {
-			v_min,
-			v_max,
+			v_min: Bounds::min_or_none( v_min ),
+			v_max: Bounds::max_or_none( v_max ),
 			is_min_mut,
 			is_max_mut,
 		})
@@ -45,16 +60,17 @@ impl Bounds {
 	/// 
 	/// # Errors
 	/// `CsInvalidValue::Nan` when either `v_min` or `v_max` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `v_min` is `f64::INFINITY` or `v_max` is `f64::NEG_INFINITY` <br>
 	/// `CsLogicIssue::MinGreaterThanMax` when `v_min` > `v_max` <br>
 	#[inline]
 	pub fn new_const ( v_min: f64, v_max: f64 ) -> Result< Self, CharStatError > {
-		Bounds::check_nan( v_min, "v_min".to_string() )?;
-		Bounds::check_nan( v_max, "v_max".to_string() )?;
+		Bounds::check_min( v_min, "v_min".to_string() )?;
+		Bounds::check_max( v_max, "v_max".to_string() )?;
 		Bounds::check_values( v_min, v_max )?;
 		
 		Ok( Bounds {
-			v_min,
-			v_max,
+			v_min: Bounds::min_or_none( v_min ),
+			v_max: Bounds::max_or_none( v_max ),
 			is_min_mut: false,
 			is_max_mut: false,
 		})
@@ -64,23 +80,56 @@ impl Bounds {
 	/// 
 	/// # Errors
 	/// `CsInvalidValue::Nan` when either `v_min` or `v_max` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `v_min` is `f64::INFINITY` or `v_max` is `f64::NEG_INFINITY` <br>
 	/// `CsLogicIssue::MinGreaterThanMax` when `v_min` > `v_max` <br>
 	#[inline]
 	pub fn new_mut ( v_min: f64, v_max: f64 ) -> Result< Self, CharStatError > {
-		Bounds::check_nan( v_min, "v_min".to_string() )?;
-		Bounds::check_nan( v_max, "v_max".to_string() )?;
+		Bounds::check_min( v_min, "v_min".to_string() )?;
+		Bounds::check_max( v_max, "v_max".to_string() )?;
 		Bounds::check_values( v_min, v_max )?;
 		
 		Ok( Bounds {
-			v_min,
-			v_max,
+			v_min: Bounds::min_or_none( v_min ),
+			v_max: Bounds::max_or_none( v_max ),
 			is_min_mut: true,
 			is_max_mut: true,
 		})
 	}
 	
+	/// only min, mutability within setters disabled
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `v_min` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `v_min` is `f64::INFINITY` <br>
+	#[inline]
+	pub fn at_least ( v_min: f64 ) -> Result< Self, CharStatError > {
+		Bounds::new_const( v_min, f64::INFINITY )
+	}
+	
+	/// only max, mutability within setters disabled
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `v_max` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `v_max` is `f64::NEG_INFINITY` <br>
+	#[inline]
+	pub fn at_most ( v_max: f64 ) -> Result< Self, CharStatError > {
+		Bounds::new_const( f64::NEG_INFINITY, v_max )
+	}
+	
+	/// neither min nor max, mutability within setters disabled
+	#[inline]
+	pub fn unbounded () -> Self {
+		Bounds {
+			v_min: None,
+			v_max: None,
+			is_min_mut: false,
+			is_max_mut: false,
+		}
+	}
+	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is `f64::INFINITY` <br>
 	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_min_mut` is false <br>
 	#[inline]
@@ -89,28 +138,29 @@ impl Bounds {
 			return Err( CsLogicIssue::FieldIsConst.into() )
 		}
 		
-		Bounds::check_nan( new_val, "new_val".to_string() )?;
-		Bounds::check_values( new_val, self.v_max )?;
+		Bounds::check_min( new_val, "new_val".to_string() )?;
+		Bounds::check_values( new_val, self.max() )?;
 		
-		self.v_min = new_val;
+		self.v_min = Bounds::min_or_none( new_val );
 		
 		Ok(())
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is `f64::NEG_INFINITY` <br>
 	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_max_mut` is false <br>
 	#[inline]
 	pub fn set_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		if !self.is_min_mut {
+		if !self.is_max_mut {
 			return Err( CsLogicIssue::FieldIsConst.into() )
 		}
 		
-		Bounds::check_nan( new_val, "new_val".to_string() )?;
-		Bounds::check_values( self.v_min, new_val )?;
+		Bounds::check_max( new_val, "new_val".to_string() )?;
+		Bounds::check_values( self.min(), new_val )?;
 		
-		self.v_max = new_val;
+		self.v_max = Bounds::max_or_none( new_val );
 		
 		Ok(())
 	}
@@ -127,30 +177,128 @@ impl Bounds {
 		self.is_max_mut = false;
 	}
 	
+	/// returns `f64::NEG_INFINITY` when there is no min
 	#[inline]
 	pub fn min ( &self ) -> f64 {
-		self.v_min
+		self.v_min.unwrap_or( f64::NEG_INFINITY )
 	}
 	
+	/// returns `f64::INFINITY` when there is no max
 	#[inline]
 	pub fn max ( &self ) -> f64 {
+		self.v_max.unwrap_or( f64::INFINITY )
+	}
+	
+	#[inline]
+	pub fn min_opt ( &self ) -> Option< f64 > {
+		self.v_min
+	}
+	
+	#[inline]
+	pub fn max_opt ( &self ) -> Option< f64 > {
 		self.v_max
 	}
+	
+	#[inline]
+	pub fn is_min_mut ( &self ) -> bool {
+		self.is_min_mut
+	}
+	
+	#[inline]
+	pub fn is_max_mut ( &self ) -> bool {
+		self.is_max_mut
+	}
+	
+	#[inline]
+	pub fn clamp ( &self, value: f64 ) -> f64 {
+		value.clamp( self.min(), self.max() )
+	}
 }
 
+// pub-crate
+impl Bounds {
+	/// min/max flagged as percent are multiplied by `value`, missing min/max stays unbounded. <br>
+	/// When both min and max are percent, negative `value` flips them, then min and max are swapped. <br>
+	/// When only one of them is percent and it crosses the other one, the absolute bound wins.
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn scaled ( &self, value: f64, is_min_percent: bool, is_max_percent: bool ) -> ( f64, f64 ) {
+		let eff_min = match self.v_min {
+			Some( tmp ) if is_min_percent => tmp * value,
+			Some( tmp ) => tmp,
+			None => f64::NEG_INFINITY,
+		};
+		
+		let eff_max = match self.v_max {
+			Some( tmp ) if is_max_percent => tmp * value,
+			Some( tmp ) => tmp,
+			None => f64::INFINITY,
+		};
+		
+		if eff_min <= eff_max {
+			return ( eff_min, eff_max )
+		}
+		
+		match ( is_min_percent, is_max_percent ) {
+			( true, true ) => ( eff_max, eff_min ),
+			( false, _ ) => ( eff_min, eff_min ),
+			( true, false ) => ( eff_max, eff_max ),
+		}
+	}
+}// pub-crate
+
 //priv
 impl Bounds {
 	#[inline( always )]
 	#[doc( hidden )]
-	fn check_nan( value: f64, name: String ) -> Result<(), CharStatError > {
+	fn check_min( value: f64, name: String ) -> Result<(), CharStatError > {
 		if value.is_nan() {
 			
 			return Err( CsInvalidValue::Nan( name ).into() )
 		}
+		if value.is_infinite() && value.is_sign_positive() {
+			
+			return Err( CsInvalidValue::Infinite( name ).into() )
+		}
 		
 		Ok(())
 	}
 	
+	#[inline( always )]
+	#[doc( hidden )]
+	fn check_max( value: f64, name: String ) -> Result<(), CharStatError > {
+		if value.is_nan() {
+			
+			return Err( CsInvalidValue::Nan( name ).into() )
+		}
+		if value.is_infinite() && value.is_sign_negative() {
+			
+			return Err( CsInvalidValue::Infinite( name ).into() )
+		}
+		
+		Ok(())
+	}
+	
+	#[inline( always )]
+	#[doc( hidden )]
+	fn min_or_none( value: f64 ) -> Option< f64 > {
+		if value.is_infinite() && value.is_sign_negative() {
+			return None
+		}
+		
+		Some( value )
+	}
+	
+	#[inline( always )]
+	#[doc( hidden )]
+	fn max_or_none( value: f64 ) -> Option< f64 > {
+		if value.is_infinite() && value.is_sign_positive() {
+			return None
+		}
+		
+		Some( value )
+	}
+	
 	#[inline( always )]
 	#[doc( hidden )]
 	fn check_values( min: f64, max: f64 ) -> Result<(), CharStatError > {
@@ -172,13 +320,13 @@ impl Display for Bounds {
 			"const"
 		};
 		
-		let max_m = if self.is_min_mut {
+		let max_m = if self.is_max_mut {
 			"mut"
 		} else {
 			"const"
 		};
 		
-		write!( f, "Bounds = ({2} min: {0}, {3} max: {1})", self.v_min, self.v_max, min_m, max_m )
+		write!( f, "Bounds = ({2} min: {0}, {3} max: {1})", self.min(), self.max(), min_m, max_m )
 	}
 }
 
@@ -188,6 +336,8 @@ impl Display for Bounds {
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use super::*;
 	
 	#[test]
@@ -230,6 +380,61 @@ mod tests {
 		assert_eq!( bounds_2.set_min( 1.0 ), Err( expected.clone() ) );
 		assert_eq!( bounds_2.set_max( 10.0 ), Err( expected ) );
 	}
+	
+	#[test]
+	fn half_open() {
+		let at_least = Bounds::at_least( 1.0 ).unwrap();
+		assert_eq!( ( at_least.min_opt(), at_least.max_opt() ), ( Some( 1.0 ), None ) );
+		assert_eq!( at_least.clamp( f64::MAX ), f64::MAX );
+		
+		let at_most = Bounds::at_most( 1.0 ).unwrap();
+		assert_eq!( ( at_most.min_opt(), at_most.max_opt() ), ( None, Some( 1.0 ) ) );
+		assert_eq!( at_most.clamp( 5.0 ), 1.0 );
+		
+		// infinities are stored as a missing bound
+		assert_eq!( Bounds::new_const( f64::NEG_INFINITY, f64::INFINITY ), Ok( Bounds::unbounded() ) );
+		assert_eq!( Bounds::unbounded().to_string(), "Bounds = (const min: -inf, const max: inf)" );
+		
+		// percent bounds stay unbounded
+		assert_eq!( at_least.scaled( 0.0, true, true ), ( 0.0, f64::INFINITY ) );
+		assert_eq!( at_least.scaled( -2.0, true, true ), ( -2.0, f64::INFINITY ) );
+		
+		let mut bounds = Bounds::new( 0.0, 1.0, false, true ).unwrap();
+		assert_eq!( bounds.set_max( f64::INFINITY ), Ok(()) );
+		assert_eq!( bounds.max_opt(), None );
+		assert_eq!( bounds.to_string(), "Bounds = (const min: 0, mut max: inf)" );
+		
+		let expected: CharStatError = CsInvalidValue::Infinite( "v_min".to_string() ).into();
+		assert_eq!( Bounds::at_least( f64::INFINITY ), Err( expected ) );
+		
+		let expected: CharStatError = CsInvalidValue::Infinite( "new_val".to_string() ).into();
+		assert_eq!( bounds.set_max( f64::NEG_INFINITY ), Err( expected ) );
+	}
+	
+	#[test]
+	fn scaled() {
+		let bounds = Bounds::new_const( 0.5, 0.8 ).unwrap();
+		assert_eq!( bounds.scaled( 10.0, true, true ), ( 5.0, 8.0 ) );
+		
+		// both percent, negative value swaps min and max
+		assert_eq!( bounds.scaled( -10.0, true, true ), ( -8.0, -5.0 ) );
+		
+		// absolute bound wins over the crossing percent one
+		assert_eq!( bounds.scaled( 0.25, false, true ), ( 0.5, 0.5 ) );
+		assert_eq!( bounds.scaled( -10.0, false, true ), ( 0.5, 0.5 ) );
+		assert_eq!( bounds.scaled( 2.0, true, false ), ( 0.8, 0.8 ) );
+		assert_eq!( bounds.scaled( 1.5, true, false ), ( 0.75, 0.8 ) );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let serialized = serde_json::to_string( &Bounds::at_least( 1.0 ).unwrap() ).unwrap();
+		assert_eq!( serialized, r#"{"v_min":1.0,"is_min_mut":false,"is_max_mut":false}"# );
+		
+		let deserialized: Bounds = serde_json::from_str( &serialized ).unwrap();
+		assert_eq!( deserialized, Bounds::at_least( 1.0 ).unwrap() );
+	}
 }
 
 // --Tests
@@ -10,13 +10,13 @@ use std::marker::PhantomData;
 //------------------------------------------------------------------------------
 // --Modules
 
-use super::{ CharStat, BaseConf, UpgradeConf, ModConf, ModMultConf };
+use super::{ CharStat, BaseConf, UpgradeConf, ModConf, ModMultConf, ModMoreConf, Pipeline, TotalBounds, RoundingHelper, SoftCap };
 
 // --Modules
 //------------------------------------------------------------------------------
 // struct - CharStatBuilder
 
-// derive: 3, constructors: 2, priv impl: 1, setters: 6, build: 3
+// derive: 3, constructors: 2, priv impl: 2, setters: 12, build: 4
 
 /// A typestate builder for `CharStat`
 /// 
@@ -24,6 +24,7 @@ use super::{ CharStat, BaseConf, UpgradeConf, ModConf, ModMultConf };
 /// - `BaseConf` must always be set
 /// - `mod_of_upgrade` and `mod_of_base_plus_upgrade` require `UpgradeConf`
 /// - `mod_mult` requires at least one of the `mod_of_*` to be set
+/// - `pipeline` requires that none of the `mod_of_*` is set, afterwards they are denied and `mod_mult` is allowed
 /// 
 /// To make invalid state unreachable, a type-state pattern has been implemented.
 /// 
@@ -58,6 +59,11 @@ where
 	mod_of_upgrade:						Option< ModConf >,
 	mod_of_base_plus_upgrade:	Option< ModConf >,
 	mod_mult:									Option< ModMultConf >,
+	mod_more:									Option< ModMoreConf >,
+	pipeline:									Option< Pipeline >,
+	total_bounds:							Option< TotalBounds >,
+	total_rounding:						Option< RoundingHelper >,
+	total_soft_cap:						Option< SoftCap >,
 	
 	b: PhantomData< B >,
 	up: PhantomData< Up >,
@@ -80,6 +86,11 @@ impl CharStatBuilder< FldEmpty, FldEmpty, FldDeny, FldEmpty, FldDeny, FldEmpty,
 			mod_of_upgrade:						None,
 			mod_of_base_plus_upgrade:	None,
 			mod_mult:									None,
+			mod_more:									None,
+			pipeline:									None,
+			total_bounds:							None,
+			total_rounding:						None,
+			total_soft_cap:						None,
 			
 			b: PhantomData::< FldEmpty >,
 			up: PhantomData::< FldEmpty >,
@@ -113,7 +124,46 @@ where
 		let mod_of_base_plus_upgrade = self.mod_of_base_plus_upgrade.clone();
 		let mod_mult = self.mod_mult.clone();
 		
-		CharStat::new( base, upgrade, mod_of_base, mod_of_upgrade, mod_of_base_plus_upgrade, mod_mult )
+		let mut cs = match self.pipeline.clone() {
+			Some( pipeline ) => CharStat::new_pipeline( base, upgrade, pipeline, mod_mult ),
+			None => CharStat::new( base, upgrade, mod_of_base, mod_of_upgrade, mod_of_base_plus_upgrade, mod_mult ),
+		};
+		self.apply_total( &mut cs );
+		
+		cs
+	}
+}
+
+impl< B, Up, Upc, MoB, MoBc, MoU, MoUc, MoBpU, Mmc > CharStatBuilder< B, Up, Upc, MoB, MoBc, MoU, MoUc, MoBpU, Mmc >
+where
+	B: FldState,
+	Up: FldState,
+	Upc: FldCtrl,
+	MoB: FldState,
+	MoBc: FldCtrl,
+	MoU: FldState,
+	MoUc: FldCtrl,
+	MoBpU: FldState,
+	Mmc: FldCtrl,
+{
+	fn apply_total ( &self, cs: &mut CharStat ) {
+		if self.mod_more.is_some() {
+			cs.set_mod_more( self.mod_more.clone() );
+		}
+		
+		if self.total_bounds.is_some() {
+			cs.set_total_bounds( self.total_bounds );
+		}
+		
+		if self.total_rounding.is_some() {
+			cs.set_total_rounding( self.total_rounding.clone() );
+		}
+		
+		if self.total_soft_cap.is_some() {
+			cs.set_total_soft_cap( self.total_soft_cap );
+		}
+		
+		cs.flush();
 	}
 }
 
@@ -147,6 +197,11 @@ where
 			mod_of_upgrade:						self.mod_of_upgrade,
 			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
 			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< Up >,
@@ -179,6 +234,11 @@ where
 			mod_of_upgrade:						self.mod_of_upgrade,
 			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
 			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< FldSet >,
@@ -211,6 +271,11 @@ where
 			mod_of_upgrade:						self.mod_of_upgrade,
 			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
 			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< Up >,
@@ -241,6 +306,11 @@ where
 			mod_of_upgrade:						Some( value ),
 			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
 			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< FldSet >,
@@ -271,6 +341,11 @@ where
 			mod_of_upgrade:						self.mod_of_upgrade,
 			mod_of_base_plus_upgrade:	Some( value ),
 			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< FldSet >,
@@ -303,6 +378,11 @@ where
 			mod_of_upgrade:						self.mod_of_upgrade,
 			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
 			mod_mult:									Some( value ),
+			mod_more:									self.mod_more,
+			pipeline:									self.pipeline,
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
 			
 			b: PhantomData::< FldSet >,
 			up: PhantomData::< Up >,
@@ -317,6 +397,100 @@ where
 	}
 }
 
+impl< Up, MoUc, Mmc > CharStatBuilder< FldSet, Up, FldAllow, FldEmpty, FldAllow, FldEmpty, MoUc, FldEmpty, Mmc >
+where
+	Up: FldState,
+	MoUc: FldCtrl,
+	Mmc: FldCtrl,
+{
+	/// Replaces `mod_of_*` components with a user defined `Pipeline`, see `CharStat::new_pipeline`.
+	/// Upgrade has to be set before the pipeline.
+	#[inline]
+	pub fn pipeline ( self, value: Pipeline ) -> CharStatBuilder< FldSet, Up, FldDeny, FldEmpty, FldDeny, FldEmpty, FldDeny, FldEmpty, FldAllow > {
+		CharStatBuilder {
+			base:											self.base,
+			upgrade:									self.upgrade,
+			mod_of_base:							self.mod_of_base,
+			mod_of_upgrade:						self.mod_of_upgrade,
+			mod_of_base_plus_upgrade:	self.mod_of_base_plus_upgrade,
+			mod_mult:									self.mod_mult,
+			mod_more:									self.mod_more,
+			pipeline:									Some( value ),
+			total_bounds:							self.total_bounds,
+			total_rounding:						self.total_rounding,
+			total_soft_cap:						self.total_soft_cap,
+			
+			b: PhantomData::< FldSet >,
+			up: PhantomData::< Up >,
+			up_ctrl: PhantomData::< FldDeny >,
+			mod_b: PhantomData::< FldEmpty >,
+			mod_b_ctrl: PhantomData::< FldDeny >,
+			mod_u: PhantomData::< FldEmpty >,
+			mod_u_ctrl: PhantomData::< FldDeny >,
+			mod_b_u: PhantomData::< FldEmpty >,
+			mod_m_ctrl: PhantomData::< FldAllow >,
+		}
+	}
+}
+
+impl< Up > CharStatBuilder< FldSet, Up, FldDeny, FldEmpty, FldDeny, FldEmpty, FldDeny, FldEmpty, FldAllow >
+where
+	Up: FldState,
+{
+	/// `mod_mult` of a pipeline, applied to modifiers of every stage
+	#[inline]
+	#[must_use]
+	pub fn mod_mult ( mut self, value: ModMultConf ) -> Self {
+		self.mod_mult = Some( value );
+		self
+	}
+}
+
+impl< B, Up, Upc, MoB, MoBc, MoU, MoUc, MoBpU, Mmc > CharStatBuilder< B, Up, Upc, MoB, MoBc, MoU, MoUc, MoBpU, Mmc >
+where
+	B: FldState,
+	Up: FldState,
+	Upc: FldCtrl,
+	MoB: FldState,
+	MoBc: FldCtrl,
+	MoU: FldState,
+	MoUc: FldCtrl,
+	MoBpU: FldState,
+	Mmc: FldCtrl,
+{
+	/// see `CharStat::set_mod_more`, can be set in any state
+	#[inline]
+	#[must_use]
+	pub fn mod_more ( mut self, value: ModMoreConf ) -> Self {
+		self.mod_more = Some( value );
+		self
+	}
+	
+	/// see `CharStat::set_total_bounds`, can be set in any state
+	#[inline]
+	#[must_use]
+	pub fn total_bounds ( mut self, value: TotalBounds ) -> Self {
+		self.total_bounds = Some( value );
+		self
+	}
+	
+	/// see `CharStat::set_total_rounding`, can be set in any state
+	#[inline]
+	#[must_use]
+	pub fn total_rounding ( mut self, value: RoundingHelper ) -> Self {
+		self.total_rounding = Some( value );
+		self
+	}
+	
+	/// see `CharStat::set_total_soft_cap`, can be set in any state
+	#[inline]
+	#[must_use]
+	pub fn total_soft_cap ( mut self, value: SoftCap ) -> Self {
+		self.total_soft_cap = Some( value );
+		self
+	}
+}
+
 // setters
 //------------------------------------------------------------------------------
 // build
@@ -326,7 +500,10 @@ impl CharStatBuilder< FldSet, FldEmpty, FldAllow, FldEmpty, FldAllow, FldEmpty,
 	pub fn build ( &self ) -> CharStat {
 		let base = self.base.clone().unwrap();
 		
-		CharStat::new_minimal( base )
+		let mut cs = CharStat::new_minimal( base );
+		self.apply_total( &mut cs );
+		
+		cs
 	}
 }
 
@@ -336,7 +513,10 @@ impl CharStatBuilder< FldSet, FldSet, FldAllow, FldEmpty, FldAllow, FldEmpty, Fl
 		let base = self.base.clone().unwrap();
 		let upgrade = self.upgrade.clone();
 		
-		CharStat::new_no_mod( base, upgrade )
+		let mut cs = CharStat::new_no_mod( base, upgrade );
+		self.apply_total( &mut cs );
+		
+		cs
 	}
 }
 
@@ -354,6 +534,16 @@ where
 	}
 }
 
+impl< Up > CharStatBuilder< FldSet, Up, FldDeny, FldEmpty, FldDeny, FldEmpty, FldDeny, FldEmpty, FldAllow >// pipeline
+where
+	Up: FldState,
+{
+	#[inline]
+	pub fn build ( &self ) -> CharStat {
+		self.build_impl()
+	}
+}
+
 // struct - CharStatBuilder
 //------------------------------------------------------------------------------
 // traits
@@ -386,6 +576,8 @@ impl FldCtrl for FldAllow {}
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use crate::{ Bounds, ModCalcStage, RoundingHelper  };
 	use super::*;
 	
@@ -415,6 +607,18 @@ mod tests {
 		
 		assert_eq!( cs_2, cs_ref );
 		
+		let rounded = CharStatBuilder::new().total_rounding( RoundingHelper::new_none() ).base( base.clone() ).build();
+		let mut rounded_ref = CharStat::new_minimal( base.clone() );
+		rounded_ref.set_total_rounding( Some( RoundingHelper::new_none() ) );
+		rounded_ref.flush();
+		assert_eq!( rounded, rounded_ref );
+		
+		let pipeline = Pipeline::classic( None, None, None ).unwrap();
+		let piped = CharStatBuilder::new().base( base.clone() ).upgrade( up_conf.clone() ).pipeline( pipeline.clone() ).mod_mult( ModMultConf::new( bounds ) ).build();
+		let piped_ref = CharStat::new_pipeline( base.clone(), Some( up_conf.clone() ), pipeline, Some( ModMultConf::new( bounds ) ) );
+		assert_eq!( piped, piped_ref );
+		assert_eq!( piped.value(), 4.0 );
+		
 		let up_mod_cfg = ModConf::new(
 			ModCalcStage::Upgrade,
 			bounds,
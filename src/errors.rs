@@ -79,6 +79,7 @@ pub enum CsLogicIssue {
 	MinGreaterThanMax,
 	FieldIsConst,
 	TimeTravel,
+	LengthMismatch( usize, usize ),
 }
 
 impl Display for CsLogicIssue {
@@ -91,6 +92,7 @@ impl Display for CsLogicIssue {
 			Self::MinGreaterThanMax => "invalid bounds: min cannot be greater than max".fmt(f),
 			Self::FieldIsConst => "cannot mutate a const property".fmt(f),
 			Self::TimeTravel => "invalid timestamp - cannot move back in time".fmt(f),
+			Self::LengthMismatch( bad, good ) => write!( f, "invalid number of elements - found: {bad}, expected: {good}", ),
 		}
 	}
 }
@@ -127,13 +129,15 @@ pub enum CsInvalidValue {
 	AboveMaximum( String ),
 	CannotBeZero( String ),
 	Nan( String ),
+	NotAllowed( String ),
+	Infinite( String ),
 }
 
 impl Display for CsInvalidValue {
 	#[inline]
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		let mut tmp = match self {
-			Self::BelowMinimum( name ) | Self::AboveMaximum( name ) | Self::CannotBeZero( name ) | Self::Nan( name ) => name,
+			Self::BelowMinimum( name ) | Self::AboveMaximum( name ) | Self::CannotBeZero( name ) | Self::Nan( name ) | Self::NotAllowed( name ) | Self::Infinite( name ) => name,
 		}.clone();
 		
 		tmp.push_str( " cannot be " );
@@ -143,6 +147,8 @@ impl Display for CsInvalidValue {
 			Self::AboveMaximum( _ ) => "greater than max",
 			Self::CannotBeZero( _ ) => "equal to zero",
 			Self::Nan( _ ) => "NAN",
+			Self::NotAllowed( _ ) => "outside of the allowed set",
+			Self::Infinite( _ ) => "infinite",
 		} );
 		
 		tmp.fmt(f)
@@ -163,6 +169,11 @@ pub enum CsMissingComponent {
 	ModOfUpgrade,
 	ModOfBasePlusUpgrade,
 	ModMult,
+	ModMore,
+	Pipeline,
+	PipelineStage,
+	Modifier,
+	LedgerStat,
 }
 
 impl Display for CsMissingComponent {
@@ -177,6 +188,11 @@ impl Display for CsMissingComponent {
 			Self::ModOfUpgrade => "ModOfUpgrade",
 			Self::ModOfBasePlusUpgrade => "ModOfBasePlusUpgrade",
 			Self::ModMult => "ModMult",
+			Self::ModMore => "ModMore",
+			Self::Pipeline => "Pipeline",
+			Self::PipelineStage => "PipelineStage",
+			Self::Modifier => "Modifier",
+			Self::LedgerStat => "LedgerStat",
 		} );
 		
 		tmp.fmt(f)
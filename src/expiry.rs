@@ -0,0 +1,153 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ Modifier, ModifierId };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - ExpiryIndex
+
+/// Min-heap of expiration timestamps of modifiers stored in a container.
+/// 
+/// It is derived from the modifiers, therefore it is not serialized and containers leave it out of `PartialEq`.
+/// `Default` creates an index which is rebuilt on first use, e.g. after deserialization.
+#[derive( Debug, Clone, Default )]
+pub( crate ) struct ExpiryIndex {
+	heap: BinaryHeap< Reverse< u64 > >,
+	is_built: bool,
+}
+
+impl ExpiryIndex {
+	#[inline]
+	pub( crate ) fn new () -> Self {
+		ExpiryIndex {
+			heap: BinaryHeap::new(),
+			is_built: true,
+		}
+	}
+	
+	#[inline]
+	pub( crate ) fn push ( &mut self, modifier: &Modifier ) {
+		if !self.is_built {
+			return
+		}
+		
+		if let Some( exp_ts ) = modifier.expiration_ts() {
+			self.heap.push( Reverse( exp_ts ) );
+		}
+	}
+	
+	/// returns the lowest expiration timestamp
+	#[inline]
+	pub( crate ) fn next ( &self, mod_vec: &[ Modifier ] ) -> Option< u64 > {
+		if self.is_built {
+			return self.heap.peek().map( |tmp| tmp.0 )
+		}
+		
+		mod_vec.iter().filter_map( Modifier::expiration_ts ).min()
+	}
+	
+	/// Removes modifiers expired at `ts` in a single pass, returns the removed ones. <br>
+	/// Returns early when the lowest expiration timestamp is greater than `ts`.
+	#[inline]
+	pub( crate ) fn remove_expired ( &mut self, mod_vec: &mut Vec< Modifier >, ts: u64 ) -> Vec< Modifier > {
+		if !self.is_built {
+			self.rebuild( mod_vec );
+		}
+		
+		let mut count = 0;
+		
+		while let Some( Reverse( exp_ts ) ) = self.heap.peek() {
+			if *exp_ts > ts {
+				break
+			}
+			
+			self.heap.pop();
+			count += 1;
+		}// while
+		
+		if count == 0 {
+			return Vec::new()
+		}
+		
+		// `mod_vec` keeps its' allocation, only the expired ones are copied
+		let mut expired = Vec::with_capacity( count );
+		expired.extend( mod_vec.iter().filter( |el| el.has_expired( ts ) ).cloned() );
+		mod_vec.retain( |el| !el.has_expired( ts ) );
+		
+		expired
+	}
+	
+	/// Replaces the modifier with `id` by `new_val` or removes it when `new_val` is `None`, returns false when there is no such modifier. <br>
+	/// The index is rebuilt on next use.
+	#[inline]
+	pub( crate ) fn swap ( &mut self, mod_vec: &mut Vec< Modifier >, id: ModifierId, new_val: Option< Modifier > ) -> bool {
+		let Some( idx ) = mod_vec.iter().position( |tmp| tmp.id() == Some( id ) ) else {
+			return false
+		};
+		
+		match new_val {
+			Some( modifier ) => mod_vec[ idx ] = modifier,
+			None => { mod_vec.remove( idx ); },
+		}
+		
+		self.heap.clear();
+		self.is_built = false;
+		
+		true
+	}
+	
+	#[inline]
+	fn rebuild ( &mut self, mod_vec: &[ Modifier ] ) {
+		self.heap = mod_vec.iter().filter_map( Modifier::expiration_ts ).map( Reverse ).collect();
+		self.is_built = true;
+	}
+}
+
+// struct - ExpiryIndex
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	use super::*;
+	use crate::{ ModCommon, ModCalcMode, ModCalcStage };
+	
+	#[test]
+	fn basic_functional() {
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		let mut mod_vec = vec![
+			Modifier::new_expiring( common, 30 ),
+			Modifier::new_persistent( common ),
+			Modifier::new_expiring( common, 10 ),
+			Modifier::new_expiring( common, 20 ),
+		];
+		
+		// not built, e.g. deserialized
+		let mut index = ExpiryIndex::default();
+		assert_eq!( index.next( &mod_vec ), Some( 10 ) );
+		assert!( index.remove_expired( &mut mod_vec, 9 ).is_empty() );
+		assert_eq!( index.remove_expired( &mut mod_vec, 20 ).len(), 2 );
+		assert_eq!( mod_vec.len(), 2 );
+		assert_eq!( index.next( &mod_vec ), Some( 30 ) );
+		
+		let modifier = Modifier::new_expiring( common, 25 );
+		index.push( &modifier );
+		mod_vec.push( modifier );
+		assert_eq!( index.next( &mod_vec ), Some( 25 ) );
+		
+		assert_eq!( index.remove_expired( &mut mod_vec, 100 ).len(), 2 );
+		assert_eq!( mod_vec.len(), 1 );
+		assert_eq!( index.next( &mod_vec ), None );
+		
+		let mut index = ExpiryIndex::new();
+		assert!( index.remove_expired( &mut mod_vec, u64::MAX ).is_empty() );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,491 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStat, CharStatError, CsInvalidValue, CsLogicIssue, CsMissingComponent, UpgradeConf };
+
+// --Modules
+//------------------------------------------------------------------------------
+// enum - CostCurve
+
+/// Cost of buying the next rank, ranks are counted from 0.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub enum CostCurve {
+	/// every rank costs the same
+	Flat( u32 ),
+	/// rank N costs `base + N * step`
+	Linear{ base: u32, step: u32 },
+	/// rank N costs `table[ N ]`, ranks beyond the table cannot be bought
+	Table( Vec< u32 > ),
+}
+
+impl CostCurve {
+	/// # Errors
+	/// `CsInvalidValue::AboveMaximum( "rank" )` when `rank` is beyond `Table` <br>
+	#[inline]
+	pub fn rank_cost ( &self, rank: u32 ) -> Result< u32, CharStatError > {
+		match self {
+			Self::Flat( cost ) => Ok( *cost ),
+			Self::Linear { base, step } => Ok( base.saturating_add( step.saturating_mul( rank ) ) ),
+			Self::Table( table ) => {
+				let idx = usize::try_from( rank ).unwrap_or( usize::MAX );
+				
+				table.get( idx ).copied().ok_or_else( || CsInvalidValue::AboveMaximum( "rank".to_string() ).into() )
+			},
+		}
+	}
+	
+	/// Cost of buying `n` ranks, starting at `rank`. `Flat` and `Linear` are calculated in constant time.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::AboveMaximum( "rank" )` when the last rank is beyond `Table` or overflows `u32` <br>
+	/// `CsInvalidValue::AboveMaximum( "cost" )` when the cost overflows `u32` <br>
+	#[inline]
+	pub fn cost ( &self, rank: u32, n: u32 ) -> Result< u32, CharStatError > {
+		if n == 0 {
+			return Ok( 0 )
+		}
+		
+		let above_rank = || CharStatError::from( CsInvalidValue::AboveMaximum( "rank".to_string() ) );
+		let above_cost = || CharStatError::from( CsInvalidValue::AboveMaximum( "cost".to_string() ) );
+		let end = rank.checked_add( n ).ok_or_else( above_rank )?;
+		
+		match self {
+			Self::Flat( cost ) => cost.checked_mul( n ).ok_or_else( above_cost ),
+			Self::Linear { base, step } => {
+				// n * base + step * ( rank + ... + end - 1 ), u128 cannot overflow
+				let rank_sum = u128::from( n ) * ( u128::from( rank ) + u128::from( end ) - 1 ) / 2;
+				let total = u128::from( n ) * u128::from( *base ) + u128::from( *step ) * rank_sum;
+				
+				u32::try_from( total ).map_err( |_| above_cost() )
+			},
+			Self::Table( table ) => {
+				let start = usize::try_from( rank ).unwrap_or( usize::MAX );
+				let end = usize::try_from( end ).unwrap_or( usize::MAX );
+				let costs = table.get( start..end ).ok_or_else( above_rank )?;
+				
+				costs.iter().try_fold( 0_u32, |acc, tmp| acc.checked_add( *tmp ) ).ok_or_else( above_cost )
+			},
+		}
+	}
+}
+
+// enum - CostCurve
+//------------------------------------------------------------------------------
+// struct - Purchase
+
+/// A single entry of `UpgradeLedger::history`.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub struct Purchase {
+	index: usize,
+	ranks: u32,
+	cost: u32,
+}
+
+impl Purchase {
+	/// index of the stat
+	#[inline]
+	pub fn index ( &self ) -> usize {
+		self.index
+	}
+	
+	#[inline]
+	pub fn ranks ( &self ) -> u32 {
+		self.ranks
+	}
+	
+	#[inline]
+	pub fn cost ( &self ) -> u32 {
+		self.cost
+	}
+}
+
+// struct - Purchase
+//------------------------------------------------------------------------------
+// struct - UpgradeLedger
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+struct LedgerEntry {
+	curve: CostCurve,
+	step: f64,
+	ranks: u32,
+	/// upgrade value before the first rank, restored by respec
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	origin: Option< f64 >,
+}
+
+/// Shared pool of upgrade points spent on `UpgradeConf` of several stats.
+/// 
+/// Stats are registered with `add_stat` and matched by index with the `CharStat` slice passed to `purchase` and `respec`.
+/// Each rank increases the upgrade value by the `step` of its' stat, respec restores the value from before the first rank.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ UpgradeLedger, CostCurve, CharStat, BaseConf, UpgradeConf, Bounds, RoundingHelper };
+/// 
+/// let bounds = Bounds::new_const( 0.0, 10.0 ).unwrap();
+/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+/// let upgrade = UpgradeConf::new( 0.0, bounds, RoundingHelper::new_none() ).unwrap();
+/// let mut stats = vec![ CharStat::new_no_mod( base, Some( upgrade ) ); 2 ];
+/// 
+/// let mut ledger = UpgradeLedger::new( 10 );
+/// let str_idx = ledger.add_stat( CostCurve::Flat( 1 ), 1.0 ).unwrap();
+/// let dex_idx = ledger.add_stat( CostCurve::Linear{ base: 1, step: 1 }, 1.0 ).unwrap();
+/// 
+/// // 1 + 2 + 3
+/// assert_eq!( ledger.purchase( &mut stats, dex_idx, 3 ), Ok( 6 ) );
+/// assert_eq!( ledger.purchase( &mut stats, str_idx, 2 ), Ok( 2 ) );
+/// assert_eq!( ledger.points(), 2 );
+/// assert_eq!( stats[ dex_idx ].value(), 13.0 );
+/// 
+/// assert_eq!( ledger.respec( &mut stats ), Ok( 8 ) );
+/// assert_eq!( ledger.points(), 10 );
+/// assert_eq!( stats[ dex_idx ].value(), 10.0 );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub struct UpgradeLedger {
+	points: u32,
+	spent: u32,
+	entries: Vec< LedgerEntry >,
+	history: Vec< Purchase >,
+}
+
+impl UpgradeLedger {
+	#[inline]
+	pub fn new ( points: u32 ) -> Self {
+		UpgradeLedger {
+			points,
+			spent: 0,
+			entries: Vec::new(),
+			history: Vec::new(),
+		}
+	}
+	
+	/// Registers a stat and returns its' index.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `step` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `step` is infinite <br>
+	/// `CsInvalidValue::CannotBeZero` when `step` is 0.0 <br>
+	/// `CsInvalidValue::BelowMinimum` when `step` is negative <br>
+	#[inline]
+	pub fn add_stat ( &mut self, curve: CostCurve, step: f64 ) -> Result< usize, CharStatError > {
+		if step.is_nan() {
+			return Err( CsInvalidValue::Nan( "step".to_string() ).into() )
+		}
+		
+		if step.is_infinite() {
+			return Err( CsInvalidValue::Infinite( "step".to_string() ).into() )
+		}
+		
+		if step == 0.0 {
+			return Err( CsInvalidValue::CannotBeZero( "step".to_string() ).into() )
+		}
+		
+		if step < 0.0 {
+			return Err( CsInvalidValue::BelowMinimum( "step".to_string() ).into() )
+		}
+		
+		self.entries.push( LedgerEntry { curve, step, ranks: 0, origin: None } );
+		
+		Ok( self.entries.len() - 1 )
+	}
+	
+	/// points available to spend
+	#[inline]
+	pub fn points ( &self ) -> u32 {
+		self.points
+	}
+	
+	/// points spent since the last respec
+	#[inline]
+	pub fn spent ( &self ) -> u32 {
+		self.spent
+	}
+	
+	/// e.g. on level up
+	#[inline]
+	pub fn add_points ( &mut self, value: u32 ) {
+		self.points = self.points.saturating_add( value );
+	}
+	
+	/// number of registered stats
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.entries.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.entries.is_empty()
+	}
+	
+	/// ranks bought for stat at `index`
+	#[inline]
+	pub fn ranks ( &self, index: usize ) -> Option< u32 > {
+		self.entries.get( index ).map( |e| e.ranks )
+	}
+	
+	/// purchases since the last respec, oldest first
+	#[inline]
+	pub fn history ( &self ) -> &[ Purchase ] {
+		&self.history
+	}
+	
+	/// Cost of buying the next `n` ranks of stat at `index`.
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::LedgerStat` when `index` is not registered <br>
+	/// errors from `CostCurve::cost` <br>
+	#[inline]
+	pub fn cost ( &self, index: usize, n: u32 ) -> Result< u32, CharStatError > {
+		let entry = self.entry( index )?;
+		
+		entry.curve.cost( entry.ranks, n )
+	}
+	
+	/// Buys `n` ranks of stat at `index`, increasing its' upgrade value by `n * step`.
+	/// Either both points and the upgrade value change or neither. Returns the cost.
+	/// 
+	/// # Errors
+	/// `CsLogicIssue::LengthMismatch` when `stats` and the ledger differ in length <br>
+	/// `CsInvalidValue::CannotBeZero( "ranks" )` when `n` is 0 <br>
+	/// `CsInvalidValue::AboveMaximum( "ranks" )` when the number of ranks would overflow `u32` <br>
+	/// `CsInvalidValue::AboveMaximum( "cost" )` when there are not enough points <br>
+	/// errors from `cost` and `CharStat::inc_upgrade_value` <br>
+	#[inline]
+	pub fn purchase ( &mut self, stats: &mut [ CharStat ], index: usize, n: u32 ) -> Result< u32, CharStatError > {
+		self.check_len( stats )?;
+		
+		if n == 0 {
+			return Err( CsInvalidValue::CannotBeZero( "ranks".to_string() ).into() )
+		}
+		
+		let entry = self.entry( index )?;
+		let ranks = entry.ranks.checked_add( n ).ok_or_else( || CharStatError::from( CsInvalidValue::AboveMaximum( "ranks".to_string() ) ) )?;
+		let cost = entry.curve.cost( entry.ranks, n )?;
+		
+		if cost > self.points {
+			return Err( CsInvalidValue::AboveMaximum( "cost".to_string() ).into() )
+		}
+		
+		let stat = stats.get_mut( index ).ok_or( CsMissingComponent::LedgerStat )?;
+		let origin = stat.upgrade_conf().map( UpgradeConf::value );
+		
+		stat.inc_upgrade_value( entry.step * f64::from( n ) )?;
+		
+		if let Some( entry ) = self.entries.get_mut( index ) {
+			if entry.ranks == 0 {
+				entry.origin = origin;
+			}
+			
+			entry.ranks = ranks;
+		}
+		
+		self.points -= cost;
+		self.spent = self.spent.saturating_add( cost );
+		self.history.push( Purchase { index, ranks: n, cost } );
+		
+		Ok( cost )
+	}
+	
+	/// Refunds every purchase and restores upgrade values from before the first bought rank.
+	/// Either every stat is reset or none. Returns refunded points.
+	/// 
+	/// # Errors
+	/// `CsLogicIssue::LengthMismatch` when `stats` and the ledger differ in length <br>
+	/// errors from `CharStat::set_upgrade_value` and `CharStat::dec_upgrade_value` <br>
+	#[inline]
+	pub fn respec ( &mut self, stats: &mut [ CharStat ] ) -> Result< u32, CharStatError > {
+		self.check_len( stats )?;
+		
+		let mut updated = stats.to_vec();
+		
+		for ( stat, entry ) in updated.iter_mut().zip( &self.entries ) {
+			match entry.origin {
+				Some( origin ) => { stat.set_upgrade_value( origin )?; },
+				// ledger saved before the origin was recorded
+				None if entry.ranks > 0 => { stat.dec_upgrade_value( entry.step * f64::from( entry.ranks ) )?; },
+				None => {},
+			}
+		}
+		
+		stats.clone_from_slice( &updated );
+		
+		for entry in &mut self.entries {
+			entry.ranks = 0;
+			entry.origin = None;
+		}
+		
+		let refunded = self.spent;
+		
+		self.points = self.points.saturating_add( refunded );
+		self.spent = 0;
+		self.history.clear();
+		
+		Ok( refunded )
+	}
+}
+
+//priv
+impl UpgradeLedger {
+	#[inline( always )]
+	#[doc( hidden )]
+	fn entry ( &self, index: usize ) -> Result< &LedgerEntry, CharStatError > {
+		self.entries.get( index ).ok_or_else( || CsMissingComponent::LedgerStat.into() )
+	}
+	
+	#[inline( always )]
+	#[doc( hidden )]
+	fn check_len ( &self, stats: &[ CharStat ] ) -> Result<(), CharStatError > {
+		if stats.len() != self.entries.len() {
+			return Err( CsLogicIssue::LengthMismatch( stats.len(), self.entries.len() ).into() )
+		}
+		
+		Ok(())
+	}
+}// priv
+
+// struct - UpgradeLedger
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	use crate::{ BaseConf, Bounds, RoundingHelper, RoundingFnEnum };
+	
+	fn stats( n: usize, up_max: f64 ) -> Vec< CharStat > {
+		let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+		let up_bounds = Bounds::new_const( 0.0, up_max ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 0.0, up_bounds, RoundingHelper::new_none() ).unwrap();
+		
+		vec![ CharStat::new_no_mod( base, Some( upgrade ) ); n ]
+	}
+	
+	#[test]
+	fn cost_curves() {
+		let flat = CostCurve::Flat( 3 );
+		let linear = CostCurve::Linear { base: 1, step: 2 };
+		let table = CostCurve::Table( vec![ 1, 1, 2, 3, 5 ] );
+		
+		assert_eq!( flat.cost( 10, 3 ), Ok( 9 ) );
+		assert_eq!( linear.cost( 0, 3 ), Ok( 1 + 3 + 5 ) );
+		assert_eq!( linear.cost( 2, 1 ), Ok( 5 ) );
+		assert_eq!( table.cost( 1, 3 ), Ok( 1 + 2 + 3 ) );
+		
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "rank".to_string() ).into();
+		assert_eq!( table.cost( 3, 3 ), Err( expected.clone() ) );
+		assert_eq!( flat.cost( u32::MAX, 1 ), Err( expected ) );
+		assert_eq!( table.cost( 7, 0 ), Ok( 0 ) );
+		
+		// closed form matches the sum of ranks
+		let sum = ( 100..1000 ).map( |r| linear.rank_cost( r ).unwrap() ).sum::< u32 >();
+		assert_eq!( linear.cost( 100, 900 ), Ok( sum ) );
+		assert_eq!( flat.cost( 0, u32::MAX / 3 ), Ok( u32::MAX / 3 * 3 ) );
+		
+		// overflow is an error instead of a saturated cost
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "cost".to_string() ).into();
+		assert_eq!( flat.cost( 0, u32::MAX / 2 ), Err( expected.clone() ) );
+		assert_eq!( linear.cost( 0, 100_000 ), Err( expected.clone() ) );
+		assert_eq!( CostCurve::Table( vec![ u32::MAX, 1 ] ).cost( 0, 2 ), Err( expected ) );
+	}
+	
+	#[test]
+	fn purchase_and_respec() {
+		let mut cs = stats( 2, 3.0 );
+		let mut ledger = UpgradeLedger::new( 5 );
+		let a = ledger.add_stat( CostCurve::Flat( 1 ), 1.0 ).unwrap();
+		let b = ledger.add_stat( CostCurve::Table( vec![ 1, 2, 3 ] ), 0.5 ).unwrap();
+		
+		assert_eq!( ledger.purchase( &mut cs, b, 2 ), Ok( 3 ) );
+		assert_eq!( cs[ b ].upgrade_raw(), Ok( 1.0 ) );
+		
+		// not enough points
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "cost".to_string() ).into();
+		assert_eq!( ledger.purchase( &mut cs, a, 3 ), Err( expected ) );
+		
+		// upgrade bounds are checked, points stay untouched
+		let mut ledger_2 = UpgradeLedger::new( 10 );
+		let c = ledger_2.add_stat( CostCurve::Flat( 1 ), 2.0 ).unwrap();
+		ledger_2.add_stat( CostCurve::Flat( 1 ), 1.0 ).unwrap();
+		
+		let mut cs_2 = stats( 2, 3.0 );
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "value".to_string() ).into();
+		assert_eq!( ledger_2.purchase( &mut cs_2, c, 2 ), Err( expected ) );
+		assert_eq!( ledger_2.points(), 10 );
+		assert_eq!( ledger_2.ranks( c ), Some( 0 ) );
+		
+		assert_eq!( ledger.purchase( &mut cs, a, 2 ), Ok( 2 ) );
+		assert_eq!( ledger.points(), 0 );
+		assert_eq!( ledger.history().len(), 2 );
+		assert_eq!( ledger.history()[ 1 ].cost(), 2 );
+		
+		let expected: CharStatError = CsLogicIssue::LengthMismatch( 1, 2 ).into();
+		assert_eq!( ledger.respec( &mut cs[ ..1 ] ), Err( expected ) );
+		
+		assert_eq!( ledger.respec( &mut cs ), Ok( 5 ) );
+		assert_eq!( ledger.points(), 5 );
+		assert_eq!( ledger.spent(), 0 );
+		assert!( ledger.history().is_empty() );
+		assert_eq!( cs[ a ].value(), 10.0 );
+		assert_eq!( cs[ b ].value(), 10.0 );
+		
+		assert_eq!( ledger.cost( 2, 1 ), Err( CsMissingComponent::LedgerStat.into() ) );
+	}
+	
+	#[test]
+	fn invalid_input() {
+		let mut ledger = UpgradeLedger::new( 10 );
+		
+		assert_eq!( ledger.add_stat( CostCurve::Flat( 1 ), -1.0 ), Err( CsInvalidValue::BelowMinimum( "step".to_string() ).into() ) );
+		assert_eq!( ledger.add_stat( CostCurve::Flat( 1 ), f64::INFINITY ), Err( CsInvalidValue::Infinite( "step".to_string() ).into() ) );
+		assert!( ledger.is_empty() );
+		
+		// free ranks, the count cannot overflow
+		let bounds = Bounds::new_const( 0.0, f64::MAX ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 0.0, bounds, RoundingHelper::new_none() ).unwrap();
+		let mut cs = vec![ CharStat::new_no_mod( base, Some( upgrade ) ) ];
+		let idx = ledger.add_stat( CostCurve::Flat( 0 ), 1.0 ).unwrap();
+		
+		ledger.entries[ idx ].ranks = u32::MAX;
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "ranks".to_string() ).into();
+		assert_eq!( ledger.purchase( &mut cs, idx, 1 ), Err( expected ) );
+		assert_eq!( ledger.ranks( idx ), Some( u32::MAX ) );
+		assert_eq!( cs[ 0 ].upgrade_raw(), Ok( 0.0 ) );
+	}
+	
+	#[test]
+	fn respec_restores_value() {
+		let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let rounding = RoundingHelper::new( RoundingFnEnum::Round, None );
+		let upgrade = UpgradeConf::new( 1.0, bounds, rounding ).unwrap();
+		let mut cs = vec![ CharStat::new_no_mod( base, Some( upgrade ) ) ];
+		
+		let mut ledger = UpgradeLedger::new( 10 );
+		let idx = ledger.add_stat( CostCurve::Flat( 1 ), 0.5 ).unwrap();
+		
+		// 1.5 and 2.5 are rounded up, 2 * 0.5 no longer matches the increase
+		ledger.purchase( &mut cs, idx, 1 ).unwrap();
+		ledger.purchase( &mut cs, idx, 1 ).unwrap();
+		assert_eq!( cs[ 0 ].upgrade_raw(), Ok( 3.0 ) );
+		
+		assert_eq!( ledger.respec( &mut cs ), Ok( 2 ) );
+		assert_eq!( cs[ 0 ].upgrade_raw(), Ok( 1.0 ) );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -123,7 +123,6 @@
 	clippy::manual_let_else,
 	clippy::manual_ok_or, 
 	clippy::manual_string_new,
-	clippy::match_on_vec_items,
 	clippy::match_same_arms,
 	clippy::match_wild_err_arm,
 	clippy::match_wildcard_for_single_variants,
@@ -242,6 +241,10 @@
 //------------------------------------------------------------------------------
 // --Imports
 
+use std::sync::OnceLock;
+use std::borrow::Cow;
+use std::fmt::{ Display, Formatter };
+
 #[cfg( feature = "serde" )]
 use serde::{ Serialize, Deserialize };
 
@@ -258,6 +261,8 @@ pub use errors::*;
 mod modifier;
 pub use modifier::*;
 
+mod expiry;
+
 mod base;
 pub use base::*;
 
@@ -273,17 +278,99 @@ pub use mod_mgr::*;
 mod mod_mult;
 pub use mod_mult::*;
 
+mod mod_more;
+pub use mod_more::*;
+
+mod pipeline;
+pub use pipeline::*;
+
+mod mod_fn;
+pub use mod_fn::*;
+
+mod rounding;
+pub use rounding::*;
+
+mod soft_cap;
+pub use soft_cap::*;
+
+mod bound_spec;
+pub use bound_spec::*;
+
+mod allocation;
+pub use allocation::*;
+
+mod ledger;
+pub use ledger::*;
+
+mod batch;
+pub use batch::*;
+
+mod lite;
+pub use lite::*;
+
+mod shared;
+pub use shared::*;
+
+mod listener;
+pub use listener::*;
+
+mod threshold;
+pub use threshold::*;
+
+mod total;
+pub use total::*;
+
 #[cfg( feature = "builder" )]
 mod builder;
 #[cfg( feature = "builder" )]
 pub use builder::*;
 
+#[cfg( feature = "ruleset" )]
+pub mod ruleset;
+
 // --Modules
 //------------------------------------------------------------------------------
+// struct - Calculated
+
+/// Values recalculated by `CharStat::flush`, calculated without changing the stat.
+/// `None` results of the containers mean their stored values are up to date.
+#[derive( Debug, Clone, PartialEq, Default )]
+struct Calculated {
+	current_value: f64,
+	val_base: f64,
+	val_base_mod: f64,
+	val_upgrade: f64,
+	val_upgrade_mod: f64,
+	val_base_plus_upgrade_mod: f64,
+	val_mod_mult: f64,
+	overflow: Option< CsInvalidValue >,
+	stage_overflow: u8,
+	mod_of_base: Option< ModConfCalc >,
+	mod_of_upgrade: Option< ModConfCalc >,
+	mod_of_base_plus_upgrade: Option< ModConfCalc >,
+	pipeline: Option< PipelineCalc >,
+}
+
+impl Calculated {
+	/// clamps an infinite value of `stage` ( a dirty flag of `CharStat` ) to `f64::MIN` / `f64::MAX` and records the overflow
+	#[inline]
+	fn clamp_stage ( &mut self, stage: u8, value: f64 ) -> f64 {
+		if value.is_infinite() {
+			self.stage_overflow |= stage;
+		} else {
+			self.stage_overflow &= !stage;
+		}
+		
+		value.clamp( f64::MIN, f64::MAX )
+	}
+}
+
+// struct - Calculated
+//------------------------------------------------------------------------------
 // struct - CharStat
 
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, PartialEq )]
+#[derive( Debug, Clone )]
 pub struct CharStat {
 	current_value: f64,
 	time_stamp: u64,
@@ -301,6 +388,38 @@ pub struct CharStat {
 	mod_of_upgrade:						Option< Box< ModConf > >,
 	mod_of_base_plus_upgrade:	Option< Box< ModConf > >,
 	mod_mult:									Option< Box< ModMultConf > >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	mod_more:									Option< Box< ModMoreConf > >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	pipeline:									Option< Box< Pipeline > >,
+	
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	total_bounds:							Option< TotalBounds >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	total_rounding:						Option< RoundingHelper >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	total_soft_cap:						Option< SoftCap >,
+	/// error of the last calculation, a deserialized stat is recalculated anyway
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	overflow:									Option< CsInvalidValue >,
+	/// dirty flags of the stages clamped by the last calculation
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	stage_overflow:						u8,
+	/// deserialized stat is recalculated on the first read
+	#[cfg_attr( feature = "serde", serde( skip, default = "CharStat::dirty_all" ) )]
+	dirty:										u8,
+	/// values calculated by getters of a dirty stat, reused by `flush` and dropped by every mutation
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	cache:										OnceLock< Box< Calculated > >,
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	listener:									Option< SharedListener >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	thresholds:								Option< Box< Thresholds > >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	next_mod_id:							u64,
+	/// kept outside of `Thresholds`, which are dropped with the last threshold
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	next_threshold_id:				u32,
 }
 
 impl CharStat {
@@ -360,11 +479,23 @@ impl CharStat {
 			mod_of_upgrade,
 			mod_of_base_plus_upgrade,
 			mod_mult,
+			mod_more: None,
+			pipeline: None,
+			
+			total_bounds: None,
+			total_rounding: None,
+			total_soft_cap: None,
+			overflow: None,
+			stage_overflow: 0,
+			dirty: CharStat::DIRTY_ALL,
+			cache: OnceLock::new(),
+			listener: None,
+			thresholds: None,
+			next_mod_id: 0,
+			next_threshold_id: 0,
 		};
 		
-		out.update_base();
-		out.update_upgrade();
-		out.update_current_value();
+		out.flush();
 		
 		out
 	}
@@ -388,10 +519,23 @@ impl CharStat {
 			mod_of_upgrade: None,
 			mod_of_base_plus_upgrade: None,
 			mod_mult: None,
+			mod_more: None,
+			pipeline: None,
+			
+			total_bounds: None,
+			total_rounding: None,
+			total_soft_cap: None,
+			overflow: None,
+			stage_overflow: 0,
+			dirty: CharStat::DIRTY_ALL,
+			cache: OnceLock::new(),
+			listener: None,
+			thresholds: None,
+			next_mod_id: 0,
+			next_threshold_id: 0,
 		};
 		
-		out.update_base();
-		out.update_current_value();
+		out.flush();
 		
 		out
 	}
@@ -417,33 +561,108 @@ impl CharStat {
 			mod_of_upgrade: None,
 			mod_of_base_plus_upgrade: None,
 			mod_mult: None,
+			mod_more: None,
+			pipeline: None,
+			
+			total_bounds: None,
+			total_rounding: None,
+			total_soft_cap: None,
+			overflow: None,
+			stage_overflow: 0,
+			dirty: CharStat::DIRTY_ALL,
+			cache: OnceLock::new(),
+			listener: None,
+			thresholds: None,
+			next_mod_id: 0,
+			next_threshold_id: 0,
 		};
 		
-		out.update_base();
-		out.update_upgrade();
-		out.update_current_value();
+		out.flush();
 		
 		out
 	}
 	
-	/// returns the current ( total ) value
+	/// Creates `CharStat` which uses a user defined `Pipeline` instead of `mod_of_*` components.
+	/// Modifiers of Base, Upgrade, BasePlusUpgrade and Custom stages are dispatched to the pipeline.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, Pipeline, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new_pipeline( base, None, Pipeline::classic( Some( mod_of_base ), None, None ).unwrap(), None );
+	/// 
+	/// let common = ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+	/// cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+	/// assert_eq!( cs.value(), 15.0 );
+	/// ```
+	#[inline]
+	pub fn new_pipeline ( base: BaseConf, upgrade: Option< UpgradeConf >, pipeline: Pipeline, mod_mult: Option< ModMultConf > ) -> Self {
+		let val_mod_mult = if let Some( tmp ) = &mod_mult {
+			tmp.value()
+		} else {
+			1.0
+		};
+		
+		let mut out = CharStat {
+			current_value: 0.0,
+			time_stamp: 0,
+			
+			val_base: 0.0,
+			val_base_mod: 0.0,
+			val_upgrade: 0.0,
+			val_upgrade_mod: 0.0,
+			val_base_plus_upgrade_mod: 0.0,
+			val_mod_mult,
+			
+			base: Box::new( base ),
+			upgrade: upgrade.map( Box::new ),
+			mod_of_base: None,
+			mod_of_upgrade: None,
+			mod_of_base_plus_upgrade: None,
+			mod_mult: mod_mult.map( Box::new ),
+			mod_more: None,
+			pipeline: Some( Box::new( pipeline ) ),
+			
+			total_bounds: None,
+			total_rounding: None,
+			total_soft_cap: None,
+			overflow: None,
+			stage_overflow: 0,
+			dirty: CharStat::DIRTY_ALL,
+			cache: OnceLock::new(),
+			listener: None,
+			thresholds: None,
+			next_mod_id: 0,
+			next_threshold_id: 0,
+		};
+		
+		out.flush();
+		
+		out
+	}
+	
+	/// returns the current ( total ) value, see `flush`
 	#[inline]
 	pub fn value ( &self ) -> f64 {
-		self.current_value
+		self.flushed().current_value
 	}
 	
-	/// Sets the timestamp and checks for expired modifiers.
+	/// Sets the timestamp and removes expired modifiers.
+	/// Returned `StatReport` holds the removed modifiers ( `Modifier::calc_stage` returns their stage ) and the change of the value they caused, pending changes are flushed first.
 	/// 
 	/// # Examples
 	/// ```rust
-	/// use char_stat::{ CharStat, BaseConf, Bounds, RoundingHelper, CharStatError, CsLogicIssue };
+	/// use char_stat::{ CharStat, BaseConf, Bounds, RoundingHelper, CharStatError, CsLogicIssue, StatReport };
 	/// 
 	/// // example uses `new_minimal` for the sake of brevity
 	/// // `new_minimal` and `new_no_mod` does not allow any modifiers which makes setting TS pointless
 	/// let bounds = Bounds::new_const( 0.0, 1.0 ).unwrap();
 	/// let base = BaseConf::new( 0.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
 	/// let mut cs = CharStat::new_minimal( base );
-	/// assert_eq!( cs.set_ts( 100 ), Ok(()) );
+	/// assert_eq!( cs.set_ts( 100 ), Ok( StatReport::default() ) );
 	/// 
 	/// // check for "time travel"
 	/// let err = Err( CsLogicIssue::TimeTravel.into() );
@@ -453,19 +672,17 @@ impl CharStat {
 	/// # Errors
 	/// `CsLogicIssue::TimeTravel` when `new_val` > `self.time_stamp` <br>
 	#[inline]
-	pub fn set_ts( &mut self, new_val: u64 ) -> Result<(), CharStatError > {
+	pub fn set_ts( &mut self, new_val: u64 ) -> Result< StatReport, CharStatError > {
 		if new_val < self.time_stamp {
 			return Err( CsLogicIssue::TimeTravel.into() )
 		}
 		
 		self.time_stamp = new_val;
 		
-		self.remove_expired_modifiers();
-		
-		Ok(())
+		Ok( self.remove_expired_modifiers() )
 	}
 	
-	/// Sets the timestamp and checks for expired modifiers.
+	/// Sets the timestamp and removes expired modifiers, same as `set_ts`.
 	/// Performs no checks against new TS being lower (earlier)
 	/// 
 	/// # Examples
@@ -481,14 +698,53 @@ impl CharStat {
 	/// cs.set_ts( 0 );
 	/// ```
 	#[inline]
-	pub fn set_ts_unchecked( &mut self, new_val: u64 ) {
+	pub fn set_ts_unchecked( &mut self, new_val: u64 ) -> StatReport {
 		self.time_stamp = new_val;
 		
-		self.remove_expired_modifiers();
+		self.remove_expired_modifiers()
+	}
+	
+	#[inline]
+	pub fn ts ( &self ) -> u64 {
+		self.time_stamp
+	}
+	
+	/// Returns the lowest expiration timestamp of all modifiers, `None` when no modifier expires. <br>
+	/// `set_ts` with a lower timestamp does not remove any modifier and returns early.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 10.0 ).unwrap();
+	/// let base = BaseConf::new( 1.0, false, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// assert_eq!( cs.next_expiration(), None );
+	/// 
+	/// let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+	/// cs.append_modifier( Modifier::new_expiring( common, 50 ) ).unwrap();
+	/// cs.append_modifier( Modifier::new_expiring( common, 20 ) ).unwrap();
+	/// assert_eq!( cs.next_expiration(), Some( 20 ) );
+	/// 
+	/// cs.set_ts( 20 ).unwrap();
+	/// assert_eq!( cs.next_expiration(), Some( 50 ) );
+	/// assert_eq!( cs.value(), 2.0 );
+	/// ```
+	#[inline]
+	pub fn next_expiration ( &self ) -> Option< u64 > {
+		let mods = [ &self.mod_of_base, &self.mod_of_upgrade, &self.mod_of_base_plus_upgrade ];
+		
+		mods.into_iter().flatten().filter_map( |tmp| tmp.next_expiration() )
+			.chain( self.mod_mult.as_ref().and_then( |tmp| tmp.next_expiration() ) )
+			.chain( self.mod_more.as_ref().and_then( |tmp| tmp.next_expiration() ) )
+			.chain( self.pipeline.as_ref().and_then( |tmp| tmp.next_expiration() ) )
+			.min()
 	}
 	
 	/// Appends modifier to list of active modifiers. Each ModConf maintains its own list (Vec) of active modifiers.
 	/// This method will dispatch modifiers based on output from `modifier.stage()`.
+	/// The modifier is given a new `ModifierId`, returned in `StatReport::added`.
 	/// 
 	/// # Examples
 	/// ```rust
@@ -504,38 +760,43 @@ impl CharStat {
 	/// let mod_base = Modifier::new_expiring( common, 24 );
 	///
 	/// let mut cs = CharStat::new( base, None, mod_conf, None, None, None );
-	/// assert_eq!( cs.append_modifier( mod_base ), Ok(()) );
+	/// let id = cs.append_modifier( mod_base ).unwrap().added.unwrap();
 	/// assert_eq!( cs.value(), 1.0 );
+	/// assert_eq!( cs.modifier( id ).unwrap().remaining(), Some( 24 ) );
 	/// ```
 	/// 
 	/// # Errors
 	/// `CsMissingComponent::*` when associated `ModConf` or `ModMult` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "custom_fn" )` when a `Custom` mode modifier has no function <br>
 	#[inline]
-	pub fn append_modifier( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
-		match &modifier.calc_stage() {
-			ModCalcStage::Base => self.append_base_mod( modifier ),
-			ModCalcStage::Upgrade => self.append_upgrade_mod( modifier ),
-			ModCalcStage::BasePlusUpgrade => self.append_base_plus_upgrade_mod( modifier ),
-			ModCalcStage::ModMult => self.append_modmult( modifier ),
-		}?;
+	pub fn append_modifier( &mut self, mut modifier: Modifier ) -> Result< StatReport, CharStatError > {
+		let id = ModifierId( self.next_mod_id );
+		modifier.set_id( id );
 		
-		self.update_current_value();
-		Ok(())
+		let stage = modifier.calc_stage();
+		let flags = self.push_modifier( modifier )?;
+		// a rejected modifier does not consume the id
+		self.next_mod_id += 1;
+		
+		let mut out = self.mark_dirty( flags, StatChangeCause::ModifierAdded( stage ) );
+		out.added = Some( id );
+		
+		Ok( out )
 	}
 	
 	/// returns the value of base with multiplier and modifiers applied
 	#[inline]
 	pub fn base ( &self ) -> f64 {
-		if self.mod_of_base.is_some() {
-			return self.val_base + self.val_base_mod
-		}
-		self.val_base
+		let cs = self.flushed();
+		
+		// 0.0 without modifiers of base
+		cs.val_base + cs.val_base_mod
 	}
 	
 	/// returns the "raw" value of base with multiplier but not modifiers
 	#[inline]
 	pub fn base_raw ( &self ) -> f64 {
-		self.val_base
+		self.flushed().val_base
 	}
 	
 	/// returns the value of upgrade with modifiers applied.
@@ -544,11 +805,13 @@ impl CharStat {
 	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
 	#[inline]
 	pub fn upgrade ( &self ) -> Result< f64, CharStatError > {
-		match ( &self.upgrade, &self.mod_of_upgrade ) {
-			( Some(_), Some(_) ) => Ok( self.val_upgrade + self.val_upgrade_mod ),
-			( Some(_), None ) => Ok( self.val_upgrade ),
-			( _, _ ) => Err( CsMissingComponent::Upgrade.into() ),
+		if self.upgrade.is_some() {
+			let cs = self.flushed();
+			
+			return Ok( cs.val_upgrade + cs.val_upgrade_mod )
 		}
+		
+		Err( CsMissingComponent::Upgrade.into() )
 	}
 	
 	/// returns the "raw" value of upgrade without modifiers.
@@ -558,164 +821,1006 @@ impl CharStat {
 	#[inline]
 	pub fn upgrade_raw ( &self ) -> Result< f64, CharStatError > {
 		if self.upgrade.is_some() {
-			return Ok( self.val_upgrade )
+			return Ok( self.flushed().val_upgrade )
 		}
 		
 		Err( CsMissingComponent::Upgrade.into() )
 	}
+	
+	/// Returns value of every calculation stage.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, UpgradeConf, TotalBounds, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let upgrade = UpgradeConf::new( 5.0, bounds, RoundingHelper::new_none() ).unwrap();
+	/// let mut cs = CharStat::new_no_mod( base, Some( upgrade ) );
+	/// 
+	/// // total cannot exceed 120% of base
+	/// let total_bounds = TotalBounds::new( Bounds::new_const( 0.0, 1.2 ).unwrap(), false, true );
+	/// cs.set_total_bounds( Some( total_bounds ) );
+	/// 
+	/// let breakdown = cs.breakdown();
+	/// assert_eq!( breakdown.sum(), 15.0 );
+	/// assert_eq!( breakdown.total(), 12.0 );
+	/// assert_eq!( cs.value(), 12.0 );
+	/// ```
+	#[inline]
+	pub fn breakdown ( &self ) -> ValueBreakdown {
+		let cs = self.flushed();
+		let sum = self.stage_sum( &cs );
+		let before_cap = self.mod_more.as_ref().map_or( sum, |tmp| tmp.apply( sum ) );
+		let total_soft_cap_loss = self.total_soft_cap.as_ref().map_or( 0.0, |tmp| before_cap - tmp.apply( before_cap ) );
+		
+		ValueBreakdown {
+			mod_mult: cs.val_mod_mult,
+			base: cs.val_base,
+			upgrade: cs.val_upgrade,
+			mod_of_base: cs.val_base_mod,
+			mod_of_upgrade: cs.val_upgrade_mod,
+			mod_of_base_plus_upgrade: cs.val_base_plus_upgrade_mod,
+			sum,
+			more: self.mod_more.as_ref().map_or( 1.0, |tmp| tmp.value() ),
+			mod_soft_cap_loss: self.mod_soft_cap_loss( &cs ),
+			total_soft_cap_loss,
+			total: cs.current_value,
+		}
+	}
+	
+	/// Returns `Pipeline` when `CharStat` was created with `new_pipeline`. <br>
+	/// Values of its stages are those of the last `flush`.
+	#[inline]
+	pub fn pipeline ( &self ) -> Option< &Pipeline > {
+		self.pipeline.as_deref()
+	}
+	
+	#[inline]
+	pub fn base_conf ( &self ) -> &BaseConf {
+		&self.base
+	}
+	
+	#[inline]
+	pub fn upgrade_conf ( &self ) -> Option< &UpgradeConf > {
+		self.upgrade.as_deref()
+	}
 }
 
+// more
+/// Methods for manipulation of ModMoreConf
+impl CharStat {
+	/// Sets ( or removes with `None` ) the "more" stage, applied after the additive stages.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModMoreConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mut cs = CharStat::new_minimal( base );
+	/// cs.set_mod_more( Some( ModMoreConf::new( bounds, RoundingHelper::new_none() ) ) );
+	/// 
+	/// // two 50% more modifiers compound
+	/// let common = ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::More ).unwrap();
+	/// cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+	/// cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+	/// assert_eq!( cs.value(), 22.5 );
+	/// ```
+	#[inline]
+	pub fn set_mod_more ( &mut self, new_val: Option< ModMoreConf > ) -> StatReport {
+		self.mod_more = new_val.map( Box::new );
+		self.mark_dirty( CharStat::DIRTY_TOTAL, StatChangeCause::Config )
+	}
+	
+	#[inline]
+	pub fn mod_more ( &self ) -> Option< &ModMoreConf > {
+		self.mod_more.as_deref()
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::ModMore` when `ModMoreConf` is missing <br>
+	#[inline]
+	pub fn set_mod_more_rounding ( &mut self, new_val: RoundingHelper ) -> Result< StatReport, CharStatError > {
+		if let Some( mod_more ) = &mut self.mod_more {
+			mod_more.set_rounding( new_val );
+			return Ok( self.mark_dirty( CharStat::DIRTY_TOTAL, StatChangeCause::Config ) )
+		}
+		
+		Err( CsMissingComponent::ModMore.into() )
+	}
+}// more
+
+// total
+/// Methods for manipulation of total bounds and rounding
+impl CharStat {
+	/// Bounds applied to the total value, before rounding. `None` disables them.
+	#[inline]
+	pub fn set_total_bounds ( &mut self, new_val: Option< TotalBounds > ) -> StatReport {
+		self.total_bounds = new_val;
+		self.mark_dirty( CharStat::DIRTY_TOTAL, StatChangeCause::Config )
+	}
+	
+	/// Rounding applied to the total value, after bounds. `None` disables it.
+	#[inline]
+	pub fn set_total_rounding ( &mut self, new_val: Option< RoundingHelper > ) -> StatReport {
+		self.total_rounding = new_val;
+		self.mark_dirty( CharStat::DIRTY_TOTAL, StatChangeCause::Config )
+	}
+	
+	#[inline]
+	pub fn total_bounds ( &self ) -> Option< &TotalBounds > {
+		self.total_bounds.as_ref()
+	}
+	
+	#[inline]
+	pub fn total_rounding ( &self ) -> Option< &RoundingHelper > {
+		self.total_rounding.as_ref()
+	}
+	
+	/// Soft cap applied to the total value, after "more" modifiers and before total bounds. `None` disables it.
+	#[inline]
+	pub fn set_total_soft_cap ( &mut self, new_val: Option< SoftCap > ) -> StatReport {
+		self.total_soft_cap = new_val;
+		self.mark_dirty( CharStat::DIRTY_TOTAL, StatChangeCause::Config )
+	}
+	
+	#[inline]
+	pub fn total_soft_cap ( &self ) -> Option< &SoftCap > {
+		self.total_soft_cap.as_ref()
+	}
+	
+	/// Returns true when the last calculation did not produce a finite value. <br>
+	/// Infinite stage values and total are clamped to `f64::MIN` / `f64::MAX`, NaN keeps the previous value.
+	#[inline]
+	pub fn has_overflowed ( &self ) -> bool {
+		self.flushed().overflow.is_some()
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Infinite` when a stage or the total overflowed, see `has_overflowed` <br>
+	/// `CsInvalidValue::Nan` when the total is NaN, the returned value would be the previous one <br>
+	#[inline]
+	pub fn value_checked ( &self ) -> Result< f64, CharStatError > {
+		let cs = self.flushed();
+		
+		if let Some( err ) = &cs.overflow {
+			return Err( err.clone().into() )
+		}
+		
+		Ok( cs.current_value )
+	}
+}// total
+
+// dynamic bounds
+/// Methods for manipulation of dynamic bounds
+impl CharStat {
+	/// Sets ( or removes with `None` ) dynamic bounds of the upgrade value, the stored value is not changed.
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, UpgradeConf, DynBounds, BoundSpec, SharedValue, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let upgrade = UpgradeConf::new( 50.0, bounds, RoundingHelper::new_none() ).unwrap();
+	/// let mut cs = CharStat::new_no_mod( base, Some( upgrade ) );
+	/// 
+	/// // upgrade cannot exceed the value of another stat
+	/// let other_stat = SharedValue::new( 20.0 );
+	/// cs.set_upgrade_dyn_bounds( Some( DynBounds::new( None, Some( BoundSpec::source( other_stat.clone() ) ) ) ) ).unwrap();
+	/// assert_eq!( cs.value(), 30.0 );
+	/// 
+	/// other_stat.set( 30.0 );
+	/// cs.refresh();
+	/// assert_eq!( cs.value(), 40.0 );
+	/// ```
+	#[inline]
+	pub fn set_upgrade_dyn_bounds ( &mut self, new_val: Option< DynBounds > ) -> Result< StatReport, CharStatError > {
+		if let Some( upgrade ) = &mut self.upgrade {
+			upgrade.set_dyn_bounds( new_val );
+			let out = self.mark_dirty( CharStat::DIRTY_ALL, StatChangeCause::Config );
+			self.flush();
+			
+			return Ok( out )
+		}
+		
+		Err( CsMissingComponent::Upgrade.into() )
+	}
+	
+	/// Sets ( or removes with `None` ) dynamic bounds of `ModConf` of `stage`.
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade` or `CsMissingComponent::ModOfBasePlusUpgrade` when `ModConf` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is not `Base`, `Upgrade` or `BasePlusUpgrade` <br>
+	#[inline]
+	pub fn set_mod_dyn_bounds ( &mut self, stage: ModCalcStage, new_val: Option< DynBounds > ) -> Result< StatReport, CharStatError > {
+		let ( mod_conf, missing ) = match stage {
+			ModCalcStage::Base => ( &mut self.mod_of_base, CsMissingComponent::ModOfBase ),
+			ModCalcStage::Upgrade => ( &mut self.mod_of_upgrade, CsMissingComponent::ModOfUpgrade ),
+			ModCalcStage::BasePlusUpgrade => ( &mut self.mod_of_base_plus_upgrade, CsMissingComponent::ModOfBasePlusUpgrade ),
+			_ => return Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() ),
+		};
+		
+		if let Some( mod_conf ) = mod_conf {
+			mod_conf.set_dyn_bounds( new_val );
+			let out = self.mark_dirty( CharStat::DIRTY_ALL, StatChangeCause::Config );
+			self.flush();
+			
+			return Ok( out )
+		}
+		
+		Err( missing.into() )
+	}
+	
+	/// Recalculates every stage, needed after a value used by `BoundSpec` changes.
+	#[inline]
+	pub fn refresh ( &mut self ) -> StatReport {
+		let out = self.mark_dirty( CharStat::DIRTY_ALL, StatChangeCause::Refresh );
+		self.flush();
+		
+		out
+	}
+}// dynamic bounds
+
+// lazy update
+/// Mutations only mark the affected stages dirty, they are recalculated by `flush`.
+impl CharStat {
+	/// Recalculates stages changed since the last flush. <br>
+	/// Getters of a dirty `CharStat` return the same values, the first one calculates them and caches the result
+	/// until the next mutation, `flush` then stores it instead of calculating again. <br>
+	/// `mod_conf` and `pipeline` return the containers with values of the last `flush`. <br>
+	/// `PartialEq` compares the flushed state.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// 
+	/// let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+	/// for _ in 0..20 {
+	///     cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+	/// }
+	/// assert!( cs.is_dirty() );
+	/// 
+	/// cs.flush();
+	/// assert!( !cs.is_dirty() );
+	/// assert_eq!( cs.value(), 30.0 );
+	/// ```
+	#[inline]
+	pub fn flush ( &mut self ) {
+		if self.dirty == 0 {
+			return
+		}
+		
+		// a getter may have calculated the values already
+		let calc = match self.cache.take() {
+			Some( calc ) => *calc,
+			None => self.calculate(),
+		};
+		
+		self.store( calc );
+		self.dirty = 0;
+	}
+	
+	/// returns true when some stages changed since the last `flush`
+	#[inline]
+	pub fn is_dirty ( &self ) -> bool {
+		self.dirty != 0
+	}
+}// lazy update
+
+// listener
+/// Methods for observing changes of the value
+impl CharStat {
+	/// Sets ( or removes with `None` ) listener notified after a mutation changed the value, e.g. to update UI. <br>
+	/// A stat with a listener recalculates after every mutation instead of lazily, see `flush`.
+	/// Clones share the listener, it is not serialized.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// use char_stat::{ ChangeLog, SharedListener, StatChange, StatChangeCause };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// 
+	/// let log = ChangeLog::new();
+	/// cs.set_listener( Some( SharedListener::new( log.clone() ) ) );
+	/// 
+	/// let common = ModCommon::new( 5.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+	/// cs.append_modifier( Modifier::new_expiring( common, 10 ) ).unwrap();
+	/// cs.set_base_value( 20.0 ).unwrap();
+	/// cs.set_ts( 10 ).unwrap();
+	/// 
+	/// assert_eq!( log.take(), vec![
+	///     StatChange { old: 10.0, new: 15.0, cause: StatChangeCause::ModifierAdded( ModCalcStage::Base ) },
+	///     StatChange { old: 15.0, new: 25.0, cause: StatChangeCause::Base },
+	///     StatChange { old: 25.0, new: 20.0, cause: StatChangeCause::ModifierExpired },
+	/// ] );
+	/// ```
+	#[inline]
+	pub fn set_listener ( &mut self, new_val: Option< SharedListener > ) {
+		self.flush();
+		self.listener = new_val;
+	}
+	
+	#[inline]
+	pub fn listener ( &self ) -> Option< &SharedListener > {
+		self.listener.as_ref()
+	}
+}// listener
+
+// thresholds
+/// Methods for manipulation of thresholds
+impl CharStat {
+	/// Adds a threshold reported by the `StatReport` of mutations when the value crosses it. <br>
+	/// The current state is evaluated without reporting, see `is_threshold_active`.
+	/// A stat with thresholds recalculates after every mutation instead of lazily, see `flush`.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, UpgradeConf, ThresholdSpec, ThresholdDirection, BoundSpec, StageRef, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 100.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mut hp = CharStat::new_minimal( base );
+	/// 
+	/// // HP below 25%, inactive again at 30%
+	/// let spec = ThresholdSpec::new( BoundSpec::Const( 25.0 ), ThresholdDirection::Below, 5.0 ).unwrap();
+	/// let low_hp = hp.add_threshold( spec );
+	/// 
+	/// assert!( hp.set_base_value( 20.0 ).unwrap().has_entered( low_hp ) );
+	/// assert!( hp.set_base_value( 28.0 ).unwrap().is_empty() );
+	/// assert!( hp.set_base_value( 30.0 ).unwrap().has_left( low_hp ) );
+	/// assert_eq!( hp.is_threshold_active( low_hp ), Some( false ) );
+	/// ```
+	#[inline]
+	pub fn add_threshold ( &mut self, spec: ThresholdSpec ) -> ThresholdId {
+		self.flush();
+		
+		let ctx = self.stage_values();
+		let id = ThresholdId::new( self.next_threshold_id );
+		self.next_threshold_id += 1;
+		
+		self.thresholds.get_or_insert_with( Box::default ).add( id, spec, self.current_value, &ctx );
+		
+		id
+	}
+	
+	#[inline]
+	pub fn remove_threshold ( &mut self, id: ThresholdId ) -> Option< ThresholdSpec > {
+		let thresholds = self.thresholds.as_mut()?;
+		let out = thresholds.remove( id );
+		
+		if thresholds.is_empty() {
+			self.thresholds = None;
+		}
+		
+		out
+	}
+	
+	/// returns `None` when threshold `id` does not exist
+	#[inline]
+	pub fn is_threshold_active ( &self, id: ThresholdId ) -> Option< bool > {
+		self.thresholds.as_ref()?.is_active( id )
+	}
+}// thresholds
+
+// modifiers
+/// Methods for querying stored modifiers
+impl CharStat {
+	/// Iterates over modifiers of every stage: of base, upgrade, base plus upgrade, multiplier, pipeline, more.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// 
+	/// let bless = ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+	/// let curse = ModCommon::new( 2.0, ModCalcMode::Sub, ModCalcStage::Base ).unwrap();
+	/// cs.append_modifier( Modifier::new_expiring( bless, 30 ) ).unwrap();
+	/// cs.append_modifier( Modifier::new_persistent( curse ) ).unwrap();
+	/// cs.set_ts( 10 ).unwrap();
+	/// 
+	/// // buff bar
+	/// let buffs: Vec< _ > = cs.modifiers().filter( |tmp| tmp.is_buff() ).map( |tmp| ( tmp.value(), tmp.remaining() ) ).collect();
+	/// assert_eq!( buffs, vec![ ( 0.5, Some( 20 ) ) ] );
+	/// assert_eq!( cs.modifiers().filter( |tmp| tmp.is_debuff() ).count(), 1 );
+	/// assert_eq!( cs.modifier_count(), 2 );
+	/// ```
+	#[inline]
+	pub fn modifiers ( &self ) -> impl Iterator< Item = ModifierRef< '_ > > {
+		let ts = self.time_stamp;
+		let mods = [ &self.mod_of_base, &self.mod_of_upgrade, &self.mod_of_base_plus_upgrade ];
+		
+		mods.into_iter().flatten().flat_map( |tmp| tmp.modifiers() )
+			.chain( self.mod_mult.iter().flat_map( |tmp| tmp.modifiers() ) )
+			.chain( self.pipeline.iter().flat_map( |tmp| tmp.stages() ).filter_map( PipelineStage::mods ).flat_map( ModConf::modifiers ) )
+			.chain( self.mod_more.iter().flat_map( |tmp| tmp.modifiers() ) )
+			.map( move |modifier| ModifierRef::new( modifier, ts ) )
+	}
+	
+	/// iterates over modifiers of `stage`
+	#[inline]
+	pub fn modifiers_in ( &self, stage: ModCalcStage ) -> impl Iterator< Item = ModifierRef< '_ > > {
+		self.modifiers().filter( move |tmp| tmp.calc_stage() == stage )
+	}
+	
+	/// returns the modifier with `id`, `None` when it was not appended or has expired
+	#[inline]
+	pub fn modifier ( &self, id: ModifierId ) -> Option< ModifierRef< '_ > > {
+		self.modifiers().find( |tmp| tmp.id() == Some( id ) )
+	}
+	
+	/// returns the number of stored modifiers
+	#[inline]
+	pub fn modifier_count ( &self ) -> usize {
+		let mods = [ &self.mod_of_base, &self.mod_of_upgrade, &self.mod_of_base_plus_upgrade ];
+		
+		mods.into_iter().flatten().map( |tmp| tmp.len() ).sum::< usize >()
+			+ self.mod_mult.as_ref().map_or( 0, |tmp| tmp.len() )
+			+ self.pipeline.as_ref().map_or( 0, |tmp| tmp.stages().iter().filter_map( PipelineStage::mods ).map( ModConf::len ).sum() )
+			+ self.mod_more.as_ref().map_or( 0, |tmp| tmp.len() )
+	}
+	
+	/// returns the number of stored modifiers of `stage`
+	#[inline]
+	pub fn modifier_count_in ( &self, stage: ModCalcStage ) -> usize {
+		self.modifiers_in( stage ).count()
+	}
+	
+	/// Calls `f` with a copy of the modifier with `id`, which replaces the stored one when `f` succeeds.
+	/// The copy is validated like `ModCommon::new`, a changed stage moves the modifier to the container of that stage.
+	/// The stat is not changed on error.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+	/// 
+	/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// 
+	/// let rage = ModCommon::new( 0.1, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+	/// let id = cs.append_modifier( Modifier::new_expiring( rage, 10 ) ).unwrap().added.unwrap();
+	/// 
+	/// // rage grows and lasts longer
+	/// cs.update_modifier( id, |tmp| {
+	///     tmp.set_value( 0.5 )?;
+	///     tmp.set_expiration_ts( 20 )
+	/// } ).unwrap();
+	/// assert_eq!( cs.value(), 15.0 );
+	/// assert_eq!( cs.modifier( id ).unwrap().remaining(), Some( 20 ) );
+	/// 
+	/// // division by zero is rejected, the modifier is unchanged
+	/// assert!( cs.update_modifier( id, |tmp| tmp.set_calc_mode( ModCalcMode::Div ).and( tmp.set_value( 0.0 ) ) ).is_err() );
+	/// assert_eq!( cs.value(), 15.0 );
+	/// ```
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::Modifier` when there is no modifier with `id` <br>
+	/// `CsMissingComponent::*` when the container of the new stage is missing <br>
+	/// any error returned by `f` or `ModCommon::new` <br>
+	#[inline]
+	pub fn update_modifier ( &mut self, id: ModifierId, f: impl FnOnce( &mut Modifier ) -> Result< (), CharStatError > ) -> Result< StatReport, CharStatError > {
+		let mut modifier = self.modifier( id ).ok_or( CsMissingComponent::Modifier )?.get().clone();
+		let stage = modifier.calc_stage();
+		
+		f( &mut modifier )?;
+		ModCommon::new( modifier.value(), modifier.calc_mode(), modifier.calc_stage() )?;
+		modifier.check_fn()?;
+		
+		let flags = if modifier.calc_stage() == stage {
+			self.swap_modifier( stage, id, Some( modifier ) )
+		} else {
+			// the old one is removed only after the new stage accepted the modifier
+			self.push_modifier( modifier )? | self.swap_modifier( stage, id, None )
+		};
+		
+		Ok( self.mark_dirty( flags, StatChangeCause::ModifierUpdated ) )
+	}
+}// modifiers
+
+// batch
+impl CharStat {
+	/// Exchanges base, upgrade and the stage values with `base`, `upgrade` and `stages`,
+	/// `StatBatch` keeps them in columns while the stat is not recalculated.
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn swap_parts ( &mut self, base: &mut BaseConf, upgrade: &mut Option< UpgradeConf >, stages: &mut StageRow ) {
+		std::mem::swap( &mut *self.base, base );
+		
+		let tmp = self.upgrade.take().map( |tmp| *tmp );
+		self.upgrade = upgrade.take().map( Box::new );
+		*upgrade = tmp;
+		
+		let row = StageRow {
+			value: self.current_value,
+			base: self.val_base,
+			base_mod: self.val_base_mod,
+			upgrade: self.val_upgrade,
+			upgrade_mod: self.val_upgrade_mod,
+			base_plus_upgrade_mod: self.val_base_plus_upgrade_mod,
+			mod_mult: self.val_mod_mult,
+		};
+		
+		self.current_value = stages.value;
+		self.val_base = stages.base;
+		self.val_base_mod = stages.base_mod;
+		self.val_upgrade = stages.upgrade;
+		self.val_upgrade_mod = stages.upgrade_mod;
+		self.val_base_plus_upgrade_mod = stages.base_plus_upgrade_mod;
+		self.val_mod_mult = stages.mod_mult;
+		*stages = row;
+	}
+}// batch
+
 // priv
 impl CharStat {
+	const DIRTY_BASE: u8 = 1;
+	const DIRTY_UPGRADE: u8 = 1 << 1;
+	const DIRTY_MOD_OF_BASE: u8 = 1 << 2;
+	const DIRTY_MOD_OF_UPGRADE: u8 = 1 << 3;
+	const DIRTY_MOD_OF_BASE_PLUS_UPGRADE: u8 = 1 << 4;
+	const DIRTY_MOD_MULT: u8 = 1 << 5;
+	/// pipeline, more modifiers and every total setting
+	const DIRTY_TOTAL: u8 = 1 << 6;
+	const DIRTY_ALL: u8 = u8::MAX;
+	
+	/// marks stages dirty, with a listener or thresholds the value is recalculated immediately to report the change
 	#[inline]
 	#[doc( hidden )]
-	fn update_current_value ( &mut self ) {
-		self.current_value = self.val_base + self.val_base_mod + self.val_upgrade + self.val_upgrade_mod + self.val_base_plus_upgrade_mod;
+	fn mark_dirty ( &mut self, flags: u8, cause: StatChangeCause ) -> StatReport {
+		self.dirty |= flags;
+		self.cache.take();
+		
+		if self.listener.is_none() && self.thresholds.is_none() {
+			return StatReport::default()
+		}
+		
+		self.notify( cause )
 	}
 	
+	/// stat with a listener or thresholds is always flushed, so `current_value` holds the old value
 	#[inline]
 	#[doc( hidden )]
-	fn append_base_mod( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
-		if let Some( mod_of_base ) = &mut self.mod_of_base {
-			mod_of_base.append_mod_unchecked( self.base.value(), modifier );
-			self.update_base_mod();
-			
-			return Ok(())
+	fn notify ( &mut self, cause: StatChangeCause ) -> StatReport {
+		let old = self.current_value;
+		self.flush();
+		
+		if let Some( listener ) = &self.listener {
+			if old.total_cmp( &self.current_value ) != std::cmp::Ordering::Equal {
+				listener.get().on_change( &StatChange { old, new: self.current_value, cause } );
+			}
 		}
 		
-		Err( CsMissingComponent::ModOfBase.into() )
+		let mut out = StatReport::default();
+		let ctx = self.stage_values();
+		
+		if let Some( thresholds ) = &mut self.thresholds {
+			thresholds.evaluate( self.current_value, &ctx, &mut out.crossed );
+		}
+		
+		out
 	}
 	
+	/// returns the stored values when clean, otherwise the cached calculated ones
 	#[inline]
 	#[doc( hidden )]
-	fn append_upgrade_mod( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
-		if let ( Some( mod_of_upgrade ), Some( upgrade ) ) = ( &mut self.mod_of_upgrade, &mut self.upgrade ) {
-			mod_of_upgrade.append_mod_unchecked( upgrade.value(), modifier );
-			self.update_upgrade_mod();
-			
-			return Ok(())
+	fn flushed ( &self ) -> Cow< '_, Calculated > {
+		if self.dirty == 0 {
+			return Cow::Owned( self.stored() )
 		}
 		
-		Err( CsMissingComponent::ModOfUpgrade.into() )
+		Cow::Borrowed( self.cache.get_or_init( || Box::new( self.calculate() ) ) )
 	}
 	
+	/// values of the last `flush`, results of the containers are left in them
 	#[inline]
 	#[doc( hidden )]
-	fn append_base_plus_upgrade_mod( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
-		if let Some( tmp ) = &mut self.mod_of_base_plus_upgrade {
-			let val = self.val_base + self.val_upgrade;
-			tmp.append_mod_unchecked( val, modifier );
-			self.update_base_plus_upgrade_mod();
-			
-			return Ok(())
+	fn stored ( &self ) -> Calculated {
+		Calculated {
+			current_value: self.current_value,
+			val_base: self.val_base,
+			val_base_mod: self.val_base_mod,
+			val_upgrade: self.val_upgrade,
+			val_upgrade_mod: self.val_upgrade_mod,
+			val_base_plus_upgrade_mod: self.val_base_plus_upgrade_mod,
+			val_mod_mult: self.val_mod_mult,
+			overflow: self.overflow.clone(),
+			stage_overflow: self.stage_overflow,
+			..Calculated::default()
+		}
+	}
+	
+	/// like `flushed`, with the results of every container filled in
+	#[inline]
+	#[doc( hidden )]
+	fn flushed_full ( &self ) -> Calculated {
+		let mut out = self.flushed().into_owned();
+		out.mod_of_base = out.mod_of_base.or_else( || self.mod_of_base.as_ref().map( |tmp| tmp.stored() ) );
+		out.mod_of_upgrade = out.mod_of_upgrade.or_else( || self.mod_of_upgrade.as_ref().map( |tmp| tmp.stored() ) );
+		out.mod_of_base_plus_upgrade = out.mod_of_base_plus_upgrade.or_else( || self.mod_of_base_plus_upgrade.as_ref().map( |tmp| tmp.stored() ) );
+		out.pipeline = out.pipeline.or_else( || self.pipeline.as_ref().map( |tmp| tmp.stored() ) );
+		
+		out
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[inline]
+	#[doc( hidden )]
+	fn dirty_all () -> u8 {
+		CharStat::DIRTY_ALL
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn stage_sum ( &self, calc: &Calculated ) -> f64 {
+		if let Some( pipeline ) = &self.pipeline {
+			return calc.pipeline.as_ref().map_or( pipeline.value(), |tmp| tmp.value )
 		}
 		
-		Err( CsMissingComponent::ModOfBasePlusUpgrade.into() )
+		calc.val_base + calc.val_base_mod + calc.val_upgrade + calc.val_upgrade_mod + calc.val_base_plus_upgrade_mod
 	}
 	
 	#[inline]
 	#[doc( hidden )]
-	fn append_modmult( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
-		if let Some( mod_mult ) = &mut self.mod_mult {
-			mod_mult.append_mod_unchecked( modifier );
-			
-			self.val_mod_mult = mod_mult.value();
-			
-			if self.mod_of_base.is_some() {
-				self.update_base_mod();
+	fn mod_soft_cap_loss ( &self, calc: &Calculated ) -> f64 {
+		let mut loss = 0.0;
+		let mods = [
+			( &self.mod_of_base, calc.mod_of_base ),
+			( &self.mod_of_upgrade, calc.mod_of_upgrade ),
+			( &self.mod_of_base_plus_upgrade, calc.mod_of_base_plus_upgrade ),
+		];
+		
+		for ( mod_conf, calc ) in mods {
+			if let Some( mod_conf ) = mod_conf {
+				loss += calc.unwrap_or_else( || mod_conf.stored() ).soft_cap_loss;
 			}
+		}// for
+		
+		if let Some( pipeline ) = &self.pipeline {
+			let stored;
+			let pipeline = if let Some( tmp ) = &calc.pipeline {
+				tmp
+			} else {
+				stored = pipeline.stored();
+				&stored
+			};
 			
-			if self.mod_of_upgrade.is_some() {
-				self.update_upgrade_mod();
+			loss += pipeline.stages.iter().filter_map( |tmp| tmp.1 ).map( |tmp| tmp.soft_cap_loss ).sum::< f64 >();
+		}
+		
+		loss * calc.val_mod_mult
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn stage_values ( &self ) -> StageValues {
+		self.stage_values_of( &self.stored() )
+	}
+	
+	/// stage values of `calc`, values of pipeline stages are the stored ones
+	#[inline]
+	#[doc( hidden )]
+	fn stage_values_of ( &self, calc: &Calculated ) -> StageValues {
+		StageValues {
+			base: calc.val_base,
+			upgrade: calc.val_upgrade,
+			mod_of_base: calc.val_base_mod,
+			mod_of_upgrade: calc.val_upgrade_mod,
+			mod_of_base_plus_upgrade: calc.val_base_plus_upgrade_mod,
+			stages: self.pipeline.as_ref().map_or_else( Vec::new, |tmp| tmp.stage_values() ),
+		}
+	}
+	
+	/// recalculates the dirty stages without changing anything, the result is applied by `store`
+	#[inline]
+	#[doc( hidden )]
+	fn calculate ( &self ) -> Calculated {
+		let dirty = self.dirty;
+		let mut out = self.stored();
+		
+		// recalculated stages record their overflow again, removed ones drop it
+		out.stage_overflow &= !dirty;
+		
+		if dirty & CharStat::DIRTY_MOD_MULT != 0 {
+			let value = self.mod_mult.as_ref().map_or( 1.0, |tmp| tmp.value() );
+			out.val_mod_mult = out.clamp_stage( CharStat::DIRTY_MOD_MULT, value );
+		}
+		
+		if dirty & CharStat::DIRTY_BASE != 0 {
+			out.val_base = self.base.value();
+		}
+		
+		if dirty & ( CharStat::DIRTY_BASE | CharStat::DIRTY_MOD_OF_BASE | CharStat::DIRTY_MOD_MULT ) != 0 {
+			if let Some( mod_mgr ) = &self.mod_of_base {
+				let ( calc, value ) = self.calc_mod( mod_mgr, out.val_base, &out );
+				out.val_base_mod = out.clamp_stage( CharStat::DIRTY_MOD_OF_BASE, value );
+				out.mod_of_base = Some( calc );
 			}
-			
-			if self.mod_of_base_plus_upgrade.is_some() {
-				self.update_base_plus_upgrade_mod();
+		}
+		
+		if dirty & CharStat::DIRTY_UPGRADE != 0 {
+			if let Some( upgrade ) = &self.upgrade {
+				let value = upgrade.effective_value( &self.stage_values_of( &out ) );
+				out.val_upgrade = out.clamp_stage( CharStat::DIRTY_UPGRADE, value );
 			}
-			
-			return Ok(())
 		}
 		
-		Err( CsMissingComponent::ModMult.into() )
+		if dirty & ( CharStat::DIRTY_UPGRADE | CharStat::DIRTY_MOD_OF_UPGRADE | CharStat::DIRTY_MOD_MULT ) != 0 {
+			if let Some( mod_mgr ) = &self.mod_of_upgrade {
+				let ( calc, value ) = self.calc_mod( mod_mgr, out.val_upgrade, &out );
+				out.val_upgrade_mod = out.clamp_stage( CharStat::DIRTY_MOD_OF_UPGRADE, value );
+				out.mod_of_upgrade = Some( calc );
+			}
+		}
+		
+		if dirty & ( CharStat::DIRTY_BASE | CharStat::DIRTY_UPGRADE | CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE | CharStat::DIRTY_MOD_MULT ) != 0 {
+			if let Some( mod_mgr ) = &self.mod_of_base_plus_upgrade {
+				let ( calc, value ) = self.calc_mod( mod_mgr, out.val_base + out.val_upgrade, &out );
+				out.val_base_plus_upgrade_mod = out.clamp_stage( CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE, value );
+				out.mod_of_base_plus_upgrade = Some( calc );
+			}
+		}
+		
+		self.calc_current_value( &mut out );
+		
+		out
 	}
 	
+	/// calculates `mod_mgr` for `input`, returns its values and the value of its stage
 	#[inline]
 	#[doc( hidden )]
-	fn remove_expired_modifiers( &mut self ) {
-		if let Some( tmp ) = &mut self.mod_mult {
-			tmp.remove_expired( self.time_stamp );
-			self.val_mod_mult = tmp.value();
+	fn calc_mod ( &self, mod_mgr: &ModConf, input: f64, calc: &Calculated ) -> ( ModConfCalc, f64 ) {
+		let mut out = mod_mgr.calc( input );
+		out.value = mod_mgr.dyn_bounded( out.value, &self.stage_values_of( calc ) );
+		
+		( out, out.value * calc.val_mod_mult )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn calc_current_value ( &self, calc: &mut Calculated ) {
+		let mut overflow = calc.stage_overflow != 0;
+		
+		if let Some( pipeline ) = &self.pipeline {
+			let mut ctx = StageValues {
+				base: calc.val_base,
+				upgrade: calc.val_upgrade,
+				mod_of_base: calc.val_base_mod,
+				mod_of_upgrade: calc.val_upgrade_mod,
+				mod_of_base_plus_upgrade: calc.val_base_plus_upgrade_mod,
+				stages: Vec::with_capacity( pipeline.len() ),
+			};
+			
+			let ( out, pipeline_overflow ) = pipeline.calc( calc.val_base, calc.val_upgrade, calc.val_mod_mult, &mut ctx );
+			overflow |= pipeline_overflow;
+			
+			calc.val_base_mod = ctx.mod_of_base;
+			calc.val_upgrade_mod = ctx.mod_of_upgrade;
+			calc.val_base_plus_upgrade_mod = ctx.mod_of_base_plus_upgrade;
+			calc.pipeline = Some( out );
 		}
 		
-		if let Some( tmp ) = &mut self.mod_of_base {
-			tmp.remove_expired( self.time_stamp );
-			self.update_base_mod();
+		let mut total = self.stage_sum( calc );
+		
+		if let Some( mod_more ) = &self.mod_more {
+			total = mod_more.apply( total );
 		}
 		
-		if let Some( tmp ) = &mut self.mod_of_upgrade {
-			tmp.remove_expired( self.time_stamp );
-			self.update_upgrade_mod();
+		if let Some( soft_cap ) = &self.total_soft_cap {
+			total = soft_cap.apply( total );
 		}
 		
-		if let Some( tmp ) = &mut self.mod_of_base_plus_upgrade {
-			tmp.remove_expired( self.time_stamp );
-			self.update_base_plus_upgrade_mod();
+		if let Some( bounds ) = &self.total_bounds {
+			total = bounds.apply( total, calc.val_base );
+		}
+		
+		// after bounds, so the value stays on the rounding grid
+		if let Some( rounding ) = &self.total_rounding {
+			total = rounding.do_rounding( total );
+		}
+		
+		if total.is_nan() {
+			calc.overflow = Some( CsInvalidValue::Nan( "value".to_string() ) );
+			return
 		}
 		
-		self.update_current_value();
+		calc.overflow = ( overflow || total.is_infinite() ).then( || CsInvalidValue::Infinite( "value".to_string() ) );
+		calc.current_value = total.clamp( f64::MIN, f64::MAX );
 	}
 	
+	/// applies values returned by `calculate`
 	#[inline]
 	#[doc( hidden )]
-	fn update_base( &mut self ) {
-		self.val_base = self.base.value();
+	fn store ( &mut self, calc: Calculated ) {
+		self.current_value = calc.current_value;
+		self.val_base = calc.val_base;
+		self.val_base_mod = calc.val_base_mod;
+		self.val_upgrade = calc.val_upgrade;
+		self.val_upgrade_mod = calc.val_upgrade_mod;
+		self.val_base_plus_upgrade_mod = calc.val_base_plus_upgrade_mod;
+		self.val_mod_mult = calc.val_mod_mult;
+		self.overflow = calc.overflow;
+		self.stage_overflow = calc.stage_overflow;
+		
+		let mods = [
+			( &mut self.mod_of_base, calc.mod_of_base ),
+			( &mut self.mod_of_upgrade, calc.mod_of_upgrade ),
+			( &mut self.mod_of_base_plus_upgrade, calc.mod_of_base_plus_upgrade ),
+		];
+		
+		for ( mod_conf, calc ) in mods {
+			if let ( Some( mod_conf ), Some( calc ) ) = ( mod_conf, calc ) {
+				mod_conf.store( calc );
+			}
+		}// for
 		
-		if self.mod_of_base.is_some() {
-			self.update_base_mod();
+		if let ( Some( pipeline ), Some( calc ) ) = ( &mut self.pipeline, &calc.pipeline ) {
+			pipeline.store( calc );
 		}
 	}
 	
+	/// dispatches `modifier` to the container of its stage, returns the dirty flags
 	#[inline]
 	#[doc( hidden )]
-	fn update_base_mod( &mut self ) {
-		if let Some( mod_mgr ) = &mut self.mod_of_base {
-			mod_mgr.update( self.val_base );
-			self.val_base_mod = mod_mgr.value() * self.val_mod_mult;
+	fn push_modifier( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		modifier.check_fn()?;
+		
+		match &modifier.calc_stage() {
+			ModCalcStage::Base | ModCalcStage::Upgrade | ModCalcStage::BasePlusUpgrade | ModCalcStage::Custom(_) if self.pipeline.is_some() => self.append_pipeline_mod( modifier ),
+			ModCalcStage::Custom(_) => Err( CsMissingComponent::Pipeline.into() ),
+			ModCalcStage::Base => self.append_base_mod( modifier ),
+			ModCalcStage::Upgrade => self.append_upgrade_mod( modifier ),
+			ModCalcStage::BasePlusUpgrade => self.append_base_plus_upgrade_mod( modifier ),
+			ModCalcStage::ModMult => self.append_modmult( modifier ),
+			ModCalcStage::More => self.append_more_mod( modifier ),
 		}
 	}
 	
+	/// Replaces or removes ( `None` ) the modifier with `id` in the container of `stage`, same dispatch as `push_modifier`. <br>
+	/// Returns the dirty flags, 0 when there is no such modifier.
 	#[inline]
 	#[doc( hidden )]
-	fn update_upgrade( &mut self ) {
-		if let Some( upgrade ) = &self.upgrade {
-			self.val_upgrade = upgrade.value();
-			
-			if self.mod_of_upgrade.is_some() {
-				self.update_upgrade_mod();
-			}
-			
-			if self.mod_of_base_plus_upgrade.is_some() {
-				self.update_base_plus_upgrade_mod();
-			}
+	fn swap_modifier( &mut self, stage: ModCalcStage, id: ModifierId, new_val: Option< Modifier > ) -> u8 {
+		let ( flags, is_found ) = match stage {
+			ModCalcStage::Base | ModCalcStage::Upgrade | ModCalcStage::BasePlusUpgrade | ModCalcStage::Custom(_) if self.pipeline.is_some() => {
+				( CharStat::DIRTY_TOTAL, self.pipeline.as_mut().is_some_and( |tmp| tmp.swap_mod( stage, id, new_val ) ) )
+			},
+			ModCalcStage::Custom(_) => ( 0, false ),
+			ModCalcStage::Base => ( CharStat::DIRTY_MOD_OF_BASE, self.mod_of_base.as_mut().is_some_and( |tmp| tmp.swap_mod( id, new_val ) ) ),
+			ModCalcStage::Upgrade => ( CharStat::DIRTY_MOD_OF_UPGRADE, self.mod_of_upgrade.as_mut().is_some_and( |tmp| tmp.swap_mod( id, new_val ) ) ),
+			ModCalcStage::BasePlusUpgrade => ( CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE, self.mod_of_base_plus_upgrade.as_mut().is_some_and( |tmp| tmp.swap_mod( id, new_val ) ) ),
+			ModCalcStage::ModMult => ( CharStat::DIRTY_MOD_MULT, self.mod_mult.as_mut().is_some_and( |tmp| tmp.swap_mod( id, new_val ) ) ),
+			ModCalcStage::More => ( CharStat::DIRTY_TOTAL, self.mod_more.as_mut().is_some_and( |tmp| tmp.swap_mod( id, new_val ) ) ),
+		};
+		
+		if is_found { flags } else { 0 }
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn append_base_mod( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let Some( mod_of_base ) = &mut self.mod_of_base {
+			mod_of_base.push_mod( modifier );
+			return Ok( CharStat::DIRTY_MOD_OF_BASE )
+		}
+		
+		Err( CsMissingComponent::ModOfBase.into() )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn append_upgrade_mod( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let ( Some( mod_of_upgrade ), Some(_) ) = ( &mut self.mod_of_upgrade, &self.upgrade ) {
+			mod_of_upgrade.push_mod( modifier );
+			return Ok( CharStat::DIRTY_MOD_OF_UPGRADE )
+		}
+		
+		Err( CsMissingComponent::ModOfUpgrade.into() )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn append_base_plus_upgrade_mod( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let Some( tmp ) = &mut self.mod_of_base_plus_upgrade {
+			tmp.push_mod( modifier );
+			return Ok( CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE )
+		}
+		
+		Err( CsMissingComponent::ModOfBasePlusUpgrade.into() )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn append_modmult( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let Some( mod_mult ) = &mut self.mod_mult {
+			mod_mult.append_mod_unchecked( modifier );
+			return Ok( CharStat::DIRTY_MOD_MULT )
+		}
+		
+		Err( CsMissingComponent::ModMult.into() )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn append_pipeline_mod( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let Some( pipeline ) = &mut self.pipeline {
+			pipeline.append_mod( modifier )?;
+			return Ok( CharStat::DIRTY_TOTAL )
 		}
+		
+		Err( CsMissingComponent::Pipeline.into() )
 	}
 	
 	#[inline]
 	#[doc( hidden )]
-	fn update_upgrade_mod( &mut self ) {
-		if let Some( mod_mgr ) = &mut self.mod_of_upgrade {
-			mod_mgr.update( self.val_upgrade );
-			self.val_upgrade_mod = mod_mgr.value() * self.val_mod_mult;
+	fn append_more_mod( &mut self, modifier: Modifier ) -> Result< u8, CharStatError > {
+		if let Some( mod_more ) = &mut self.mod_more {
+			mod_more.append_mod( modifier )?;
+			return Ok( CharStat::DIRTY_TOTAL )
 		}
+		
+		Err( CsMissingComponent::ModMore.into() )
 	}
 	
 	#[inline]
 	#[doc( hidden )]
-	fn update_base_plus_upgrade_mod( &mut self ) {
-		if let Some( mod_mgr ) = &mut self.mod_of_base_plus_upgrade {
-			mod_mgr.update( self.val_base + self.val_upgrade );
-			self.val_base_plus_upgrade_mod = mod_mgr.value() * self.val_mod_mult;
+	fn remove_expired_modifiers( &mut self ) -> StatReport {
+		let ts = self.time_stamp;
+		
+		match self.next_expiration() {
+			Some( next ) if next <= ts => {},
+			_ => return StatReport::default(),
 		}
+		
+		// pending changes are not part of the delta
+		self.flush();
+		let old = self.current_value;
+		
+		let removed = [
+			( CharStat::DIRTY_MOD_MULT, self.mod_mult.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+			( CharStat::DIRTY_MOD_OF_BASE, self.mod_of_base.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+			( CharStat::DIRTY_MOD_OF_UPGRADE, self.mod_of_upgrade.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+			( CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE, self.mod_of_base_plus_upgrade.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+			( CharStat::DIRTY_TOTAL, self.pipeline.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+			( CharStat::DIRTY_TOTAL, self.mod_more.as_mut().map( |tmp| tmp.remove_expired( ts ) ) ),
+		];
+		
+		let mut flags = 0;
+		let mut expired = Vec::new();
+		
+		for ( flag, mods ) in removed {
+			if let Some( mods ) = mods.filter( |tmp| !tmp.is_empty() ) {
+				flags |= flag;
+				expired.extend( mods );
+			}
+		}// for
+		
+		// `mark_dirty` may already have recalculated, then `flush` is a no-op
+		let mut out = self.mark_dirty( flags, StatChangeCause::ModifierExpired );
+		self.flush();
+		
+		out.expired = expired;
+		out.delta = self.current_value - old;
+		
+		out
 	}
 }// priv
 
@@ -724,27 +1829,23 @@ impl CharStat {
 impl CharStat {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `self.bounds` <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_mut` is false <br>
 	#[inline]
-	pub fn set_base_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn set_base_value ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_value( value )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::Base ) )
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsLogicIssue::FieldIsConst` when `self.is_mut` is false <br>
 	#[inline]
-	pub fn set_base_value_clamping ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn set_base_value_clamping ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_value_clamping( value )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::Base ) )
 	}
 	
 	#[inline]
@@ -793,6 +1894,13 @@ impl CharStat {
 	pub fn base_bounds_max ( &self ) -> f64 {
 		self.base.bounds_max()
 	}
+	
+	/// rounding of the base value with multiplier applied
+	#[inline]
+	pub fn set_base_rounding ( &mut self, new_val: impl Into< Shared< RoundingHelper > > ) -> StatReport {
+		self.base.set_rounding( new_val );
+		self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::Config )
+	}
 }// base
 
 // base mult
@@ -800,52 +1908,48 @@ impl CharStat {
 impl CharStat {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `new_val` is not within `self.bounds` <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
-	pub fn set_mult_base ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+	pub fn set_mult_base ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_mult_base( new_val )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::BaseMult ) )
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `new_val` is not within `self.bounds` <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
-	pub fn set_mult_exponent ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+	pub fn set_mult_exponent ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_mult_exponent( new_val )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::BaseMult ) )
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
-	pub fn set_mult_base_clamping ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+	pub fn set_mult_base_clamping ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_mult_base_clamping( new_val )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::BaseMult ) )
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite, or the multiplier overflows <br>
+	/// `CsInvalidValue::CannotBeZero` when the multiplier underflows to 0.0 <br>
 	/// `CsMissingComponent::BaseMult` when `BaseMultConf` is missing <br>
 	#[inline]
-	pub fn set_mult_exponent_clamping ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+	pub fn set_mult_exponent_clamping ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
 		self.base.set_mult_exponent_clamping( new_val )?;
-		self.update_base();
-		self.update_current_value();
-		
-		Ok(())
+		Ok( self.mark_dirty( CharStat::DIRTY_BASE, StatChangeCause::BaseMult ) )
 	}
 }// base mult
 
@@ -854,16 +1958,14 @@ impl CharStat {
 impl CharStat {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `self.bounds` <br>
 	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
 	#[inline]
-	pub fn set_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn set_upgrade_value ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		if let Some( upgrade ) = &mut self.upgrade {
 			upgrade.set_value( value )?;
-			self.update_upgrade();
-			self.update_current_value();
-			
-			return Ok(())
+			return Ok( self.mark_dirty( CharStat::DIRTY_UPGRADE, StatChangeCause::Upgrade ) )
 		}
 		
 		Err( CsMissingComponent::Upgrade.into() )
@@ -871,15 +1973,13 @@ impl CharStat {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
 	#[inline]
-	pub fn set_upgrade_value_clamping ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn set_upgrade_value_clamping ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		if let Some( upgrade ) = &mut self.upgrade {
 			upgrade.set_value_clamping( value )?;
-			self.update_upgrade();
-			self.update_current_value();
-			
-			return Ok(())
+			return Ok( self.mark_dirty( CharStat::DIRTY_UPGRADE, StatChangeCause::Upgrade ) )
 		}
 		
 		Err( CsMissingComponent::Upgrade.into() )
@@ -891,13 +1991,10 @@ impl CharStat {
 	/// `CsInvalidValue::AboveMaximum` when new value is not within `self.bounds` <br>
 	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
 	#[inline]
-	pub fn inc_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn inc_upgrade_value ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		if let Some( upgrade ) = &mut self.upgrade {
 			upgrade.set_value( upgrade.value() + value )?;
-			self.update_upgrade();
-			self.update_current_value();
-			
-			return Ok(())
+			return Ok( self.mark_dirty( CharStat::DIRTY_UPGRADE, StatChangeCause::Upgrade ) )
 		}
 		
 		Err( CsMissingComponent::Upgrade.into() )
@@ -909,13 +2006,10 @@ impl CharStat {
 	/// `CsInvalidValue::BelowMinimum` when new value is not within `self.bounds` <br>
 	/// `CsMissingComponent::Upgrade` when `UpgradeConf` is missing <br>
 	#[inline]
-	pub fn dec_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+	pub fn dec_upgrade_value ( &mut self, value: f64 ) -> Result< StatReport, CharStatError > {
 		if let Some( upgrade ) = &mut self.upgrade {
 			upgrade.set_value( upgrade.value() - value )?;
-			self.update_upgrade();
-			self.update_current_value();
-			
-			return Ok(())
+			return Ok( self.mark_dirty( CharStat::DIRTY_UPGRADE, StatChangeCause::Upgrade ) )
 		}
 		
 		Err( CsMissingComponent::Upgrade.into() )
@@ -998,100 +2092,307 @@ impl CharStat {
 	}
 }// upgrade
 
-// struct - CharStat
-//------------------------------------------------------------------------------
-// enum - RoundingFnEnum
-
-#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, Copy, PartialEq )]
-pub enum RoundingFnEnum {
-	Round,
-	RoundTiesEven,
-	Floor,
-	Ceil,
-	Trunk,
-	None,
-}
-
-impl RoundingFnEnum {
-	fn do_rounding( self, val: f64 ) -> f64 {
-		match self {
-			RoundingFnEnum::Round => val.round(),
-			RoundingFnEnum::RoundTiesEven => val.round_ties_even(),
-			RoundingFnEnum::Floor => val.floor(),
-			RoundingFnEnum::Ceil => val.ceil(),
-			RoundingFnEnum::Trunk => val.trunc(),
-			RoundingFnEnum::None => val,
+// modifier stages
+/// Methods for manipulation of ModConf and ModMultConf
+impl CharStat {
+	/// Returns `ModConf` of `stage`, with a pipeline the one of its stage. <br>
+	/// Its value is up to date, see `flush`.
+	/// 
+	/// # Examples
+	/// ```rust
+	/// use char_stat::{ CharStat, BaseConf, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper, RoundingFnEnum };
+	/// 
+	/// let bounds = Bounds::new_mut( 0.0, 100.0 ).unwrap();
+	/// let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+	/// let mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+	/// let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+	/// 
+	/// let common = ModCommon::new( 0.75, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+	/// cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+	/// assert_eq!( cs.value(), 17.5 );
+	/// 
+	/// // modifiers of base are limited to +50% of base, rounded down
+	/// cs.set_mod_percent( ModCalcStage::Base, false, true ).unwrap();
+	/// cs.set_mod_bounds_max( ModCalcStage::Base, 0.5 ).unwrap();
+	/// assert_eq!( cs.value(), 15.0 );
+	/// 
+	/// cs.set_mod_bounds_max( ModCalcStage::Base, 1.0 ).unwrap();
+	/// cs.set_mod_rounding( ModCalcStage::Base, RoundingHelper::new( RoundingFnEnum::Floor, None ) ).unwrap();
+	/// assert_eq!( cs.value(), 17.0 );
+	/// assert!( cs.mod_conf( ModCalcStage::Base ).unwrap().is_max_percent() );
+	/// assert!( cs.set_mod_rounding( ModCalcStage::Upgrade, RoundingHelper::new_none() ).is_err() );
+	/// ```
+	#[inline]
+	pub fn mod_conf ( &self, stage: ModCalcStage ) -> Option< &ModConf > {
+		if let ( Some( pipeline ), ModCalcStage::Base | ModCalcStage::Upgrade | ModCalcStage::BasePlusUpgrade | ModCalcStage::Custom(_) ) = ( &self.pipeline, stage ) {
+			return pipeline.stages().iter().filter_map( PipelineStage::mods ).find( |tmp| tmp.stage() == stage )
+		}
+		
+		match stage {
+			ModCalcStage::Base => self.mod_of_base.as_deref(),
+			ModCalcStage::Upgrade => self.mod_of_upgrade.as_deref(),
+			ModCalcStage::BasePlusUpgrade => self.mod_of_base_plus_upgrade.as_deref(),
+			_ => None,
 		}
 	}
-}
-
-// enum - RoundingFnEnum
-//------------------------------------------------------------------------------
-// struct - RoundingHelper
-
-#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, PartialEq )]
-pub struct RoundingHelper {
-	function: RoundingFnEnum,
-	precision: Option< f64 >,
-}
-
-impl RoundingHelper {
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
 	#[inline]
-	pub fn new ( function: RoundingFnEnum, mut precision: Option< f64 >, ) -> Self {
-		if let Some( val ) = &precision {
-			if val.is_nan() {
-				precision = None;
-			}
-		}
+	pub fn set_mod_bounds_min ( &mut self, stage: ModCalcStage, new_val: f64 ) -> Result< StatReport, CharStatError > {
+		let ( mod_conf, flags ) = self.mod_conf_mut( stage )?;
+		mod_conf.set_bounds_min( new_val )?;
+		
+		Ok( self.mark_dirty( flags, StatChangeCause::Config ) )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
+	#[inline]
+	pub fn set_mod_bounds_max ( &mut self, stage: ModCalcStage, new_val: f64 ) -> Result< StatReport, CharStatError > {
+		let ( mod_conf, flags ) = self.mod_conf_mut( stage )?;
+		mod_conf.set_bounds_max( new_val )?;
+		
+		Ok( self.mark_dirty( flags, StatChangeCause::Config ) )
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
+	#[inline]
+	pub fn set_mod_bounds_min_const ( &mut self, stage: ModCalcStage ) -> Result< StatReport, CharStatError > {
+		self.mod_conf_mut( stage )?.0.set_bounds_min_const();
+		
+		Ok( StatReport::default() )
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
+	#[inline]
+	pub fn set_mod_bounds_max_const ( &mut self, stage: ModCalcStage ) -> Result< StatReport, CharStatError > {
+		self.mod_conf_mut( stage )?.0.set_bounds_max_const();
+		
+		Ok( StatReport::default() )
+	}
+	
+	#[inline]
+	pub fn mod_bounds_min ( &self, stage: ModCalcStage ) -> Option< f64 > {
+		self.mod_conf( stage ).map( ModConf::bounds_min )
+	}
+	
+	#[inline]
+	pub fn mod_bounds_max ( &self, stage: ModCalcStage ) -> Option< f64 > {
+		self.mod_conf( stage ).map( ModConf::bounds_max )
+	}
+	
+	/// Sets whether the bounds of `ModConf` of `stage` are fractions of the stage input.
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
+	#[inline]
+	pub fn set_mod_percent ( &mut self, stage: ModCalcStage, is_min_percent: bool, is_max_percent: bool ) -> Result< StatReport, CharStatError > {
+		let ( mod_conf, flags ) = self.mod_conf_mut( stage )?;
+		mod_conf.set_percent( is_min_percent, is_max_percent );
+		
+		Ok( self.mark_dirty( flags, StatChangeCause::Config ) )
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::ModOfBase`, `CsMissingComponent::ModOfUpgrade`, `CsMissingComponent::ModOfBasePlusUpgrade`, `CsMissingComponent::Pipeline` or `CsMissingComponent::PipelineStage` when `ModConf` of `stage` is missing <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `stage` is `ModMult` or `More` <br>
+	#[inline]
+	pub fn set_mod_rounding ( &mut self, stage: ModCalcStage, new_val: impl Into< Shared< RoundingHelper > > ) -> Result< StatReport, CharStatError > {
+		let ( mod_conf, flags ) = self.mod_conf_mut( stage )?;
+		mod_conf.set_rounding( new_val );
 		
-		RoundingHelper {
-			function,
-			precision,
+		Ok( self.mark_dirty( flags, StatChangeCause::Config ) )
+	}
+	
+	#[inline]
+	pub fn mod_mult ( &self ) -> Option< &ModMultConf > {
+		self.mod_mult.as_deref()
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
+	/// `CsMissingComponent::ModMult` when `ModMultConf` is missing <br>
+	#[inline]
+	pub fn set_mod_mult_bounds_min ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
+		if let Some( mod_mult ) = &mut self.mod_mult {
+			mod_mult.set_bounds_min( new_val )?;
+			return Ok( self.mark_dirty( CharStat::DIRTY_MOD_MULT, StatChangeCause::Config ) )
 		}
+		
+		Err( CsMissingComponent::ModMult.into() )
 	}
 	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
+	/// `CsMissingComponent::ModMult` when `ModMultConf` is missing <br>
 	#[inline]
-	pub fn new_none() -> Self {
-		RoundingHelper {
-			function: RoundingFnEnum::None,
-			precision: None,
+	pub fn set_mod_mult_bounds_max ( &mut self, new_val: f64 ) -> Result< StatReport, CharStatError > {
+		if let Some( mod_mult ) = &mut self.mod_mult {
+			mod_mult.set_bounds_max( new_val )?;
+			return Ok( self.mark_dirty( CharStat::DIRTY_MOD_MULT, StatChangeCause::Config ) )
 		}
+		
+		Err( CsMissingComponent::ModMult.into() )
 	}
 	
-	pub( crate ) fn do_rounding( &self, mut value: f64 ) -> f64 {
-		if let RoundingFnEnum::None = self.function {
-			return value;
+	/// # Errors
+	/// `CsMissingComponent::ModMult` when `ModMultConf` is missing <br>
+	#[inline]
+	pub fn set_mod_mult_bounds_min_const ( &mut self ) -> Result< StatReport, CharStatError > {
+		if let Some( mod_mult ) = &mut self.mod_mult {
+			mod_mult.set_bounds_min_const();
+			
+			return Ok( StatReport::default() )
 		}
 		
-		if let Some( prec ) = self.precision {
-			value /= prec;
-			value = self.function.do_rounding( value );
+		Err( CsMissingComponent::ModMult.into() )
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::ModMult` when `ModMultConf` is missing <br>
+	#[inline]
+	pub fn set_mod_mult_bounds_max_const ( &mut self ) -> Result< StatReport, CharStatError > {
+		if let Some( mod_mult ) = &mut self.mod_mult {
+			mod_mult.set_bounds_max_const();
 			
-			return value * prec
+			return Ok( StatReport::default() )
 		}
 		
-		self.function.do_rounding( value )
+		Err( CsMissingComponent::ModMult.into() )
 	}
-}
+	
+	#[inline]
+	pub fn mod_mult_bounds_min ( &self ) -> Option< f64 > {
+		self.mod_mult.as_ref().map( |tmp| tmp.bounds_min() )
+	}
+	
+	#[inline]
+	pub fn mod_mult_bounds_max ( &self ) -> Option< f64 > {
+		self.mod_mult.as_ref().map( |tmp| tmp.bounds_max() )
+	}
+	
+	/// Moves `mod_of_*` components together with their modifiers into `Pipeline::classic`, the value does not change.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "pipeline" )` when the stat already uses a pipeline <br>
+	#[inline]
+	pub fn use_classic_pipeline ( &mut self ) -> Result< StatReport, CharStatError > {
+		if self.pipeline.is_some() {
+			return Err( CsInvalidValue::NotAllowed( "pipeline".to_string() ).into() )
+		}
+		
+		let pipeline = Pipeline::classic(
+			self.mod_of_base.as_deref().cloned(),
+			self.mod_of_upgrade.as_deref().cloned(),
+			self.mod_of_base_plus_upgrade.as_deref().cloned(),
+		)?;
+		
+		self.mod_of_base = None;
+		self.mod_of_upgrade = None;
+		self.mod_of_base_plus_upgrade = None;
+		self.pipeline = Some( Box::new( pipeline ) );
+		
+		Ok( self.mark_dirty( CharStat::DIRTY_ALL, StatChangeCause::Config ) )
+	}
+	
+	/// returns `ModConf` of `stage` and the dirty flags of its stage, same dispatch as `mod_conf`
+	#[inline]
+	#[doc( hidden )]
+	fn mod_conf_mut ( &mut self, stage: ModCalcStage ) -> Result< ( &mut ModConf, u8 ), CharStatError > {
+		if let ( Some( pipeline ), ModCalcStage::Base | ModCalcStage::Upgrade | ModCalcStage::BasePlusUpgrade | ModCalcStage::Custom(_) ) = ( &mut self.pipeline, stage ) {
+			return pipeline.mods_mut( stage ).map( |tmp| ( tmp, CharStat::DIRTY_TOTAL ) ).ok_or_else( || CsMissingComponent::PipelineStage.into() )
+		}
+		
+		let ( mod_conf, flags, missing ) = match stage {
+			ModCalcStage::Base => ( &mut self.mod_of_base, CharStat::DIRTY_MOD_OF_BASE, CsMissingComponent::ModOfBase ),
+			ModCalcStage::Upgrade => ( &mut self.mod_of_upgrade, CharStat::DIRTY_MOD_OF_UPGRADE, CsMissingComponent::ModOfUpgrade ),
+			ModCalcStage::BasePlusUpgrade => ( &mut self.mod_of_base_plus_upgrade, CharStat::DIRTY_MOD_OF_BASE_PLUS_UPGRADE, CsMissingComponent::ModOfBasePlusUpgrade ),
+			ModCalcStage::Custom(_) => return Err( CsMissingComponent::Pipeline.into() ),
+			ModCalcStage::ModMult | ModCalcStage::More => return Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() ),
+		};
+		
+		mod_conf.as_deref_mut().map( |tmp| ( tmp, flags ) ).ok_or_else( || missing.into() )
+	}
+}// modifier stages
 
-impl Default for RoundingHelper {
+/// Compares the flushed state, dirty flags and the cached values are ignored.
+impl PartialEq for CharStat {
 	#[inline]
-	fn default() -> Self {
-		RoundingHelper {
-			function: RoundingFnEnum::None,
-			precision: None,
+	fn eq( &self, other: &Self ) -> bool {
+		fn eq_mod_conf ( lhs: Option< &ModConf >, rhs: Option< &ModConf > ) -> bool {
+			match ( lhs, rhs ) {
+				( Some( lhs ), Some( rhs ) ) => lhs.eq_config( rhs ),
+				( None, None ) => true,
+				_ => false,
+			}
 		}
+		
+		let pipeline_eq = match ( &self.pipeline, &other.pipeline ) {
+			( Some( lhs ), Some( rhs ) ) => lhs.eq_config( rhs ),
+			( None, None ) => true,
+			_ => false,
+		};
+		
+		self.flushed_full() == other.flushed_full() &&
+		self.time_stamp == other.time_stamp &&
+		self.base == other.base &&
+		self.upgrade == other.upgrade &&
+		eq_mod_conf( self.mod_of_base.as_deref(), other.mod_of_base.as_deref() ) &&
+		eq_mod_conf( self.mod_of_upgrade.as_deref(), other.mod_of_upgrade.as_deref() ) &&
+		eq_mod_conf( self.mod_of_base_plus_upgrade.as_deref(), other.mod_of_base_plus_upgrade.as_deref() ) &&
+		self.mod_mult == other.mod_mult &&
+		self.mod_more == other.mod_more &&
+		pipeline_eq &&
+		self.total_bounds == other.total_bounds &&
+		self.total_rounding == other.total_rounding &&
+		self.total_soft_cap == other.total_soft_cap &&
+		self.listener == other.listener &&
+		self.thresholds == other.thresholds &&
+		self.next_mod_id == other.next_mod_id &&
+		self.next_threshold_id == other.next_threshold_id
+	}
+}
+
+/// First line holds the value, timestamp and number of modifiers, followed by one line per modifier and `ValueBreakdown`.
+impl Display for CharStat {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		writeln!( f, "CharStat = (value: {}, ts: {}, modifiers: {})", self.value(), self.time_stamp, self.modifier_count() )?;
+		
+		for modifier in self.modifiers() {
+			writeln!( f, "\t{}", modifier.get() )?;
+		}// for
+		
+		self.breakdown().fmt( f )
 	}
 }
 
-// struct - RoundingHelper
+// struct - CharStat
 //------------------------------------------------------------------------------
 // --Tests
 
 #[cfg( test )]
 mod char_stat_tests {
+	#![allow( clippy::float_cmp )]
+	
 	use super::*;
 	
 	#[test]
@@ -1130,13 +2431,13 @@ mod char_stat_tests {
 		let mut cs = CharStat::new( base, Some( upgrade ), None, Some( up_mod ), None, None );
 		assert_eq!( cs.value(), v_final );
 		
-		assert_eq!( cs.set_upgrade_value( v_upgrade + 2.0 ), Ok(()) );
+		assert_eq!( cs.set_upgrade_value( v_upgrade + 2.0 ), Ok( StatReport::default() ) );
 		assert_eq!( cs.upgrade_raw(), Ok( v_upgrade + 2.0 ) );
 		
 		assert_eq!( cs.value(), v_final + 2.0 );
 		
 		let modifier = Modifier::new_persistent( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Upgrade ).unwrap() );
-		assert_eq!( cs.append_modifier( modifier ), Ok(()) );
+		assert_eq!( cs.append_modifier( modifier ).unwrap().added, Some( ModifierId( 0 ) ) );
 		assert_eq!( cs.value(), v_final + 4.0 );
 	}
 	
@@ -1177,10 +2478,17 @@ mod char_stat_tests {
 		let mod_mult = Some( ModMultConf::new( bounds_mod_mult ) );
 		
 		let mut cs = CharStat::new( base, upgrade, mod_of_base, mod_of_upgrade, mod_of_base_plus_upgrade, mod_mult );
-		assert_eq!( cs.append_modifier( mod_base ), Ok(()) );
-		assert_eq!( cs.append_modifier( mod_upgrade ), Ok(()) );
-		assert_eq!( cs.append_modifier( mod_base_and_up ), Ok(()) );
-		assert_eq!( cs.append_modifier( mod_mod_mlt ), Ok(()) );
+		// a rejected modifier does not consume an id
+		let mod_more = Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::More ).unwrap() );
+		assert_eq!( cs.append_modifier( mod_more ), Err( CsMissingComponent::ModMore.into() ) );
+		
+		let ids: Vec< _ > = [ mod_base, mod_upgrade, mod_base_and_up, mod_mod_mlt.clone() ].into_iter().map( |tmp| cs.append_modifier( tmp ).unwrap().added ).collect();
+		assert_eq!( ids, ( 0..4 ).map( |i| Some( ModifierId( i ) ) ).collect::< Vec<_> >() );
+		// the id is not part of the value
+		assert_eq!( *cs.modifier( ids[ 3 ].unwrap() ).unwrap().get(), mod_mod_mlt );
+		assert_eq!( cs.modifier_count(), 4 );
+		assert_eq!( cs.modifier_count_in( ModCalcStage::ModMult ), 1 );
+		assert_eq!( cs.modifiers_in( ModCalcStage::Upgrade ).map( |tmp| tmp.remaining() ).collect::< Vec<_> >(), vec![ Some( 49 ) ] );
 		
 		let mod_mult = 1.0 + v_mod_mult;
 		
@@ -1192,17 +2500,109 @@ mod char_stat_tests {
 		assert_eq!( cs.upgrade().unwrap(), v_upgrade + res_up_mod );
 		assert_eq!( cs.value(), v_base + v_upgrade + res_base_mod + res_up_mod + res_base_and_up_mod );
 		
-		assert_eq!( cs.set_ts( 25 ), Ok(()) );
+		assert!( cs.set_ts( 25 ).unwrap().crossed.is_empty() );
 		assert_eq!( cs.base(), v_base );
 		assert_eq!( cs.value(), v_base + v_upgrade + res_up_mod + res_base_and_up_mod );
 		
-		assert_eq!( cs.set_ts( 50 ), Ok(()) );
+		assert!( cs.set_ts( 50 ).unwrap().crossed.is_empty() );
 		assert_eq!( cs.value(), v_base + v_upgrade + res_base_and_up_mod );
 		
-		assert_eq!( cs.set_ts( 75 ), Ok(()) );
+		assert!( cs.set_ts( 75 ).unwrap().crossed.is_empty() );
+		assert!( cs.modifier( ids[ 2 ].unwrap() ).is_none() );
+		assert_eq!( cs.modifier_count(), 1 );
 		assert_eq!( cs.value(), v_base + v_upgrade );
 	}
 	
+	#[test]
+	fn set_ts_report() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none, false, false );
+		let mod_mult = ModMultConf::new( Bounds::new_const( 0.0, 3.0 ).unwrap() );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, Some( mod_mult ) );
+		
+		let add = |cs: &mut CharStat, val, mode, stage, ts| cs.append_modifier( Modifier::new_expiring( ModCommon::new( val, mode, stage ).unwrap(), ts ) ).unwrap().added;
+		let short = add( &mut cs, 0.5, ModCalcMode::Mul, ModCalcStage::Base, 10 );
+		let mult = add( &mut cs, 1.0, ModCalcMode::Add, ModCalcStage::ModMult, 20 );
+		let long = add( &mut cs, 2.0, ModCalcMode::Add, ModCalcStage::Base, 20 );
+		cs.flush();
+		assert_eq!( cs.value(), 10.0 + ( 5.0 + 2.0 ) * 2.0 );
+		
+		let expired_ids = |report: &StatReport| report.expired.iter().map( Modifier::id ).collect::< Vec<_> >();
+		
+		// nothing expired, nothing reported
+		assert_eq!( cs.set_ts( 5 ), Ok( StatReport::default() ) );
+		
+		let report = cs.set_ts( 15 ).unwrap();
+		assert_eq!( expired_ids( &report ), vec![ short ] );
+		assert_eq!( report.expired[ 0 ].calc_stage(), ModCalcStage::Base );
+		assert_eq!( report.delta, -10.0 );
+		assert!( !cs.is_dirty() );
+		
+		// pending changes are flushed first, the delta holds only the change caused by the expired modifiers
+		cs.set_base_value( 20.0 ).unwrap();
+		let report = cs.set_ts( 20 ).unwrap();
+		assert_eq!( expired_ids( &report ), vec![ mult, long ] );
+		assert_eq!( report.delta, 20.0 - ( 20.0 + 2.0 * 2.0 ) );
+		assert_eq!( cs.value(), 20.0 );
+		assert_eq!( cs.modifier_count(), 0 );
+	}
+	
+	#[test]
+	fn update_modifier() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+		let mod_mult = ModMultConf::new( Bounds::new_const( 0.0, 1.0 ).unwrap() );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, Some( mod_mult ) );
+		
+		let log = ChangeLog::new();
+		cs.set_listener( Some( SharedListener::new( log.clone() ) ) );
+		
+		let common = ModCommon::new( 2.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		let id = cs.append_modifier( Modifier::new_expiring( common, 10 ) ).unwrap().added.unwrap();
+		let other = cs.append_modifier( Modifier::new_persistent( common ) ).unwrap().added.unwrap();
+		assert_eq!( cs.value(), 14.0 );
+		log.take();
+		
+		// value and expiration in place
+		cs.update_modifier( id, |tmp| {
+			tmp.set_value( 5.0 )?;
+			tmp.set_expiration_ts( 20 )
+		} ).unwrap();
+		assert_eq!( cs.value(), 17.0 );
+		assert_eq!( cs.next_expiration(), Some( 20 ) );
+		assert_eq!( cs.modifiers().map( |tmp| tmp.id() ).collect::< Vec<_> >(), vec![ Some( id ), Some( other ) ] );
+		assert_eq!( log.take(), vec![ StatChange { old: 14.0, new: 17.0, cause: StatChangeCause::ModifierUpdated } ] );
+		
+		// move to modifier multiplier, 0.5 scales the remaining base modifier
+		cs.update_modifier( id, |tmp| {
+			tmp.set_value( 0.5 )?;
+			tmp.set_calc_stage( ModCalcStage::ModMult )
+		} ).unwrap();
+		assert_eq!( cs.value(), 13.0 );
+		assert_eq!( cs.modifier_count_in( ModCalcStage::Base ), 1 );
+		assert_eq!( cs.modifier( id ).unwrap().calc_stage(), ModCalcStage::ModMult );
+		assert_eq!( cs.modifier_count(), 2 );
+		assert_eq!( log.take().len(), 1 );
+		
+		// invalid changes keep the stat unchanged
+		let expected: CharStatError = CsLogicIssue::InvalidModifierMode( ModCalcMode::Mul, vec![ ModCalcMode::Add, ModCalcMode::Sub ] ).into();
+		assert_eq!( cs.update_modifier( id, |tmp| tmp.set_calc_mode( ModCalcMode::Mul ) ), Err( expected ) );
+		assert_eq!( cs.update_modifier( other, |tmp| tmp.set_calc_stage( ModCalcStage::More ) ), Err( CsMissingComponent::ModMore.into() ) );
+		assert_eq!( cs.update_modifier( other, |tmp| tmp.set_expiration_ts( 5 ) ).map( |_| () ), Err( CsLogicIssue::InvalidModifierType( ModType::Persistent, "Expiring".to_string() ).into() ) );
+		assert_eq!( cs.update_modifier( ModifierId( 9 ), |_| Ok(()) ), Err( CsMissingComponent::Modifier.into() ) );
+		assert_eq!( cs.value(), 13.0 );
+		assert_eq!( cs.modifier_count(), 2 );
+		assert!( log.take().is_empty() );
+		
+		// expiration follows the moved modifier
+		let report = cs.set_ts( 20 ).unwrap();
+		assert_eq!( report.expired.iter().map( Modifier::id ).collect::< Vec<_> >(), vec![ Some( id ) ] );
+		assert_eq!( cs.value(), 12.0 );
+	}
+	
 	#[test]
 	fn nan_handling() {
 		//let expected: CharStatError = CsInvalidValue::Nan( "value".to_string() ).into();
@@ -1214,17 +2614,620 @@ mod char_stat_tests {
 		assert_eq!( ronuding_helper.precision, Some( 0.1 ) );
 	}
 	
+	#[test]
+	fn overflow() {
+		#[derive( Debug )]
+		struct NotANumber;
+		
+		impl ModifierFn for NotANumber {
+			fn contribution ( &self, _stage_input: f64, _ctx: &ModFnContext ) -> f64 {
+				f64::NAN
+			}
+			
+			fn type_name ( &self ) -> &'static str {
+				"not_a_number"
+			}
+		}
+		
+		let bounds = Bounds::unbounded();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( f64::MAX, true, bounds, none.clone(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none, false, false );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, None );
+		
+		let expected: CharStatError = CsInvalidValue::Infinite( "value".to_string() ).into();
+		assert_eq!( cs.set_base_value( f64::INFINITY ), Err( expected.clone() ) );
+		
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		assert_eq!( cs.value(), f64::MAX );
+		assert!( cs.has_overflowed() );
+		assert_eq!( cs.value_checked(), Err( expected ) );
+		
+		cs.set_base_value( 1.0 ).unwrap();
+		assert_eq!( cs.value_checked(), Ok( 2.0 ) );
+		
+		// infinite stage is clamped and reported even when the total bounds bring the value back
+		let mut bound = cs.clone();
+		bound.append_modifier( Modifier::new_persistent( ModCommon::new( f64::MAX, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		bound.set_base_value( f64::MAX ).unwrap();
+		bound.set_total_bounds( Some( TotalBounds::new_absolute( Bounds::at_most( 100.0 ).unwrap() ) ) );
+		assert_eq!( bound.value(), 100.0 );
+		assert!( bound.has_overflowed() );
+		assert_eq!( bound.breakdown().mod_of_base(), f64::MAX );
+		
+		bound.set_base_value( 1.0 ).unwrap();
+		assert!( !bound.has_overflowed() );
+		
+		// NaN keeps the previous value, but is reported
+		cs.flush();
+		let modifier = Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Custom, ModCalcStage::Base ).unwrap() ).with_fn( NotANumber ).unwrap();
+		cs.append_modifier( modifier ).unwrap();
+		assert_eq!( cs.value(), 2.0 );
+		assert_eq!( cs.value_checked(), Err( CsInvalidValue::Nan( "value".to_string() ).into() ) );
+	}
+	
+	#[test]
+	fn lazy_update() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, none.clone() ).unwrap();
+		let mod_conf = |stage| Some( ModConf::new( stage, bounds, none.clone(), false, false ) );
+		let mod_mult = ModMultConf::new( Bounds::new_const( 0.0, 3.0 ).unwrap() );
+		let mut lazy = CharStat::new( base, Some( upgrade ), mod_conf( ModCalcStage::Base ), mod_conf( ModCalcStage::Upgrade ), mod_conf( ModCalcStage::BasePlusUpgrade ), Some( mod_mult ) );
+		lazy.set_mod_more( Some( ModMoreConf::new( Bounds::new_const( 0.0, 10.0 ).unwrap(), none ) ) );
+		assert!( lazy.is_dirty() );
+		lazy.flush();
+		let mut eager = lazy.clone();
+		
+		let steps: Vec< fn( &mut CharStat ) -> StatReport > = vec![
+			|cs| cs.append_modifier( Modifier::new_expiring( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap(), 10 ) ).unwrap(),
+			|cs| cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Upgrade ).unwrap() ) ).unwrap(),
+			|cs| cs.append_modifier( Modifier::new_persistent( ModCommon::new( 0.1, ModCalcMode::Mul, ModCalcStage::BasePlusUpgrade ).unwrap() ) ).unwrap(),
+			|cs| cs.append_modifier( Modifier::new_expiring( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::ModMult ).unwrap(), 20 ) ).unwrap(),
+			|cs| cs.append_modifier( Modifier::new_persistent( ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::More ).unwrap() ) ).unwrap(),
+			|cs| cs.set_base_value( 20.0 ).unwrap(),
+			|cs| cs.set_upgrade_value( 5.0 ).unwrap(),
+			|cs| cs.set_ts( 15 ).unwrap(),
+			|cs| cs.set_total_rounding( Some( RoundingHelper::new( RoundingFnEnum::Floor, None ) ) ),
+			|cs| cs.set_ts( 25 ).unwrap(),
+		];
+		
+		for step in steps {
+			let report = step( &mut lazy );
+			step( &mut eager );
+			eager.refresh();
+			
+			// `set_ts` which removes a modifier recalculates to report the delta
+			assert_eq!( lazy.is_dirty(), report.expired.is_empty() );
+			assert_eq!( lazy.value(), eager.value() );
+			assert_eq!( lazy.breakdown(), eager.breakdown() );
+			assert_eq!( lazy, eager );
+		}
+		
+		lazy.flush();
+		assert!( !lazy.is_dirty() );
+		assert_eq!( lazy, eager );
+	}
+	
+	#[test]
+	fn listener() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, none.clone() ).unwrap();
+		let mod_conf = ModConf::new( ModCalcStage::Base, bounds, none, false, false );
+		let mut cs = CharStat::new( base, Some( upgrade ), Some( mod_conf ), None, None, None );
+		
+		let log = ChangeLog::new();
+		cs.set_base_value( 20.0 ).unwrap();
+		cs.set_listener( Some( SharedListener::new( log.clone() ) ) );
+		assert!( !cs.is_dirty() );
+		
+		// unchanged value is not reported
+		cs.set_base_value( 20.0 ).unwrap();
+		cs.set_ts( 5 ).unwrap();
+		cs.refresh();
+		assert!( log.take().is_empty() );
+		
+		let mut clone = cs.clone();
+		cs.inc_upgrade_value( 3.0 ).unwrap();
+		clone.append_modifier( Modifier::new_expiring( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap(), 10 ) ).unwrap();
+		clone.set_total_bounds( Some( TotalBounds::new_absolute( Bounds::at_most( 25.0 ).unwrap() ) ) );
+		clone.set_ts( 10 ).unwrap();
+		assert!( !clone.is_dirty() );
+		
+		let causes: Vec< _ > = log.take().iter().map( |tmp| ( tmp.old, tmp.new, tmp.cause ) ).collect();
+		assert_eq!( causes, vec![
+			( 22.0, 25.0, StatChangeCause::Upgrade ),
+			( 22.0, 32.0, StatChangeCause::ModifierAdded( ModCalcStage::Base ) ),
+			( 32.0, 25.0, StatChangeCause::Config ),
+			( 25.0, 22.0, StatChangeCause::ModifierExpired ),
+		] );
+		
+		cs.set_listener( None );
+		cs.set_base_value( 30.0 ).unwrap();
+		assert!( cs.is_dirty() );
+		assert!( log.take().is_empty() );
+	}
+	
+	#[test]
+	fn thresholds() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 80.0, true, bounds, none.clone(), None ).unwrap();
+		let mod_conf = ModConf::new( ModCalcStage::Base, Bounds::new_const( -100.0, 100.0 ).unwrap(), none, false, false );
+		let mut cs = CharStat::new( base, None, Some( mod_conf ), None, None, None );
+		
+		let low = cs.add_threshold( ThresholdSpec::new( BoundSpec::percent_of( StageRef::Base, 0.5 ), ThresholdDirection::Below, 10.0 ).unwrap() );
+		let high = cs.add_threshold( ThresholdSpec::above( BoundSpec::Const( 70.0 ) ) );
+		assert_eq!( cs.is_threshold_active( low ), Some( false ) );
+		assert_eq!( cs.is_threshold_active( high ), Some( true ) );
+		
+		// expiring debuff crosses both thresholds
+		let report = cs.append_modifier( Modifier::new_expiring( ModCommon::new( 45.0, ModCalcMode::Sub, ModCalcStage::Base ).unwrap(), 10 ) ).unwrap();
+		assert_eq!( report.crossed, vec![ ThresholdCrossing { id: low, is_entered: true }, ThresholdCrossing { id: high, is_entered: false } ] );
+		assert!( !cs.is_dirty() );
+		
+		// level of `low` follows base, hysteresis keeps it active
+		assert!( cs.set_base_value( 85.0 ).unwrap().is_empty() );
+		assert!( cs.set_ts( 5 ).unwrap().is_empty() );
+		
+		#[cfg( feature = "serde" )]
+		{
+			let json = serde_json::to_string( &cs ).unwrap();
+			let mut out: CharStat = serde_json::from_str( &json ).unwrap();
+			assert_eq!( out, cs );
+			
+			let report = out.set_ts( 10 ).unwrap();
+			assert!( report.has_left( low ) && report.has_entered( high ) );
+			
+			// registered source is serialized by id
+			let source = SharedSource::register( "lib_tests_threshold", SharedValue::new( 200.0 ) );
+			let id = out.add_threshold( ThresholdSpec::above( BoundSpec::Source( source ) ) );
+			let json = serde_json::to_string( &out ).unwrap();
+			let out: CharStat = serde_json::from_str( &json ).unwrap();
+			assert_eq!( out.is_threshold_active( id ), Some( false ) );
+		}
+		
+		assert!( cs.remove_threshold( low ).is_some() );
+		assert!( cs.remove_threshold( low ).is_none() );
+		assert_eq!( cs.set_ts( 10 ).unwrap().crossed, vec![ ThresholdCrossing { id: high, is_entered: true } ] );
+		
+		assert!( cs.remove_threshold( high ).is_some() );
+		cs.set_base_value( 10.0 ).unwrap();
+		assert!( cs.is_dirty() );
+		
+		// ids are not reused after the last threshold was removed
+		let next = cs.add_threshold( ThresholdSpec::above( BoundSpec::Const( 5.0 ) ) );
+		assert!( next != low && next != high );
+		assert_eq!( cs.remove_threshold( low ), None );
+	}
+	
+	#[test]
+	fn total_bounds_and_rounding() {
+		let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.5, bounds, RoundingHelper::new_none() ).unwrap();
+		let mut cs = CharStat::new_no_mod( base, Some( upgrade ) );
+		assert_eq!( cs.value(), 12.5 );
+		
+		cs.set_total_rounding( Some( RoundingHelper::new( RoundingFnEnum::Floor, None ) ) );
+		assert_eq!( cs.value(), 12.0 );
+		
+		// rounding happens after bounds, max is 115% of base
+		cs.set_total_bounds( Some( TotalBounds::new( Bounds::new_const( 0.0, 1.15 ).unwrap(), false, true ) ) );
+		assert_eq!( cs.value(), 11.0 );
+		assert_eq!( cs.breakdown().sum(), 12.5 );
+		
+		// bounds follow base
+		cs.set_base_value( 20.0 ).unwrap();
+		assert_eq!( cs.value(), 22.0 );
+		
+		cs.set_total_bounds( None );
+		cs.set_total_rounding( None );
+		assert_eq!( cs.value(), 22.5 );
+	}
+	
+	#[test]
+	fn more_stage() {
+		let bounds = Bounds::new_const( -10.0, 100.0 ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, None );
+		
+		let more = Modifier::new_expiring( ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::More ).unwrap(), 10 );
+		let expected = CsMissingComponent::ModMore.into();
+		assert_eq!( cs.append_modifier( more.clone() ), Err( expected ) );
+		
+		cs.set_mod_more( Some( ModMoreConf::new( bounds, RoundingHelper::new_none() ) ) );
+		cs.append_modifier( more.clone() ).unwrap();
+		cs.append_modifier( more ).unwrap();
+		
+		// two 50% increased modifiers add up to +100%
+		let increased = Modifier::new_persistent( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() );
+		cs.append_modifier( increased.clone() ).unwrap();
+		cs.append_modifier( increased ).unwrap();
+		
+		// ( 10 + 5 + 5 ) * 1.5 * 1.5
+		assert_eq!( cs.value(), 45.0 );
+		assert_eq!( cs.breakdown().more(), 2.25 );
+		
+		cs.set_ts( 10 ).unwrap();
+		assert_eq!( cs.value(), 20.0 );
+	}
+	
+	#[test]
+	fn pipeline() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, none.clone() ).unwrap();
+		
+		// classic preset gives the same result as mod_of_* components
+		// each stage keeps its' own bounds, rounding and percent flags
+		let mod_of_base = Some( ModConf::new( ModCalcStage::Base, Bounds::new_const( 0.0, 4.0 ).unwrap(), none.clone(), false, false ) );
+		let mod_of_upgrade = Some( ModConf::new( ModCalcStage::Upgrade, bounds, RoundingHelper::new( RoundingFnEnum::Floor, Some( 0.5 ) ), false, false ) );
+		let mod_of_base_plus_upgrade = Some( ModConf::new( ModCalcStage::BasePlusUpgrade, Bounds::new_const( 0.0, 0.25 ).unwrap(), none.clone(), false, true ) );
+		let mut classic = CharStat::new( base.clone(), Some( upgrade ), mod_of_base, mod_of_upgrade, mod_of_base_plus_upgrade, Some( ModMultConf::new( bounds ) ) );
+		let mut piped = classic.clone();
+		piped.use_classic_pipeline().unwrap();
+		assert_eq!( piped.use_classic_pipeline(), Err( CsInvalidValue::NotAllowed( "pipeline".to_string() ).into() ) );
+		assert_eq!( piped.mod_conf( ModCalcStage::Base ).map( ModConf::bounds_max ), Some( 4.0 ) );
+		
+		let modifiers = [
+			ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap(),
+			ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Upgrade ).unwrap(),
+			ModCommon::new( 0.25, ModCalcMode::Mul, ModCalcStage::BasePlusUpgrade ).unwrap(),
+			ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::ModMult ).unwrap(),
+		];
+		
+		for ( i, common ) in modifiers.into_iter().enumerate() {
+			classic.append_modifier( Modifier::new_expiring( common, i as u64 + 1 ) ).unwrap();
+			piped.append_modifier( Modifier::new_expiring( common, i as u64 + 1 ) ).unwrap();
+			assert_eq!( classic.value(), piped.value() );
+			assert_eq!( classic.breakdown(), piped.breakdown() );
+			assert_eq!( classic.base(), piped.base() );
+			assert_eq!( classic.upgrade(), piped.upgrade() );
+		}
+		
+		for ts in 1..5 {
+			classic.set_ts( ts ).unwrap();
+			piped.set_ts( ts ).unwrap();
+			assert_eq!( classic.value(), piped.value() );
+		}
+		
+		// custom stages
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Custom( 0 ) ).unwrap();
+		let expected = CsMissingComponent::PipelineStage.into();
+		assert_eq!( piped.append_modifier( Modifier::new_persistent( common ) ), Err( expected ) );
+		let expected = CsMissingComponent::Pipeline.into();
+		assert_eq!( classic.append_modifier( Modifier::new_persistent( common ) ), Err( expected ) );
+		
+		let mut pipeline = Pipeline::new();
+		let flat = ModConf::new( ModCalcStage::Custom( 0 ), bounds, none.clone(), false, false );
+		pipeline.push_stage( PipelineStage::new( "flat", StageInput::Base, true, Some( flat ), None, none.clone() ) ).unwrap();
+		pipeline.push_stage( PipelineStage::new( "caps", StageInput::Previous, false, None, Some( Bounds::new_const( 0.0, 12.0 ).unwrap() ), none ) ).unwrap();
+		
+		let mut cs = CharStat::new_pipeline( base, None, pipeline, None );
+		cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( cs.value(), 11.0 );
+		cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+		cs.append_modifier( Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( cs.value(), 12.0 );
+		assert!( cs.is_dirty() );
+		
+		// stage values are those of the last flush
+		assert_eq!( cs.pipeline().unwrap().stage( "flat" ).unwrap().value(), 10.0 );
+		cs.flush();
+		assert_eq!( cs.pipeline().unwrap().stage( "flat" ).unwrap().value(), 13.0 );
+	}
+	
+	#[test]
+	fn modifier_algebra_matrix() {
+		#[derive( Debug )]
+		struct Half;
+		
+		impl ModifierFn for Half {
+			fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64 {
+				ctx.value() * stage_input * 0.5
+			}
+			
+			fn type_name ( &self ) -> &'static str {
+				"half"
+			}
+		}
+		
+		let bounds = Bounds::unbounded();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 4.0, bounds, none.clone() ).unwrap();
+		let mod_conf = | stage | Some( ModConf::new( stage, bounds, none.clone(), false, false ) );
+		
+		let mut pipeline = Pipeline::classic( mod_conf( ModCalcStage::Base ), mod_conf( ModCalcStage::Upgrade ), mod_conf( ModCalcStage::BasePlusUpgrade ) ).unwrap();
+		let custom = ModConf::new( ModCalcStage::Custom( 1 ), bounds, none.clone(), false, false );
+		pipeline.push_stage( PipelineStage::new( "custom", StageInput::Previous, false, Some( custom ), None, none.clone() ) ).unwrap();
+		
+		let classic = CharStat::new( base.clone(), Some( upgrade.clone() ), mod_conf( ModCalcStage::Base ), mod_conf( ModCalcStage::Upgrade ), mod_conf( ModCalcStage::BasePlusUpgrade ), Some( ModMultConf::new( bounds ) ) );
+		let piped = CharStat::new_pipeline( base, Some( upgrade ), pipeline, Some( ModMultConf::new( bounds ) ) );
+		
+		let modes = [ ModCalcMode::Add, ModCalcMode::Sub, ModCalcMode::Mul, ModCalcMode::Div, ModCalcMode::Custom ];
+		let stages = [ ModCalcStage::Base, ModCalcStage::Upgrade, ModCalcStage::BasePlusUpgrade, ModCalcStage::ModMult, ModCalcStage::More, ModCalcStage::Custom( 1 ) ];
+		
+		// base 10 + upgrade 4 + base modifier 1 = 15, modifier value 2
+		// stage inputs: Base 10, Upgrade 4, BasePlusUpgrade 14, Custom( 1 ) 15 ( running total )
+		let expected = [
+			[ Some( 17.0 ), Some( 13.0 ), Some( 35.0 ), Some( 20.0 ), Some( 25.0 ) ],
+			[ Some( 17.0 ), Some( 13.0 ), Some( 23.0 ), Some( 17.0 ), Some( 19.0 ) ],
+			[ Some( 17.0 ), Some( 13.0 ), Some( 43.0 ), Some( 22.0 ), Some( 29.0 ) ],
+			[ Some( 17.0 ), Some( 13.0 ), None, None, None ],
+			[ Some( 45.0 ), Some( -15.0 ), Some( 30.0 ), Some( 7.5 ), None ],
+			[ Some( 17.0 ), Some( 13.0 ), Some( 45.0 ), Some( 22.5 ), Some( 30.0 ) ],
+		];
+		
+		for ( stage, row ) in stages.into_iter().zip( expected ) {
+			for ( mode, expected ) in modes.into_iter().zip( row ) {
+				let common = match ModCommon::new( 2.0, mode, stage ) {
+					Ok( tmp ) => tmp,
+					Err( err ) => {
+						assert_eq!( ( stage, expected ), ( ModCalcStage::ModMult, None ) );
+						assert_eq!( err, CsLogicIssue::InvalidModifierMode( mode, vec![ ModCalcMode::Add, ModCalcMode::Sub ] ).into() );
+						continue
+					},
+				};
+				
+				let modifier = match mode {
+					ModCalcMode::Custom => Modifier::new_persistent( common ).with_fn( Half ).unwrap(),
+					_ => Modifier::new_persistent( common ),
+				};
+				let baseline = Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap() );
+				
+				for ( mut cs, is_piped ) in [ ( classic.clone(), false ), ( piped.clone(), true ) ] {
+					cs.set_mod_more( Some( ModMoreConf::new( bounds, none.clone() ) ) );
+					cs.append_modifier( baseline.clone() ).unwrap();
+					
+					let result = cs.append_modifier( modifier.clone() );
+					
+					match expected {
+						_ if stage == ModCalcStage::Custom( 1 ) && !is_piped => assert_eq!( result, Err( CsMissingComponent::Pipeline.into() ) ),
+						Some( value ) => {
+							assert!( result.is_ok(), "{stage} {mode}" );
+							assert_eq!( cs.value(), value, "{stage} {mode}" );
+						},
+						None => assert!( result.is_err(), "{stage} {mode}" ),
+					}
+				}// for
+			}// for
+		}// for
+		
+		let expected = CsInvalidValue::CannotBeZero( "value".to_string() ).into();
+		assert_eq!( ModCommon::new( 0.0, ModCalcMode::Div, ModCalcStage::Base ), Err( expected ) );
+	}
+	
+	#[test]
+	fn soft_caps() {
+		let bounds = Bounds::new_const( 0.0, 200.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 50.0, true, bounds, none.clone(), None ).unwrap();
+		let mut mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none.clone(), false, false );
+		mod_of_base.set_soft_cap( Some( SoftCap::linear( 20.0, 0.5 ).unwrap() ) );
+		
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, Some( ModMultConf::new( bounds ) ) );
+		cs.set_total_soft_cap( Some( SoftCap::linear( 75.0, 0.5 ).unwrap() ) );
+		
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		// modifiers: 50 -> 35, total: 85 -> 80
+		assert_eq!( cs.value(), 80.0 );
+		
+		let breakdown = cs.breakdown();
+		assert_eq!( breakdown.mod_soft_cap_loss(), 15.0 );
+		assert_eq!( breakdown.total_soft_cap_loss(), 5.0 );
+		
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::ModMult ).unwrap() ) ).unwrap();
+		assert_eq!( cs.breakdown().mod_soft_cap_loss(), 30.0 );
+		
+		cs.set_total_soft_cap( None );
+		assert_eq!( cs.value(), 120.0 );
+	}
+	
+	#[test]
+	fn dyn_bounds() {
+		let bounds = Bounds::new_const( 0.0, 200.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 50.0, true, bounds, none.clone(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none.clone(), false, false );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, Some( ModMultConf::new( bounds ) ) );
+		
+		// modifiers cannot exceed 40% of base
+		let dyn_bounds = DynBounds::new( None, Some( BoundSpec::percent_of( StageRef::Base, 0.4 ) ) );
+		cs.set_mod_dyn_bounds( ModCalcStage::Base, Some( dyn_bounds ) ).unwrap();
+		
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		assert_eq!( cs.value(), 70.0 );
+		
+		cs.set_base_value( 100.0 ).unwrap();
+		assert_eq!( cs.value(), 140.0 );
+		
+		let expected = CsInvalidValue::NotAllowed( "stage".to_string() ).into();
+		assert_eq!( cs.set_mod_dyn_bounds( ModCalcStage::More, None ), Err( expected ) );
+		assert_eq!( cs.set_upgrade_dyn_bounds( None ), Err( CsMissingComponent::Upgrade.into() ) );
+		
+		// bonus cannot exceed 50% of the flat stage, repeated refresh does not drift
+		let flat = ModConf::new( ModCalcStage::Custom( 0 ), bounds, none.clone(), false, false );
+		let mut bonus = ModConf::new( ModCalcStage::Custom( 1 ), bounds, none.clone(), false, false );
+		bonus.set_dyn_bounds( Some( DynBounds::new( None, Some( BoundSpec::percent_of( StageRef::stage( "flat" ), 0.5 ) ) ) ) );
+		
+		let mut pipeline = Pipeline::new();
+		pipeline.push_stage( PipelineStage::new( "flat", StageInput::Base, true, Some( flat ), None, none.clone() ) ).unwrap();
+		pipeline.push_stage( PipelineStage::new( "bonus", StageInput::Previous, false, Some( bonus ), None, none ) ).unwrap();
+		
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let mut cs = CharStat::new_pipeline( base, None, pipeline, None );
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 2.0, ModCalcMode::Add, ModCalcStage::Custom( 0 ) ).unwrap() ) ).unwrap();
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 100.0, ModCalcMode::Add, ModCalcStage::Custom( 1 ) ).unwrap() ) ).unwrap();
+		assert_eq!( cs.value(), 18.0 );
+		
+		cs.refresh();
+		cs.refresh();
+		assert_eq!( cs.value(), 18.0 );
+	}
+	
+	#[test]
+	fn mod_stage_config() {
+		let bounds = Bounds::new_mut( 0.0, 100.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let base = BaseConf::new( 10.0, true, bounds, none.clone(), None ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none.clone(), false, false );
+		let mod_mult = ModMultConf::new( Bounds::new_mut( 0.0, 1.0 ).unwrap() );
+		let mut cs = CharStat::new( base.clone(), None, Some( mod_of_base ), None, None, Some( mod_mult ) );
+		
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::ModMult ).unwrap() ) ).unwrap();
+		assert_eq!( cs.value(), 20.0 );
+		
+		cs.set_mod_mult_bounds_max( 0.5 ).unwrap();
+		assert_eq!( cs.value(), 17.5 );
+		assert_eq!( cs.mod_mult().unwrap().value(), 1.5 );
+		
+		assert_eq!( cs.set_mod_mult_bounds_max_const(), Ok( StatReport::default() ) );
+		assert_eq!( cs.set_mod_mult_bounds_max( 1.0 ), Err( CsLogicIssue::FieldIsConst.into() ) );
+		assert_eq!( cs.mod_mult_bounds_max(), Some( 0.5 ) );
+		
+		cs.set_mod_bounds_max( ModCalcStage::Base, 4.0 ).unwrap();
+		assert_eq!( cs.value(), 16.0 );
+		assert_eq!( cs.mod_bounds_max( ModCalcStage::Base ), Some( 4.0 ) );
+		assert_eq!( cs.mod_bounds_max( ModCalcStage::Upgrade ), None );
+		
+		assert_eq!( cs.set_mod_percent( ModCalcStage::Upgrade, true, true ), Err( CsMissingComponent::ModOfUpgrade.into() ) );
+		assert_eq!( cs.set_mod_rounding( ModCalcStage::Custom( 0 ), none.clone() ), Err( CsMissingComponent::Pipeline.into() ) );
+		assert_eq!( cs.set_mod_bounds_min_const( ModCalcStage::ModMult ), Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() ) );
+		
+		let mut minimal = CharStat::new_minimal( BaseConf::new( 10.4, true, bounds, none.clone(), None ).unwrap() );
+		minimal.set_base_rounding( RoundingHelper::new( RoundingFnEnum::Round, None ) );
+		assert_eq!( minimal.value(), 10.0 );
+		
+		// stages of a pipeline
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, none.clone(), false, false );
+		let mut piped = CharStat::new_pipeline( base, None, Pipeline::classic( Some( mod_of_base ), None, None ).unwrap(), None );
+		piped.append_modifier( Modifier::new_persistent( ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap() ) ).unwrap();
+		assert_eq!( piped.value(), 15.0 );
+		
+		piped.set_mod_bounds_max( ModCalcStage::Base, 2.0 ).unwrap();
+		assert_eq!( piped.value(), 12.0 );
+		assert_eq!( piped.pipeline().unwrap().stage( "base" ).and_then( PipelineStage::mods ).map( ModConf::bounds_max ), Some( 2.0 ) );
+		assert_eq!( piped.set_mod_rounding( ModCalcStage::Custom( 3 ), none ), Err( CsMissingComponent::PipelineStage.into() ) );
+	}
+	
+	#[test]
+	fn introspection() {
+		let bounds = Bounds::new( 0.0, 100.0, false, true ).unwrap();
+		let precise = RoundingHelper::new( RoundingFnEnum::Round, Some( 0.5 ) );
+		let mult = BaseMultConf::new( 2.0, 1.0, bounds, bounds, RoundingHelper::new_none() ).unwrap();
+		let base = BaseConf::new( 10.0, false, bounds, precise.clone(), Some( mult ) ).unwrap();
+		let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), true, false );
+		let mut cs = CharStat::new( base, None, Some( mod_of_base ), None, None, None );
+		
+		let stack = ModStackConf::new( 5, 10, 1, 3 ).unwrap();
+		let common = ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+		cs.append_modifier( Modifier::new_stacked( common, stack ) ).unwrap();
+		cs.append_modifier( Modifier::new_expiring( ModCommon::new( 2.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap(), 30 ) ).unwrap();
+		cs.set_ts( 5 ).unwrap();
+		cs.flush();
+		
+		let base = cs.base_conf();
+		assert!( !base.is_mut() && !base.bounds().is_min_mut() && base.bounds().is_max_mut() );
+		assert_eq!( ( base.stored_value(), base.value() ), ( 10.0, 20.0 ) );
+		assert_eq!( ( base.rounding().function(), base.rounding().precision() ), ( RoundingFnEnum::Round, Some( 0.5 ) ) );
+		
+		let mult = base.mult().unwrap();
+		assert_eq!( ( mult.base(), mult.exponent(), mult.multiplier() ), ( 2.0, 1.0, 2.0 ) );
+		assert!( cs.upgrade_conf().is_none() );
+		
+		let mod_conf = cs.mod_conf( ModCalcStage::Base ).unwrap();
+		assert_eq!( ( mod_conf.stage(), mod_conf.is_min_percent(), mod_conf.is_max_percent() ), ( ModCalcStage::Base, true, false ) );
+		
+		let stacked = cs.modifier( ModifierId( 0 ) ).unwrap();
+		let ModType::Stacked { conf } = stacked.mod_type() else { panic!() };
+		assert_eq!( ( conf.last_ts(), conf.duration(), conf.stack_value(), conf.stack_max() ), ( 5, 10, 1, 3 ) );
+		assert_eq!( stacked.common().mode(), ModCalcMode::Mul );
+		assert_eq!( cs.ts(), 5 );
+		
+		assert_eq!( precise.to_string(), "RoundingHelper = (Round, precision: 0.5)" );
+		assert_eq!( mod_conf.to_string(), "ModConf = (Base, value: 12, modifiers: 2, Bounds = (const min: 0, mut max: 100), percent min: true, percent max: false, RoundingHelper = (None))" );
+		assert_eq!( cs.to_string().lines().take( 3 ).collect::< Vec<_> >(), vec![
+			"CharStat = (value: 32, ts: 5, modifiers: 2)",
+			"\tModifier = (id: 0, Stacked, Mul 0.5, stage: Base, stack: 1/3)",
+			"\tModifier = (id: 1, Expiring, Add 2, stage: Base, expires: 30)",
+		] );
+		assert!( cs.to_string().ends_with( "total: 32" ) );
+	}
+	
 	#[cfg( feature = "serde" )]
 	#[test]
 	fn test_serde() {
 		let bounds_base = Bounds::new_const( 4.0, 20.0 ).unwrap();
 		let base = BaseConf::new( 10.0, true, bounds_base, RoundingHelper::new_none(), None ).unwrap();
-		let cs = CharStat::new_minimal( base );
+		let mut cs = CharStat::new_minimal( base );
 		
 		let serialized = serde_json::to_string( &cs ).unwrap();
 		let deserialized: CharStat = serde_json::from_str( &serialized ).unwrap();
 		
 		assert_eq!( cs, deserialized );
+		
+		// dirty flags are not serialized, deserialized stat is recalculated
+		cs.set_base_value( 12.0 ).unwrap();
+		assert!( cs.is_dirty() );
+		assert!( !serialized.contains( "dirty" ) );
+		
+		let serialized = serde_json::to_string( &cs ).unwrap();
+		let deserialized: CharStat = serde_json::from_str( &serialized ).unwrap();
+		assert_eq!( deserialized.value(), 12.0 );
+		assert_eq!( cs, deserialized );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn serde_baseline_save() {
+		// written by 0.1.3, before `Shared` and the later components
+		let json = r#"{"current_value":21.5,"time_stamp":0,"val_base":12.0,"val_base_mod":7.5,"val_upgrade":2.0,"val_upgrade_mod":0.0,"val_base_plus_upgrade_mod":0.0,"val_mod_mult":1.25,"base":{"value":10.0,"is_mut":true,"bounds":{"v_min":0.0,"v_max":100.0,"is_min_mut":false,"is_max_mut":false},"rounding_fn":{"function":"Round","precision":0.5},"mult":{"base":1.1,"exponent":2.0,"rounding_fn":{"function":"None","precision":null},"bounds_base":{"v_min":1.0,"v_max":2.0,"is_min_mut":false,"is_max_mut":false},"bounds_exp":{"v_min":0.0,"v_max":10.0,"is_min_mut":false,"is_max_mut":false},"multiplier":1.2100000000000002}},"upgrade":{"value":2.0,"bounds":{"v_min":0.0,"v_max":100.0,"is_min_mut":false,"is_max_mut":false},"rounding_fn":{"function":"None","precision":null}},"mod_of_base":{"value":6.0,"stage":"Base","bounds":{"v_min":0.0,"v_max":100.0,"is_min_mut":false,"is_max_mut":false},"rounding_fn":{"function":"None","precision":null},"mod_vec":[{"common":{"value":0.5,"mode":"Mul","stage":"Base"},"mod_type":{"Expiring":{"exp_ts":50}}}],"is_min_percent":false,"is_max_percent":false},"mod_of_upgrade":null,"mod_of_base_plus_upgrade":null,"mod_mult":{"value":1.25,"bounds":{"v_min":0.0,"v_max":1.0,"is_min_mut":false,"is_max_mut":false},"mod_vec":[{"common":{"value":0.25,"mode":"Add","stage":"ModMult"},"mod_type":"Persistent"}]}}"#;
+		
+		let cs: CharStat = serde_json::from_str( json ).unwrap();
+		assert_eq!( cs.value(), 21.5 );
+		assert_eq!( cs.base_bounds_max(), 100.0 );
+		assert_eq!( cs.modifier_count(), 2 );
+		assert_eq!( cs.next_expiration(), Some( 50 ) );
+	}
+	
+	#[test]
+	fn shared_config() {
+		let bounds = Shared::register( "lib_tests_level", Bounds::new_mut( 1.0, 20.0 ).unwrap() );
+		let rounding = Shared::new( RoundingHelper::new( RoundingFnEnum::Floor, None ) );
+		let exp_bounds = Bounds::new_mut( 0.0, 10.0 ).unwrap();
+		let mult = Shared::new( BaseMultConf::new( 2.0, 1.0, Bounds::new_const( 1.0, 3.0 ).unwrap(), exp_bounds, RoundingHelper::new_none() ).unwrap() );
+		
+		let mut stats: Vec< CharStat > = ( 0..3 ).map( |_| {
+			let base = BaseConf::new( 5.0, true, bounds.clone(), rounding.clone(), None ).unwrap().with_shared_mult( Some( mult.clone() ) );
+			CharStat::new_minimal( base )
+		} ).collect();
+		assert_eq!( stats[ 0 ].value(), 10.0 );
+		
+		// mutations copy the config
+		stats[ 0 ].set_mult_exponent( 2.0 ).unwrap();
+		stats[ 1 ].set_base_bounds_max( 10.0 ).unwrap();
+		assert_eq!( stats[ 0 ].value(), 20.0 );
+		assert_eq!( stats[ 1 ].value(), 10.0 );
+		assert_eq!( stats[ 1 ].base_bounds_max(), 10.0 );
+		assert_eq!( stats[ 2 ].base_bounds_max(), 20.0 );
+		assert_eq!( bounds.max(), 20.0 );
+		
+		#[cfg( feature = "serde" )]
+		{
+			let json = serde_json::to_string( &stats[ 2 ] ).unwrap();
+			assert!( json.contains( r#""bounds":{"shared":"lib_tests_level"}"# ) );
+			
+			let deserialized: CharStat = serde_json::from_str( &json ).unwrap();
+			assert_eq!( deserialized, stats[ 2 ] );
+		}
 	}
 }
 
@@ -0,0 +1,140 @@
+use std::fmt::{ Display, Formatter };
+use std::sync::{ Arc, Mutex };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::ModCalcStage;
+use crate::shared::handle_eq;
+
+// --Modules
+//------------------------------------------------------------------------------
+// enum - StatChangeCause
+
+/// Mutation of `CharStat` which changed its value.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum StatChangeCause {
+	Base,
+	BaseMult,
+	Upgrade,
+	ModifierAdded( ModCalcStage ),
+	ModifierExpired,
+	ModifierUpdated,
+	/// "more" stage, total bounds, rounding, soft cap or dynamic bounds
+	Config,
+	Refresh,
+}
+
+impl Display for StatChangeCause {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Base => "Base".fmt(f),
+			Self::BaseMult => "BaseMult".fmt(f),
+			Self::Upgrade => "Upgrade".fmt(f),
+			Self::ModifierAdded( stage ) => write!( f, "ModifierAdded( {stage:?} )" ),
+			Self::ModifierExpired => "ModifierExpired".fmt(f),
+			Self::ModifierUpdated => "ModifierUpdated".fmt(f),
+			Self::Config => "Config".fmt(f),
+			Self::Refresh => "Refresh".fmt(f),
+		}
+	}
+}
+
+// enum - StatChangeCause
+//------------------------------------------------------------------------------
+// struct - StatChange
+
+/// Value of `CharStat` before and after a mutation.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct StatChange {
+	pub old: f64,
+	pub new: f64,
+	pub cause: StatChangeCause,
+}
+
+impl StatChange {
+	/// returns `new - old`
+	#[inline]
+	pub fn delta ( &self ) -> f64 {
+		self.new - self.old
+	}
+}
+
+// struct - StatChange
+//------------------------------------------------------------------------------
+// trait - StatListener
+
+/// Observer notified after a mutation changed the value of `CharStat`, see `CharStat::set_listener`.
+pub trait StatListener: std::fmt::Debug + Send + Sync {
+	fn on_change ( &self, change: &StatChange );
+}
+
+/// Thread safe list implementing `StatListener`, clones share the list.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ ChangeLog, StatListener, StatChange, StatChangeCause };
+/// 
+/// let log = ChangeLog::new();
+/// let listener = log.clone();
+/// 
+/// listener.on_change( &StatChange { old: 1.0, new: 2.0, cause: StatChangeCause::Base } );
+/// assert_eq!( log.take()[ 0 ].delta(), 1.0 );
+/// assert!( log.take().is_empty() );
+/// ```
+#[derive( Debug, Clone, Default )]
+pub struct ChangeLog( Arc< Mutex< Vec< StatChange > > > );
+
+impl ChangeLog {
+	#[inline]
+	pub fn new () -> Self {
+		ChangeLog::default()
+	}
+	
+	/// returns recorded changes and clears the list
+	#[inline]
+	pub fn take ( &self ) -> Vec< StatChange > {
+		std::mem::take( &mut *self.0.lock().unwrap_or_else( std::sync::PoisonError::into_inner ) )
+	}
+}
+
+impl StatListener for ChangeLog {
+	#[inline]
+	fn on_change ( &self, change: &StatChange ) {
+		self.0.lock().unwrap_or_else( std::sync::PoisonError::into_inner ).push( *change );
+	}
+}
+
+/// Shared handle to a `StatListener`.
+/// Two handles are equal only when they point to the same listener.
+#[derive( Debug, Clone )]
+pub struct SharedListener( Arc< dyn StatListener > );
+
+impl SharedListener {
+	#[inline]
+	pub fn new ( listener: impl StatListener + 'static ) -> Self {
+		SharedListener( Arc::new( listener ) )
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> &dyn StatListener {
+		self.0.as_ref()
+	}
+}
+
+impl PartialEq for SharedListener {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		handle_eq( &self.0, None::< &() >, &other.0, None )
+	}
+}
+
+// trait - StatListener
+//------------------------------------------------------------------------------
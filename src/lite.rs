@@ -0,0 +1,442 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStat, BaseConf, UpgradeConf, ModConf, ModMultConf, Bounds, CharStatError, CsInvalidValue, CsLogicIssue, CsMissingComponent };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - CharStatLite
+
+/// `Copy` stat with base and optional upgrade stored inline, without heap allocations.
+/// 
+/// Rounding, base multiplier and modifiers are not supported, convert into `CharStat` when they are needed.
+/// Setters and getters mirror the ones of `CharStat`.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ CharStatLite, CharStat, ModConf, Modifier, ModCommon, ModCalcMode, ModCalcStage, Bounds, RoundingHelper };
+/// 
+/// let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+/// let mut lite = CharStatLite::new( 10.0, true, bounds, Some( ( 2.0, bounds ) ) ).unwrap();
+/// let copy = lite;
+/// 
+/// lite.inc_upgrade_value( 3.0 ).unwrap();
+/// assert_eq!( lite.value(), 15.0 );
+/// assert_eq!( copy.value(), 12.0 );
+/// 
+/// // first modifier
+/// let mod_of_base = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+/// let mut cs = lite.into_char_stat_with_mods( Some( mod_of_base ), None, None, None );
+/// cs.append_modifier( Modifier::new_persistent( ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap() ) ).unwrap();
+/// assert_eq!( cs.value(), 16.0 );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct CharStatLite {
+	time_stamp: u64,
+	base: f64,
+	is_base_mut: bool,
+	base_bounds: Bounds,
+	upgrade: Option< LiteUpgrade >,
+}
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+struct LiteUpgrade {
+	value: f64,
+	bounds: Bounds,
+}
+
+impl CharStatLite {
+	/// `upgrade` is a tuple of ( value, bounds )
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::Nan` when a value is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when a value is infinite <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when a value is not within its' bounds <br>
+	#[inline]
+	pub fn new ( base: f64, is_base_mut: bool, base_bounds: Bounds, upgrade: Option< ( f64, Bounds ) > ) -> Result< Self, CharStatError > {
+		BaseConf::check_inval( base, &base_bounds )?;
+		
+		if let Some( ( value, bounds ) ) = upgrade {
+			BaseConf::check_inval( value, &bounds )?;
+		}
+		
+		Ok( CharStatLite {
+			time_stamp: 0,
+			base,
+			is_base_mut,
+			base_bounds,
+			upgrade: upgrade.map( |( value, bounds )| LiteUpgrade { value, bounds } ),
+		})
+	}
+	
+	/// Returns the current ( total ) value. <br>
+	/// Infinite sum is clamped to `f64::MIN` / `f64::MAX`, see `has_overflowed`.
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.sum().clamp( f64::MIN, f64::MAX )
+	}
+	
+	#[inline]
+	pub fn has_overflowed ( &self ) -> bool {
+		!self.sum().is_finite()
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Infinite` when the sum overflows, see `has_overflowed` <br>
+	#[inline]
+	pub fn value_checked ( &self ) -> Result< f64, CharStatError > {
+		if self.has_overflowed() {
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
+		
+		Ok( self.sum() )
+	}
+	
+	/// # Errors
+	/// `CsLogicIssue::TimeTravel` when `new_val` < `self.time_stamp` <br>
+	#[inline]
+	pub fn set_ts ( &mut self, new_val: u64 ) -> Result<(), CharStatError > {
+		if new_val < self.time_stamp {
+			return Err( CsLogicIssue::TimeTravel.into() )
+		}
+		
+		self.time_stamp = new_val;
+		
+		Ok(())
+	}
+	
+	#[inline]
+	pub fn set_ts_unchecked ( &mut self, new_val: u64 ) {
+		self.time_stamp = new_val;
+	}
+	
+	#[inline]
+	pub fn ts ( &self ) -> u64 {
+		self.time_stamp
+	}
+	
+	/// same as `base_raw`, there are no modifiers
+	#[inline]
+	pub fn base ( &self ) -> f64 {
+		self.base
+	}
+	
+	#[inline]
+	pub fn base_raw ( &self ) -> f64 {
+		self.base
+	}
+	
+	/// same as `upgrade_raw`, there are no modifiers
+	/// 
+	/// # Errors
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn upgrade ( &self ) -> Result< f64, CharStatError > {
+		self.upgrade_raw()
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn upgrade_raw ( &self ) -> Result< f64, CharStatError > {
+		self.upgrade.map( |tmp| tmp.value ).ok_or_else( || CsMissingComponent::Upgrade.into() )
+	}
+	
+	/// converts into `CharStat` without modifiers, same as `CharStat::new_no_mod`
+	#[inline]
+	pub fn into_char_stat ( self ) -> CharStat {
+		self.into_char_stat_with_mods( None, None, None, None )
+	}
+	
+	/// converts into `CharStat` with modifier components, same as `CharStat::new`
+	#[inline]
+	pub fn into_char_stat_with_mods (
+		self,
+		mod_of_base: Option< ModConf >,
+		mod_of_upgrade: Option< ModConf >,
+		mod_of_base_plus_upgrade: Option< ModConf >,
+		mod_mult: Option< ModMultConf >,
+	) -> CharStat {
+		let base = BaseConf::new_unchecked( self.base, self.is_base_mut, self.base_bounds );
+		let upgrade = self.upgrade.map( |tmp| UpgradeConf::new_unchecked( tmp.value, tmp.bounds ) );
+		
+		let mut out = CharStat::new( base, upgrade, mod_of_base, mod_of_upgrade, mod_of_base_plus_upgrade, mod_mult );
+		out.set_ts_unchecked( self.time_stamp );
+		
+		out
+	}
+}
+
+impl From< CharStatLite > for CharStat {
+	#[inline]
+	fn from( lite: CharStatLite ) -> Self {
+		lite.into_char_stat()
+	}
+}
+
+// base
+/// Same as `CharStat`
+impl CharStatLite {
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `self.base_bounds` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.is_base_mut` is false <br>
+	#[inline]
+	pub fn set_base_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		if !self.is_base_mut {
+			return Err( CsLogicIssue::FieldIsConst.into() )
+		}
+		
+		BaseConf::check_inval( value, &self.base_bounds )?;
+		self.base = value;
+		
+		Ok(())
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.is_base_mut` is false <br>
+	#[inline]
+	pub fn set_base_value_clamping ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		if !self.is_base_mut {
+			return Err( CsLogicIssue::FieldIsConst.into() )
+		}
+		
+		BaseConf::check_nan( value )?;
+		self.base = self.base_bounds.clamp( value );
+		
+		Ok(())
+	}
+	
+	#[inline]
+	pub fn set_base_value_const ( &mut self ) {
+		self.is_base_mut = false;
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
+	#[inline]
+	pub fn set_base_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.base_bounds.set_min( new_val )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
+	#[inline]
+	pub fn set_base_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.base_bounds.set_max( new_val )
+	}
+	
+	#[inline]
+	pub fn set_base_bounds_min_const ( &mut self ) {
+		self.base_bounds.set_min_const();
+	}
+	
+	#[inline]
+	pub fn set_base_bounds_max_const ( &mut self ) {
+		self.base_bounds.set_max_const();
+	}
+	
+	#[inline]
+	pub fn base_bounds_min ( &self ) -> f64 {
+		self.base_bounds.min()
+	}
+	
+	#[inline]
+	pub fn base_bounds_max ( &self ) -> f64 {
+		self.base_bounds.max()
+	}
+}// base
+
+// upgrade
+/// Same as `CharStat`
+impl CharStatLite {
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within upgrade bounds <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		let upgrade = self.upgrade_mut()?;
+		
+		BaseConf::check_inval( value, &upgrade.bounds )?;
+		upgrade.value = value;
+		
+		Ok(())
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_value_clamping ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		let upgrade = self.upgrade_mut()?;
+		
+		BaseConf::check_nan( value )?;
+		upgrade.value = upgrade.bounds.clamp( value );
+		
+		Ok(())
+	}
+	
+	/// Attempts to increment upgrade by supplied `value`
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::AboveMaximum` when new value is not within upgrade bounds <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn inc_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		let current = self.upgrade_raw()?;
+		
+		self.set_upgrade_value( current + value )
+	}
+	
+	/// Attempts to decrement upgrade by supplied `value`
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::BelowMinimum` when new value is not within upgrade bounds <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn dec_upgrade_value ( &mut self, value: f64 ) -> Result<(), CharStatError > {
+		let current = self.upgrade_raw()?;
+		
+		self.set_upgrade_value( current - value )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.upgrade_mut()?.bounds.set_min( new_val )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.upgrade_mut()?.bounds.set_max( new_val )
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_bounds_min_const ( &mut self ) -> Result<(), CharStatError > {
+		self.upgrade_mut()?.bounds.set_min_const();
+		
+		Ok(())
+	}
+	
+	/// # Errors
+	/// `CsMissingComponent::Upgrade` when upgrade is missing <br>
+	#[inline]
+	pub fn set_upgrade_bounds_max_const ( &mut self ) -> Result<(), CharStatError > {
+		self.upgrade_mut()?.bounds.set_max_const();
+		
+		Ok(())
+	}
+	
+	#[inline]
+	pub fn upgrade_bounds_min ( &self ) -> Option< f64 > {
+		self.upgrade.map( |tmp| tmp.bounds.min() )
+	}
+	
+	#[inline]
+	pub fn upgrade_bounds_max ( &self ) -> Option< f64 > {
+		self.upgrade.map( |tmp| tmp.bounds.max() )
+	}
+}// upgrade
+
+// priv
+impl CharStatLite {
+	#[inline]
+	#[doc( hidden )]
+	fn sum ( &self ) -> f64 {
+		self.base + self.upgrade.map_or( 0.0, |tmp| tmp.value )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn upgrade_mut ( &mut self ) -> Result< &mut LiteUpgrade, CharStatError > {
+		self.upgrade.as_mut().ok_or_else( || CsMissingComponent::Upgrade.into() )
+	}
+}// priv
+
+// struct - CharStatLite
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use crate::{ CharStatLite, CharStat, BaseConf, UpgradeConf, Bounds, RoundingHelper, CharStatError, CsInvalidValue, CsLogicIssue, CsMissingComponent };
+	
+	#[test]
+	fn basic_functional() {
+		let bounds = Bounds::new_const( 0.0, 20.0 ).unwrap();
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "value".to_string() ).into();
+		assert_eq!( CharStatLite::new( 21.0, true, bounds, None ), Err( expected.clone() ) );
+		assert_eq!( CharStatLite::new( 1.0, true, bounds, Some( ( 21.0, bounds ) ) ), Err( expected.clone() ) );
+		
+		let mut lite = CharStatLite::new( 10.0, true, bounds, None ).unwrap();
+		assert_eq!( lite.upgrade(), Err( CsMissingComponent::Upgrade.into() ) );
+		assert_eq!( lite.inc_upgrade_value( 1.0 ), Err( CsMissingComponent::Upgrade.into() ) );
+		
+		assert_eq!( lite.set_base_value( 25.0 ), Err( expected ) );
+		lite.set_base_value_clamping( 25.0 ).unwrap();
+		assert_eq!( lite.value(), 20.0 );
+		
+		lite.set_base_value_const();
+		assert_eq!( lite.set_base_value( 5.0 ), Err( CsLogicIssue::FieldIsConst.into() ) );
+		
+		lite.set_ts( 10 ).unwrap();
+		assert_eq!( lite.set_ts( 5 ), Err( CsLogicIssue::TimeTravel.into() ) );
+	}
+	
+	#[test]
+	fn matches_char_stat() {
+		let bounds = Bounds::new_mut( 0.0, 20.0 ).unwrap();
+		let mut lite = CharStatLite::new( 10.0, true, bounds, Some( ( 2.0, bounds ) ) ).unwrap();
+		let base = BaseConf::new( 10.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		let upgrade = UpgradeConf::new( 2.0, bounds, RoundingHelper::new_none() ).unwrap();
+		let mut cs = CharStat::new_no_mod( base, Some( upgrade ) );
+		
+		assert_eq!( lite.set_upgrade_bounds_max( 5.0 ), cs.set_upgrade_bounds_max( 5.0 ) );
+		assert_eq!( lite.inc_upgrade_value( 4.0 ), cs.inc_upgrade_value( 4.0 ).map( |_| () ) );
+		assert_eq!( lite.inc_upgrade_value( 3.0 ), cs.inc_upgrade_value( 3.0 ).map( |_| () ) );
+		assert_eq!( lite.set_upgrade_value_clamping( 9.0 ), cs.set_upgrade_value_clamping( 9.0 ).map( |_| () ) );
+		assert_eq!( lite.set_base_bounds_min( 15.0 ), cs.set_base_bounds_min( 15.0 ) );
+		assert_eq!( lite.set_base_value( 12.0 ), cs.set_base_value( 12.0 ).map( |_| () ) );
+		assert_eq!( lite.set_base_value( 16.0 ), cs.set_base_value( 16.0 ).map( |_| () ) );
+		lite.set_ts( 7 ).unwrap();
+		cs.set_ts( 7 ).unwrap();
+		
+		assert_eq!( lite.value(), cs.value() );
+		assert_eq!( lite.upgrade(), cs.upgrade() );
+		assert_eq!( lite.base_bounds_min(), cs.base_bounds_min() );
+		cs.flush();
+		assert_eq!( CharStat::from( lite ), cs );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,281 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize, Serializer, Deserializer };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::ModCalcStage;
+
+use crate::{ CharStatError, CsInvalidValue };
+use crate::shared::{ register_any, registered_any, handle_eq };
+
+// --Modules
+//------------------------------------------------------------------------------
+// trait - ModifierFn
+
+/// Custom formula of a modifier with `ModCalcMode::Custom`.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ ModifierFn, ModFnContext, Modifier, ModCommon, ModCalcMode, ModCalcStage };
+/// 
+/// // bonus grows logarithmically with the stage input
+/// #[derive( Debug )]
+/// struct LogBonus;
+/// 
+/// impl ModifierFn for LogBonus {
+///     fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64 {
+///         ctx.value() * stage_input.max( 1.0 ).ln()
+///     }
+/// 
+///     fn type_name ( &self ) -> &'static str {
+///         "log_bonus"
+///     }
+/// }
+/// 
+/// let common = ModCommon::new( 2.0, ModCalcMode::Custom, ModCalcStage::Base ).unwrap();
+/// let modifier = Modifier::new_persistent( common ).with_fn( LogBonus ).unwrap();
+/// 
+/// assert_eq!( modifier.contribution( 1.0 ), 0.0 );
+/// ```
+pub trait ModifierFn: Debug + Send + Sync {
+	/// returns the value added to the stage, same as `Mul` would return `value * stage_input`
+	fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64;
+	
+	/// name used by the registry, must be unique
+	fn type_name ( &self ) -> &'static str;
+	
+	/// Parameters passed to the constructor registered with `register_modifier_fn`, in any text format, e.g. JSON. <br>
+	/// Functions with the same `type_name` and `params` are equal, so `params` must describe all state of the function.
+	#[inline]
+	fn params ( &self ) -> String {
+		String::new()
+	}
+}
+
+// trait - ModifierFn
+//------------------------------------------------------------------------------
+// struct - ModFnContext
+
+/// Data of the modifier passed to `ModifierFn::contribution`.
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct ModFnContext {
+	value: f64,
+	stage: ModCalcStage,
+}
+
+impl ModFnContext {
+	#[inline]
+	pub fn new ( value: f64, stage: ModCalcStage ) -> Self {
+		ModFnContext {
+			value,
+			stage,
+		}
+	}
+	
+	/// value of the modifier
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.value
+	}
+	
+	#[inline]
+	pub fn stage ( &self ) -> ModCalcStage {
+		self.stage
+	}
+}
+
+// struct - ModFnContext
+//------------------------------------------------------------------------------
+// struct - CustomModFn
+
+/// Shared handle to a `ModifierFn`, cloning a `Modifier` does not clone the function.
+/// Handles are compared by pointer or by `type_name` and `params`.
+#[derive( Debug, Clone )]
+pub struct CustomModFn( Arc< dyn ModifierFn > );
+
+impl CustomModFn {
+	#[inline]
+	pub fn new ( func: impl ModifierFn + 'static ) -> Self {
+		CustomModFn( Arc::new( func ) )
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> &dyn ModifierFn {
+		self.0.as_ref()
+	}
+}
+
+impl PartialEq for CustomModFn {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		handle_eq( &self.0, Some( &( self.0.type_name(), self.0.params() ) ), &other.0, Some( &( other.0.type_name(), other.0.params() ) ) )
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Serialize, Deserialize )]
+struct CustomModFnRepr {
+	#[serde( rename = "type" )]
+	type_name: String,
+	#[serde( default )]
+	params: String,
+}
+
+#[cfg( feature = "serde" )]
+impl Serialize for CustomModFn {
+	#[inline]
+	fn serialize< S: Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error > {
+		let repr = CustomModFnRepr {
+			type_name: self.0.type_name().to_string(),
+			params: self.0.params(),
+		};
+		
+		repr.serialize( serializer )
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl< 'de > Deserialize< 'de > for CustomModFn {
+	#[inline]
+	fn deserialize< D: Deserializer< 'de > >( deserializer: D ) -> Result< Self, D::Error > {
+		let repr = CustomModFnRepr::deserialize( deserializer )?;
+		
+		make_modifier_fn( &repr.type_name, &repr.params ).map_err( serde::de::Error::custom )
+	}
+}
+
+// struct - CustomModFn
+//------------------------------------------------------------------------------
+// registry
+
+/// Constructor of a registered `ModifierFn`, receives output of `ModifierFn::params`.
+pub type ModifierFnCtor = fn( &str ) -> Result< CustomModFn, CharStatError >;
+
+/// Registers constructor used by `make_modifier_fn` and to deserialize modifiers with custom function of `type_name`.
+/// Registering the same name again replaces the constructor.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ ModifierFn, ModFnContext, CustomModFn, register_modifier_fn };
+/// 
+/// #[derive( Debug )]
+/// struct Scaled( f64 );
+/// 
+/// impl ModifierFn for Scaled {
+///     fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64 {
+///         ctx.value() * stage_input * self.0
+///     }
+/// 
+///     fn type_name ( &self ) -> &'static str {
+///         "scaled"
+///     }
+/// 
+///     fn params ( &self ) -> String {
+///         self.0.to_string()
+///     }
+/// }
+/// 
+/// register_modifier_fn( "scaled", |params| Ok( CustomModFn::new( Scaled( params.parse().unwrap_or( 1.0 ) ) ) ) );
+/// ```
+#[inline]
+pub fn register_modifier_fn ( type_name: &str, ctor: ModifierFnCtor ) {
+	register_any( type_name, ctor );
+}
+
+/// Creates a registered `ModifierFn`.
+/// 
+/// # Errors
+/// `CsInvalidValue::NotAllowed( "type_name" )` when `type_name` was not registered <br>
+/// any error returned by the constructor <br>
+#[inline]
+pub fn make_modifier_fn ( type_name: &str, params: &str ) -> Result< CustomModFn, CharStatError > {
+	match registered_any::< ModifierFnCtor >( type_name ) {
+		Some( ctor ) => ctor( params ),
+		None => Err( CsInvalidValue::NotAllowed( "type_name".to_string() ).into() ),
+	}
+}
+
+// registry
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	use crate::{ Modifier, ModCommon, ModCalcMode, ModConf, Bounds, RoundingHelper };
+	
+	#[derive( Debug )]
+	struct MissingHp {
+		max_hp: f64,
+	}
+	
+	impl ModifierFn for MissingHp {
+		fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64 {
+			ctx.value() * ( self.max_hp - stage_input ).max( 0.0 )
+		}
+		
+		fn type_name ( &self ) -> &'static str {
+			"missing_hp"
+		}
+		
+		fn params ( &self ) -> String {
+			self.max_hp.to_string()
+		}
+	}
+	
+	#[test]
+	fn basic_functional() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let mut mod_conf = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), false, false );
+		
+		let common = ModCommon::new( 0.5, ModCalcMode::Custom, ModCalcStage::Base ).unwrap();
+		let modifier = Modifier::new_persistent( common ).with_fn( MissingHp { max_hp: 100.0 } ).unwrap();
+		
+		mod_conf.append_mod( 60.0, modifier.clone() ).unwrap();
+		assert_eq!( mod_conf.value(), 20.0 );
+		
+		// custom mode requires a function, other modes do not accept one
+		let expected: CharStatError = CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into();
+		assert_eq!( mod_conf.append_mod( 60.0, Modifier::new_persistent( common ) ), Err( expected.clone() ) );
+		assert_eq!( mod_conf.value(), 20.0 );
+		
+		let add = ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		assert_eq!( Modifier::new_persistent( add ).with_fn( MissingHp { max_hp: 100.0 } ), Err( expected ) );
+		
+		// equal by pointer or by type name and params
+		assert_eq!( modifier.clone(), modifier );
+		assert_eq!( Modifier::new_persistent( common ).with_fn( MissingHp { max_hp: 100.0 } ).unwrap(), modifier );
+		assert_ne!( Modifier::new_persistent( common ).with_fn( MissingHp { max_hp: 90.0 } ).unwrap(), modifier );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let common = ModCommon::new( 0.5, ModCalcMode::Custom, ModCalcStage::Base ).unwrap();
+		let modifier = Modifier::new_persistent( common ).with_fn( MissingHp { max_hp: 80.0 } ).unwrap();
+		let serialized = serde_json::to_string( &modifier ).unwrap();
+		
+		let unknown: Result< Modifier, _ > = serde_json::from_str( &serialized.replace( "missing_hp", "unknown" ) );
+		assert!( unknown.is_err() );
+		
+		register_modifier_fn( "missing_hp", |params| {
+			let max_hp = params.parse().map_err( |_| CsInvalidValue::NotAllowed( "params".to_string() ) )?;
+			
+			Ok( CustomModFn::new( MissingHp { max_hp } ) )
+		} );
+		
+		let deserialized: Modifier = serde_json::from_str( &serialized ).unwrap();
+		assert_eq!( deserialized, modifier );
+		assert_eq!( deserialized.contribution( 60.0 ), 10.0 );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
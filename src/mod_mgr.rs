@@ -1,3 +1,5 @@
+use std::fmt::{ Display, Formatter };
+
 #[cfg( feature = "serde" )]
 use serde::{ Serialize, Deserialize };
 
@@ -5,28 +7,54 @@ use serde::{ Serialize, Deserialize };
 //------------------------------------------------------------------------------
 // --Modules
 
-use crate::{ Bounds, RoundingHelper, ModCalcMode, ModCalcStage, Modifier, CharStatError, CsLogicIssue, CsInvalidValue };
+use crate::expiry::ExpiryIndex;
+use crate::{ Shared, Bounds, RoundingHelper, ModCalcStage, Modifier, ModifierId, SoftCap, DynBounds, StageValues, CharStatError, CsLogicIssue, CsInvalidValue };
 
 // --Modules
 //------------------------------------------------------------------------------
+// struct - ModConfCalc
+
+/// Values calculated by `ModConf::calc`, stored by `ModConf::store`.
+#[derive( Debug, Clone, Copy, PartialEq, Default )]
+pub( crate ) struct ModConfCalc {
+	pub( crate ) input: f64,
+	pub( crate ) value: f64,
+	pub( crate ) soft_cap_loss: f64,
+}
+
+// struct - ModConfCalc
+//------------------------------------------------------------------------------
 // struct - ModConf
 
 /// Component handling every modifier stage other than modifier multiplier.
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, PartialEq,  )]
+#[derive( Debug, Clone )]
 pub struct ModConf {
 	value: f64,
 	stage: ModCalcStage,
-	bounds: Bounds,
-	rounding_fn: RoundingHelper,
+	bounds: Shared< Bounds >,
+	rounding_fn: Shared< RoundingHelper >,
 	mod_vec: Vec< Modifier >,
 	is_min_percent: bool,
 	is_max_percent: bool,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	soft_cap: Option< SoftCap >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	soft_cap_loss: f64,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	dyn_bounds: Option< DynBounds >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	input: f64,
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	expiry: ExpiryIndex,
 }
 
 impl ModConf {
 	#[inline]
-	pub fn new ( stage: ModCalcStage, bounds: Bounds, rounding_fn: RoundingHelper, is_min_percent: bool, is_max_percent: bool, ) -> Self {
+	pub fn new ( stage: ModCalcStage, bounds: impl Into< Shared< Bounds > >, rounding_fn: impl Into< Shared< RoundingHelper > >, is_min_percent: bool, is_max_percent: bool, ) -> Self {
+		let bounds: Shared< Bounds > = bounds.into();
+		let rounding_fn: Shared< RoundingHelper > = rounding_fn.into();
+		
 		ModConf {
 			value: 0.0,
 			stage,
@@ -35,12 +63,18 @@ impl ModConf {
 			mod_vec: Vec::new(),
 			is_min_percent,
 			is_max_percent,
+			soft_cap: None,
+			soft_cap_loss: 0.0,
+			dyn_bounds: None,
+			input: 0.0,
+			expiry: ExpiryIndex::new(),
 		}
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
 	/// `CsLogicIssue::InvalidModifierMode` when `modifier.calc_stage` is different from `self.stage` <br>
+	/// `CsInvalidValue::NotAllowed( "custom_fn" )` when a `Custom` mode modifier has no function <br>
 	#[inline]
 	pub fn append_mod ( &mut self, value: f64, modifier: Modifier ) -> Result<(), CharStatError > {
 		let stage = modifier.calc_stage();
@@ -53,6 +87,9 @@ impl ModConf {
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
 		
+		modifier.check_fn()?;
+		
+		self.expiry.push( &modifier );
 		self.mod_vec.push( modifier );
 		self.update( value );
 		
@@ -65,61 +102,165 @@ impl ModConf {
 	}
 	
 	#[inline]
-	pub fn remove_expired ( &mut self, ts: u64 ) {
-		for i in ( 0..self.mod_vec.len() ).rev() {
-			let tmp = self.mod_vec.get( i );
-			
-			if let Some( element ) = tmp {
-				if element.has_expired( ts ) {
-					self.mod_vec.remove( i );
-				}
-			}
-		}// for
+	pub fn stage ( &self ) -> ModCalcStage {
+		self.stage
+	}
+	
+	#[inline]
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	/// stored modifiers in order of appending
+	#[inline]
+	pub fn modifiers ( &self ) -> &[ Modifier ] {
+		&self.mod_vec
+	}
+	
+	/// returns the number of stored modifiers
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.mod_vec.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.mod_vec.is_empty()
+	}
+	
+	/// Removes expired modifiers, returns the removed ones. <br>
+	/// Value is not recalculated, see `update`.
+	#[inline]
+	pub fn remove_expired ( &mut self, ts: u64 ) -> Vec< Modifier > {
+		self.expiry.remove_expired( &mut self.mod_vec, ts )
+	}
+	
+	/// returns the lowest expiration timestamp of stored modifiers
+	#[inline]
+	pub fn next_expiration ( &self ) -> Option< u64 > {
+		self.expiry.next( &self.mod_vec )
+	}
+	
+	#[inline]
+	pub fn set_rounding ( &mut self, new_val: impl Into< Shared< RoundingHelper > > ) {
+		self.rounding_fn = new_val.into();
+	}
+	
+	/// Soft cap applied to the sum of modifiers, before rounding and bounds. <br>
+	/// Value is recalculated with the last input.
+	#[inline]
+	pub fn set_soft_cap ( &mut self, new_val: Option< SoftCap > ) {
+		self.soft_cap = new_val;
+		self.update( self.input );
+	}
+	
+	#[inline]
+	pub fn soft_cap ( &self ) -> Option< &SoftCap > {
+		self.soft_cap.as_ref()
+	}
+	
+	/// returns the amount removed by soft cap during the last update
+	#[inline]
+	pub fn soft_cap_loss ( &self ) -> f64 {
+		self.soft_cap_loss
 	}
 	
+	/// Dynamic bounds resolved by `CharStat` during recalculation, applied after `bounds`.
 	#[inline]
-	pub fn set_rounding ( &mut self, new_val: RoundingHelper ) {
-		self.rounding_fn = new_val;
+	pub fn set_dyn_bounds ( &mut self, new_val: Option< DynBounds > ) {
+		self.dyn_bounds = new_val;
+	}
+	
+	#[inline]
+	pub fn dyn_bounds ( &self ) -> Option< &DynBounds > {
+		self.dyn_bounds.as_ref()
 	}
 }
 
 // pub-crate
 impl ModConf {
+	/// appends without recalculating, `update` must be called before reading the value
 	#[inline]
 	#[doc( hidden )]
-	pub( crate ) fn append_mod_unchecked ( &mut self, value: f64, modifier: Modifier ) {
+	pub( crate ) fn push_mod ( &mut self, modifier: Modifier ) {
+		self.expiry.push( &modifier );
 		self.mod_vec.push( modifier );
-		self.update( value );
+	}
+	
+	/// replaces or removes ( `None` ) the modifier with `id` without recalculating
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn swap_mod ( &mut self, id: ModifierId, new_val: Option< Modifier > ) -> bool {
+		self.expiry.swap( &mut self.mod_vec, id, new_val )
 	}
 	
 	#[inline]
 	#[doc( hidden )]
 	pub( crate ) fn update ( &mut self, value: f64 ) {
+		let calc = self.calc( value );
+		self.store( calc );
+	}
+	
+	/// calculates the value for `input` without changing `self`, dynamic bounds are applied by `dyn_bounded`
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn calc ( &self, input: f64 ) -> ModConfCalc {
 		let mut tmp = 0.0;
 		
 		for el in &self.mod_vec {
-			match el.calc_mode() {
-				ModCalcMode::Add => tmp += el.value(),
-				ModCalcMode::Sub => tmp -= el.value(),
-				ModCalcMode::Mul => tmp += el.value() * value,
-				ModCalcMode::Div => tmp += value / el.value(),
-			}
+			tmp += el.contribution( input );
 		}// for
 		
-		let mut eff_min = self.bounds.min();
-		let mut eff_max = self.bounds.max();
+		let mut soft_cap_loss = 0.0;
 		
-		if self.is_min_percent {
-			eff_min *= value;
+		if let Some( soft_cap ) = &self.soft_cap {
+			let capped = soft_cap.apply( tmp );
+			soft_cap_loss = tmp - capped;
+			tmp = capped;
 		}
 		
-		if self.is_max_percent {
-			eff_max *= value;
-		}
+		let ( eff_min, eff_max ) = self.bounds.scaled( input, self.is_min_percent, self.is_max_percent );
 		
 		tmp = self.rounding_fn.do_rounding( tmp );
 		
-		self.value = tmp.clamp( eff_min, eff_max );
+		ModConfCalc { input, value: tmp.clamp( eff_min, eff_max ), soft_cap_loss }
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn store ( &mut self, calc: ModConfCalc ) {
+		self.input = calc.input;
+		self.value = calc.value;
+		self.soft_cap_loss = calc.soft_cap_loss;
+	}
+	
+	/// returns the values stored by the last `store`
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn stored ( &self ) -> ModConfCalc {
+		ModConfCalc { input: self.input, value: self.value, soft_cap_loss: self.soft_cap_loss }
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn dyn_bounded ( &self, value: f64, ctx: &StageValues ) -> f64 {
+		self.dyn_bounds.as_ref().map_or( value, |tmp| tmp.apply( value, ctx ) )
+	}
+	
+	/// compares everything except the calculated values
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn eq_config ( &self, other: &Self ) -> bool {
+		// `expiry` is derived from `mod_vec`, it differs between a built and a deserialized index
+		let ModConf { value: _, stage, bounds, rounding_fn, mod_vec, is_min_percent, is_max_percent, soft_cap, soft_cap_loss: _, dyn_bounds, input: _, expiry: _ } = self;
+		
+		*stage == other.stage && *bounds == other.bounds && *rounding_fn == other.rounding_fn && *mod_vec == other.mod_vec &&
+		*is_min_percent == other.is_min_percent && *is_max_percent == other.is_max_percent && *soft_cap == other.soft_cap && *dyn_bounds == other.dyn_bounds
 	}
 }// pub-crate
 
@@ -131,7 +272,7 @@ impl ModConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_min( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_min( new_val ) )?;
 		
 		Ok(())
 	}
@@ -142,19 +283,19 @@ impl ModConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_max( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_max( new_val ) )?;
 		
 		Ok(())
 	}
 	
 	#[inline]
 	pub fn set_bounds_min_const ( &mut self ) {
-		self.bounds.set_min_const();
+		self.bounds.update( Bounds::set_min_const );
 	}
 	
 	#[inline]
 	pub fn set_bounds_max_const ( &mut self ) {
-		self.bounds.set_max_const();
+		self.bounds.update( Bounds::set_max_const );
 	}
 	
 	#[inline]
@@ -166,14 +307,50 @@ impl ModConf {
 	pub fn bounds_max ( &self ) -> f64 {
 		self.bounds.max()
 	}
+	
+	/// Percent bounds are fractions of the stage input, e.g. min 0.0 and max 0.5 limit the modifiers to +50% of the input. <br>
+	/// Value is recalculated with the last input.
+	#[inline]
+	pub fn set_percent ( &mut self, is_min_percent: bool, is_max_percent: bool ) {
+		self.is_min_percent = is_min_percent;
+		self.is_max_percent = is_max_percent;
+		self.update( self.input );
+	}
+	
+	#[inline]
+	pub fn is_min_percent ( &self ) -> bool {
+		self.is_min_percent
+	}
+	
+	#[inline]
+	pub fn is_max_percent ( &self ) -> bool {
+		self.is_max_percent
+	}
 }// bounds
 
+impl PartialEq for ModConf {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		self.eq_config( other ) && self.stored() == other.stored()
+	}
+}
+
+impl Display for ModConf {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		write!( f, "ModConf = ({}, value: {}, modifiers: {}, {}, percent min: {}, percent max: {}, {})",
+			self.stage, self.value, self.mod_vec.len(), *self.bounds, self.is_min_percent, self.is_max_percent, *self.rounding_fn )
+	}
+}
+
 // struct - ModConf
 //------------------------------------------------------------------------------
 // --Tests
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use crate::{ ModConf, Modifier, ModCommon, ModCalcStage, ModCalcMode, Bounds, RoundingHelper, CsInvalidValue,  };
 	
 	#[test]
@@ -219,8 +396,8 @@ mod tests {
 		let mod_1 = Modifier::new_expiring( common, 69 );
 		let mod_2 = Modifier::new_expiring( common_2, 100 );
 		
-		mgr.append_mod_unchecked( 0.0, mod_1 );
-		mgr.append_mod_unchecked( 0.0, mod_2 );
+		mgr.push_mod( mod_1 );
+		mgr.push_mod( mod_2 );
 		
 		mgr.remove_expired( 50 );
 		mgr.update( 0.0 );
@@ -235,6 +412,54 @@ mod tests {
 		assert_eq!( mgr.value(), 0.0 );
 		
 	}
+	
+	#[test]
+	fn soft_cap() {
+		let stage = ModCalcStage::Base;
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let mut mgr = ModConf::new( stage, bounds, RoundingHelper::new_none(), false, false );
+		mgr.set_soft_cap( Some( crate::SoftCap::linear( 10.0, 0.5 ).unwrap() ) );
+		
+		let common = ModCommon::new( 30.0, ModCalcMode::Add, stage ).unwrap();
+		mgr.append_mod( 0.0, Modifier::new_persistent( common ) ).unwrap();
+		
+		assert_eq!( mgr.value(), 20.0 );
+		assert_eq!( mgr.soft_cap_loss(), 10.0 );
+		
+		mgr.set_soft_cap( None );
+		assert_eq!( mgr.value(), 30.0 );
+		assert_eq!( mgr.soft_cap_loss(), 0.0 );
+	}
+	
+	#[test]
+	fn set_percent() {
+		let stage = ModCalcStage::Base;
+		let bounds = Bounds::new_const( 0.0, 0.5 ).unwrap();
+		let mut mgr = ModConf::new( stage, bounds, RoundingHelper::new_none(), false, false );
+		
+		let common = ModCommon::new( 30.0, ModCalcMode::Add, stage ).unwrap();
+		mgr.append_mod( 10.0, Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( mgr.value(), 0.5 );
+		
+		// recalculated with the last input
+		mgr.set_percent( false, true );
+		assert_eq!( mgr.value(), 5.0 );
+	}
+	
+	#[test]
+	fn negative_input() {
+		// at most 50% of the input, with a negative input percent bounds are swapped
+		let bounds = Bounds::new_const( 0.0, 0.5 ).unwrap();
+		let mut mod_mgr = ModConf::new( ModCalcStage::Base, bounds, RoundingHelper::new_none(), true, true );
+		
+		let common = ModCommon::new( 100.0, ModCalcMode::Add, ModCalcStage::Base ).unwrap();
+		mod_mgr.append_mod( -10.0, Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( mod_mgr.value(), 0.0 );
+		
+		let common = ModCommon::new( 200.0, ModCalcMode::Sub, ModCalcStage::Base ).unwrap();
+		mod_mgr.append_mod( -10.0, Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( mod_mgr.value(), -5.0 );
+	}
 }
 
 // --Tests
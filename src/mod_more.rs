@@ -0,0 +1,286 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::expiry::ExpiryIndex;
+use crate::{ Bounds, RoundingHelper, ModCalcMode, ModCalcStage, Modifier, ModifierId, CharStatError, CsLogicIssue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - ModMoreConf
+
+/// Component handling "more" modifiers, applied to the total after every additive stage.
+/// 
+/// Unlike `ModConf` the modifiers compound, each one multiplies the running total:
+/// - Add => total * ( 1 + value )
+/// - Sub => total * ( 1 - value )
+/// - Mul => total * value
+/// - Div => total / value
+/// 
+/// Bounds are applied to the product of all factors, rounding to the output of the stage.
+/// Modifier multiplier ( `ModMultConf` ) does not affect this stage.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone )]
+pub struct ModMoreConf {
+	value: f64,
+	bounds: Bounds,
+	rounding_fn: RoundingHelper,
+	mod_vec: Vec< Modifier >,
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	expiry: ExpiryIndex,
+}
+
+impl ModMoreConf {
+	#[inline]
+	pub fn new ( bounds: Bounds, rounding_fn: RoundingHelper ) -> Self {
+		let mut out = ModMoreConf {
+			value: 1.0,
+			bounds,
+			rounding_fn,
+			mod_vec: Vec::new(),
+			expiry: ExpiryIndex::new(),
+		};
+		
+		out.update();
+		
+		out
+	}
+	
+	/// # Errors
+	/// `CsLogicIssue::InvalidModifierStage` when `modifier.calc_stage` is not `More` <br>
+	/// `CsLogicIssue::InvalidModifierMode` when mode is `Custom` <br>
+	#[inline]
+	pub fn append_mod ( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
+		let stage = modifier.calc_stage();
+		
+		if stage != ModCalcStage::More {
+			return Err( CsLogicIssue::InvalidModifierStage( stage, ModCalcStage::More ).into() )
+		}
+		
+		if modifier.calc_mode() == ModCalcMode::Custom {
+			return Err( CsLogicIssue::InvalidModifierMode( ModCalcMode::Custom, vec![ ModCalcMode::Add, ModCalcMode::Sub, ModCalcMode::Mul, ModCalcMode::Div ] ).into() )
+		}
+		
+		self.append_mod_unchecked( modifier );
+		
+		Ok(())
+	}
+	
+	/// returns the product of all factors, 1.0 when there are no modifiers
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.value
+	}
+	
+	/// stored modifiers in order of appending
+	#[inline]
+	pub fn modifiers ( &self ) -> &[ Modifier ] {
+		&self.mod_vec
+	}
+	
+	/// returns the number of stored modifiers
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.mod_vec.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.mod_vec.is_empty()
+	}
+	
+	/// multiplies `total` by `self.value` and applies rounding
+	#[inline]
+	pub fn apply ( &self, total: f64 ) -> f64 {
+		self.rounding_fn.do_rounding( total * self.value )
+	}
+	
+	/// removes expired modifiers, returns the removed ones
+	#[inline]
+	pub fn remove_expired ( &mut self, ts: u64 ) -> Vec< Modifier > {
+		let out = self.expiry.remove_expired( &mut self.mod_vec, ts );
+		
+		if !out.is_empty() {
+			self.update();
+		}
+		
+		out
+	}
+	
+	/// returns the lowest expiration timestamp of stored modifiers
+	#[inline]
+	pub fn next_expiration ( &self ) -> Option< u64 > {
+		self.expiry.next( &self.mod_vec )
+	}
+	
+	#[inline]
+	pub fn set_rounding ( &mut self, new_val: RoundingHelper ) {
+		self.rounding_fn = new_val;
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	/// bounds of the product of all factors
+	#[inline]
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+}
+
+// pub-crate
+impl ModMoreConf {
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn append_mod_unchecked ( &mut self, modifier: Modifier ) {
+		self.expiry.push( &modifier );
+		self.mod_vec.push( modifier );
+		self.update();
+	}
+	
+	/// replaces or removes ( `None` ) the modifier with `id`
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn swap_mod ( &mut self, id: ModifierId, new_val: Option< Modifier > ) -> bool {
+		let out = self.expiry.swap( &mut self.mod_vec, id, new_val );
+		
+		if out {
+			self.update();
+		}
+		
+		out
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn update ( &mut self ) {
+		let mut tmp = 1.0;
+		
+		for el in &self.mod_vec {
+			match el.calc_mode() {
+				ModCalcMode::Add => tmp *= 1.0 + el.value(),
+				ModCalcMode::Sub => tmp *= 1.0 - el.value(),
+				ModCalcMode::Mul => tmp *= el.value(),
+				ModCalcMode::Div => tmp /= el.value(),
+				ModCalcMode::Custom => {},
+			}
+		}// for
+		
+		self.value = tmp.clamp( self.bounds.min(), self.bounds.max() );
+	}
+}// pub-crate
+
+// bounds
+impl ModMoreConf {
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `new_val` > `self.v_max` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
+	#[inline]
+	pub fn set_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.bounds.set_min( new_val )?;
+		self.update();
+		
+		Ok(())
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `self.v_min` > `new_val` <br>
+	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
+	#[inline]
+	pub fn set_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
+		self.bounds.set_max( new_val )?;
+		self.update();
+		
+		Ok(())
+	}
+	
+	#[inline]
+	pub fn set_bounds_min_const ( &mut self ) {
+		self.bounds.set_min_const();
+	}
+	
+	#[inline]
+	pub fn set_bounds_max_const ( &mut self ) {
+		self.bounds.set_max_const();
+	}
+	
+	#[inline]
+	pub fn bounds_min ( &self ) -> f64 {
+		self.bounds.min()
+	}
+	
+	#[inline]
+	pub fn bounds_max ( &self ) -> f64 {
+		self.bounds.max()
+	}
+}// bounds
+
+impl PartialEq for ModMoreConf {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		// `expiry` is derived from `mod_vec`
+		let ModMoreConf { value, bounds, rounding_fn, mod_vec, expiry: _ } = self;
+		
+		*value == other.value && *bounds == other.bounds && *rounding_fn == other.rounding_fn && *mod_vec == other.mod_vec
+	}
+}
+
+// struct - ModMoreConf
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use crate::{ ModMoreConf, Bounds, Modifier, ModCommon, ModCalcMode, ModCalcStage, RoundingHelper, CsInvalidValue };
+	
+	#[test]
+	fn basic_functional() {
+		// bounds are applied to the product, therefore they must contain 1.0
+		let bounds = Bounds::new_const( 0.0, 10.0 ).unwrap();
+		let mut more = ModMoreConf::new( bounds, RoundingHelper::new_none() );
+		assert_eq!( more.value(), 1.0 );
+		
+		let common = ModCommon::new( 0.5, ModCalcMode::Add, ModCalcStage::More ).unwrap();
+		more.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		more.append_mod( Modifier::new_expiring( common, 10 ) ).unwrap();
+		
+		// 1.5 * 1.5, compared to 1 + 0.5 + 0.5 of an additive stage
+		assert_eq!( more.value(), 2.25 );
+		assert_eq!( more.apply( 100.0 ), 225.0 );
+		
+		assert_eq!( more.remove_expired( 10 ).len(), 1 );
+		assert_eq!( more.value(), 1.5 );
+		
+		let expected = CsInvalidValue::CannotBeZero( "value".to_string() ).into();
+		assert_eq!( ModCommon::new( 0.0, ModCalcMode::Div, ModCalcStage::More ), Err( expected ) );
+	}
+	
+	#[test]
+	fn bounds_and_rounding() {
+		let bounds = Bounds::new_const( 0.5, 2.0 ).unwrap();
+		let mut more = ModMoreConf::new( bounds, RoundingHelper::new_none() );
+		
+		let common = ModCommon::new( 3.0, ModCalcMode::Mul, ModCalcStage::More ).unwrap();
+		more.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( more.value(), 2.0 );
+		
+		let common = ModCommon::new( 0.9, ModCalcMode::Sub, ModCalcStage::More ).unwrap();
+		more.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		assert_eq!( more.value(), 0.5 );
+		
+		more.set_rounding( RoundingHelper::new( crate::RoundingFnEnum::Ceil, None ) );
+		assert_eq!( more.apply( 3.0 ), 2.0 );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
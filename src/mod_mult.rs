@@ -5,7 +5,8 @@ use serde::{ Serialize, Deserialize };
 //------------------------------------------------------------------------------
 // --Modules
 
-use super::{ Bounds, ModCalcStage, ModCalcMode, Modifier, CharStatError, CsLogicIssue };
+use crate::expiry::ExpiryIndex;
+use super::{ Bounds, ModCalcStage, ModCalcMode, Modifier, ModifierId, CharStatError, CsLogicIssue };
 
 // --Modules
 //------------------------------------------------------------------------------
@@ -13,11 +14,13 @@ use super::{ Bounds, ModCalcStage, ModCalcMode, Modifier, CharStatError, CsLogic
 
 /// Component handling modifier multiplier.
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, PartialEq,  )]
+#[derive( Debug, Clone )]
 pub struct ModMultConf {
 	value: f64,
 	bounds: Bounds,
 	mod_vec: Vec< Modifier >,
+	#[cfg_attr( feature = "serde", serde( skip ) )]
+	expiry: ExpiryIndex,
 }
 
 impl ModMultConf {
@@ -27,11 +30,12 @@ impl ModMultConf {
 			value: 1.0,
 			bounds,
 			mod_vec: Vec::new(),
+			expiry: ExpiryIndex::new(),
 		}
 	}
 	
 	/// # Errors
-	/// `CsLogicIssue::InvalidModifierMode` when `stage` is not `ModMult` or `mode` is `Mul` or `Div` <br>
+	/// `CsLogicIssue::InvalidModifierMode` when `stage` is not `ModMult` or `mode` is `Mul`, `Div` or `Custom` <br>
 	#[inline]
 	pub fn append_mod ( &mut self, modifier: Modifier ) -> Result<(), CharStatError> {
 		let stage = modifier.calc_stage();
@@ -41,7 +45,7 @@ impl ModMultConf {
 			return Err( CsLogicIssue::InvalidModifierStage( stage, ModCalcStage::ModMult ).into() )
 		}
 		
-		if let ModCalcMode::Mul | ModCalcMode::Div = mode {
+		if let ModCalcMode::Mul | ModCalcMode::Div | ModCalcMode::Custom = mode {
 			return Err( CsLogicIssue::InvalidModifierMode( mode, vec![ ModCalcMode::Add, ModCalcMode::Sub ] ).into() )
 		}
 		
@@ -56,18 +60,43 @@ impl ModMultConf {
 	}
 	
 	#[inline]
-	pub fn remove_expired ( &mut self, ts: u64 ) {
-		for i in ( 0..self.mod_vec.len() ).rev() {
-			let tmp = self.mod_vec.get( i );
-			
-			if let Some( element ) = tmp {
-				if element.has_expired( ts ) {
-					self.mod_vec.remove( i );
-				}
-			}
-		}// for
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+	
+	/// stored modifiers in order of appending
+	#[inline]
+	pub fn modifiers ( &self ) -> &[ Modifier ] {
+		&self.mod_vec
+	}
+	
+	/// returns the number of stored modifiers
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.mod_vec.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.mod_vec.is_empty()
+	}
+	
+	/// removes expired modifiers, returns the removed ones
+	#[inline]
+	pub fn remove_expired ( &mut self, ts: u64 ) -> Vec< Modifier > {
+		let out = self.expiry.remove_expired( &mut self.mod_vec, ts );
 		
-		self.update();
+		if !out.is_empty() {
+			self.update();
+		}
+		
+		out
+	}
+	
+	/// returns the lowest expiration timestamp of stored modifiers
+	#[inline]
+	pub fn next_expiration ( &self ) -> Option< u64 > {
+		self.expiry.next( &self.mod_vec )
 	}
 }
 
@@ -75,10 +104,23 @@ impl ModMultConf {
 impl ModMultConf {
 	#[inline]
 	pub( crate ) fn append_mod_unchecked ( &mut self, modifier: Modifier ) {
+		self.expiry.push( &modifier );
 		self.mod_vec.push( modifier );
 		self.update();
 	}
 	
+	/// replaces or removes ( `None` ) the modifier with `id`
+	#[inline]
+	pub( crate ) fn swap_mod ( &mut self, id: ModifierId, new_val: Option< Modifier > ) -> bool {
+		let out = self.expiry.swap( &mut self.mod_vec, id, new_val );
+		
+		if out {
+			self.update();
+		}
+		
+		out
+	}
+	
 	#[inline]
 	pub( crate ) fn update ( &mut self ) {
 		let mut tmp = 0.0;
@@ -105,6 +147,7 @@ impl ModMultConf {
 	#[inline]
 	pub fn set_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		self.bounds.set_min( new_val )?;
+		self.update();
 		
 		Ok(())
 	}
@@ -116,6 +159,7 @@ impl ModMultConf {
 	#[inline]
 	pub fn set_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
 		self.bounds.set_max( new_val )?;
+		self.update();
 		
 		Ok(())
 	}
@@ -141,12 +185,24 @@ impl ModMultConf {
 	}
 }// bounds
 
+impl PartialEq for ModMultConf {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		// `expiry` is derived from `mod_vec`
+		let ModMultConf { value, bounds, mod_vec, expiry: _ } = self;
+		
+		*value == other.value && *bounds == other.bounds && *mod_vec == other.mod_vec
+	}
+}
+
 // struct - ModMultConf
 //------------------------------------------------------------------------------
 // --Tests
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use crate::{ ModMultConf, Bounds, Modifier, ModCommon, ModCalcMode, ModCalcStage };
 	
 	#[test]
@@ -164,7 +220,7 @@ mod tests {
 		mod_mult.append_mod( modifier ).unwrap();
 		assert_eq!( mod_mult.value(), 2.0 );
 		
-		mod_mult.remove_expired( 100 );
+		assert_eq!( mod_mult.remove_expired( 100 ).len(), 1 );
 		assert_eq!( mod_mult.value(), 1.0 );
 	}
 }
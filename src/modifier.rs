@@ -1,4 +1,5 @@
 use std::fmt::{ Display, Formatter };
+use std::ops::Deref;
 
 #[cfg( feature = "serde" )]
 use serde::{ Serialize, Deserialize };
@@ -7,7 +8,7 @@ use serde::{ Serialize, Deserialize };
 //------------------------------------------------------------------------------
 // --Modules
 
-use crate::{ CharStatError, CsLogicIssue, CsInvalidValue };
+use crate::{ CharStatError, CsLogicIssue, CsInvalidValue, CustomModFn, ModifierFn, ModFnContext };
 
 // --Modules
 //------------------------------------------------------------------------------
@@ -15,10 +16,14 @@ use crate::{ CharStatError, CsLogicIssue, CsInvalidValue };
 
 /// An instance of a modifier.
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
-#[derive( Debug, Clone, PartialEq,  )]
+#[derive( Debug, Clone )]
 pub struct Modifier {
 	common: ModCommon,
 	mod_type: ModType,
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ) )]
+	custom_fn: Option< CustomModFn >,
+	#[cfg_attr( feature = "serde", serde( default, skip_serializing_if = "Option::is_none" ) )]
+	id: Option< ModifierId >,
 }
 
 impl Modifier {
@@ -26,19 +31,19 @@ impl Modifier {
 	pub fn new_expiring ( common: ModCommon, exp_ts: u64 ) -> Modifier {
 		let v_data = ModType::Expiring { exp_ts };
 		
-		Self{ common, mod_type: v_data }
+		Self{ common, mod_type: v_data, custom_fn: None, id: None }
 	}
 	
 	#[inline]
 	pub fn new_persistent ( common: ModCommon ) -> Modifier {
-		Self{ common, mod_type: ModType::Persistent }
+		Self{ common, mod_type: ModType::Persistent, custom_fn: None, id: None }
 	}
 	
 	#[inline]
 	pub fn new_stacked ( common: ModCommon, conf: ModStackConf ) -> Modifier {
 		let v_data = ModType::Stacked { conf: Box::new( conf ) };
 		
-		Self{ common, mod_type: v_data }
+		Self{ common, mod_type: v_data, custom_fn: None, id: None }
 	}
 	
 	#[inline]
@@ -74,6 +79,108 @@ impl Modifier {
 		self.common.stage
 	}
 	
+	#[inline]
+	pub fn mod_type ( &self ) -> &ModType {
+		&self.mod_type
+	}
+	
+	/// id assigned by `CharStat::append_modifier`
+	#[inline]
+	pub fn id ( &self ) -> Option< ModifierId > {
+		self.id
+	}
+	
+	#[inline]
+	pub fn is_expiring ( &self ) -> bool {
+		matches!( self.mod_type, ModType::Expiring{ .. } )
+	}
+	
+	#[inline]
+	pub fn is_persistent ( &self ) -> bool {
+		matches!( self.mod_type, ModType::Persistent )
+	}
+	
+	#[inline]
+	pub fn is_stacked ( &self ) -> bool {
+		matches!( self.mod_type, ModType::Stacked{ .. } )
+	}
+	
+	/// Returns true when the modifier increases the value, assuming a positive stage input. <br>
+	/// `Custom` mode follows the sign of the value.
+	#[inline]
+	pub fn is_buff ( &self ) -> bool {
+		let value = self.common.value;
+		
+		match ( self.common.stage, self.common.mode ) {
+			( ModCalcStage::More, ModCalcMode::Mul ) => value > 1.0,
+			( ModCalcStage::More, ModCalcMode::Div ) => value > 0.0 && value < 1.0,
+			( _, ModCalcMode::Sub ) => value < 0.0,
+			_ => value > 0.0,
+		}
+	}
+	
+	/// Returns true when the modifier decreases the value, assuming a positive stage input. <br>
+	/// `Custom` mode follows the sign of the value.
+	#[inline]
+	pub fn is_debuff ( &self ) -> bool {
+		let value = self.common.value;
+		
+		match ( self.common.stage, self.common.mode ) {
+			( ModCalcStage::More, ModCalcMode::Mul ) => value < 1.0,
+			( ModCalcStage::More, ModCalcMode::Div ) => !( 0.0..=1.0 ).contains( &value ),
+			( _, ModCalcMode::Sub ) => value > 0.0,
+			_ => value < 0.0,
+		}
+	}
+	
+	/// Attaches custom function, required when mode is `ModCalcMode::Custom`.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "custom_fn" )` when mode is not `ModCalcMode::Custom` <br>
+	#[inline]
+	pub fn with_fn ( self, func: impl ModifierFn + 'static ) -> Result< Self, CharStatError > {
+		self.with_shared_fn( CustomModFn::new( func ) )
+	}
+	
+	/// Same as `with_fn`, for a function shared between modifiers.
+	/// 
+	/// # Errors
+	/// same as `with_fn` <br>
+	#[inline]
+	pub fn with_shared_fn ( mut self, func: CustomModFn ) -> Result< Self, CharStatError > {
+		if self.common.mode != ModCalcMode::Custom {
+			return Err( CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into() )
+		}
+		
+		self.custom_fn = Some( func );
+		
+		Ok( self )
+	}
+	
+	#[inline]
+	pub fn custom_fn ( &self ) -> Option< &dyn ModifierFn > {
+		self.custom_fn.as_ref().map( CustomModFn::get )
+	}
+	
+	/// Returns the value this modifier adds to an additive stage with the given input.
+	/// `Custom` mode without a function returns 0.0
+	#[inline]
+	pub fn contribution ( &self, stage_input: f64 ) -> f64 {
+		let value = self.common.value;
+		
+		match self.common.mode {
+			ModCalcMode::Add => value,
+			ModCalcMode::Sub => -value,
+			ModCalcMode::Mul => value * stage_input,
+			ModCalcMode::Div => stage_input / value,
+			ModCalcMode::Custom => {
+				let ctx = ModFnContext::new( value, self.common.stage );
+				
+				self.custom_fn.as_ref().map_or( 0.0, |func| func.get().contribution( stage_input, &ctx ) )
+			},
+		}
+	}
+	
 	/// # Errors
 	/// CsLogicIssue::InvalidModifierType( ModType ) when `self.mod_type` is not `::Stackable`
 	#[inline]
@@ -138,11 +245,7 @@ impl Modifier {
 			let diff = ts - conf.last_ts;
 			let stacks_to_clear = u32::try_from( diff / conf.duration );
 			
-			let stacks_to_clear = if let Ok( value ) = stacks_to_clear {
-				value
-			} else {
-				u32::MAX
-			};
+			let stacks_to_clear = stacks_to_clear.unwrap_or( u32::MAX );
 			
 			conf.stack_value = conf.stack_value.saturating_sub( stacks_to_clear );
 			
@@ -151,10 +254,187 @@ impl Modifier {
 		
 		Err( CsLogicIssue::InvalidModifierType( self.mod_type.clone(), "Stacked".to_string() ).into() )
 	}
+	
+	/// # Errors
+	/// same as `ModCommon::new` <br>
+	#[inline]
+	pub fn set_value ( &mut self, new_val: f64 ) -> Result< (), CharStatError > {
+		self.common = ModCommon::new( new_val, self.common.mode, self.common.stage )?;
+		
+		Ok(())
+	}
+	
+	/// Leaving `Custom` mode drops the function.
+	/// 
+	/// # Errors
+	/// same as `ModCommon::new` <br>
+	/// same as `set_common` <br>
+	#[inline]
+	pub fn set_calc_mode ( &mut self, new_val: ModCalcMode ) -> Result< (), CharStatError > {
+		let common = ModCommon::new( self.common.value, new_val, self.common.stage )?;
+		
+		self.set_common( common )
+	}
+	
+	/// Stored modifier is moved to the container of the new stage by `CharStat::update_modifier`.
+	/// 
+	/// # Errors
+	/// same as `ModCommon::new` <br>
+	#[inline]
+	pub fn set_calc_stage ( &mut self, new_val: ModCalcStage ) -> Result< (), CharStatError > {
+		self.common = ModCommon::new( self.common.value, self.common.mode, new_val )?;
+		
+		Ok(())
+	}
+	
+	/// Leaving `Custom` mode drops the function.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "custom_fn" )` when mode of `new_val` is `Custom` and there is no function <br>
+	#[inline]
+	pub fn set_common ( &mut self, new_val: ModCommon ) -> Result< (), CharStatError > {
+		match new_val.mode {
+			ModCalcMode::Custom if self.custom_fn.is_none() => return Err( CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into() ),
+			ModCalcMode::Custom => {},
+			_ => self.custom_fn = None,
+		}
+		
+		self.common = new_val;
+		
+		Ok(())
+	}
+	
+	#[inline]
+	pub fn common ( &self ) -> ModCommon {
+		self.common
+	}
+	
+	/// # Errors
+	/// CsLogicIssue::InvalidModifierType( ModType ) when `self.mod_type` is not `::Expiring` <br>
+	#[inline]
+	pub fn set_expiration_ts ( &mut self, new_val: u64 ) -> Result< (), CharStatError > {
+		if let ModType::Expiring { ref mut exp_ts } = self.mod_type {
+			*exp_ts = new_val;
+			
+			return Ok(())
+		}
+		
+		Err( CsLogicIssue::InvalidModifierType( self.mod_type.clone(), "Expiring".to_string() ).into() )
+	}
+}
+
+// pub-crate
+impl Modifier {
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "custom_fn" )` when mode is `Custom` without a function or other mode with a function <br>
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn check_fn ( &self ) -> Result<(), CharStatError > {
+		if ( self.common.mode == ModCalcMode::Custom ) != self.custom_fn.is_some() {
+			return Err( CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into() )
+		}
+		
+		Ok(())
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn set_id ( &mut self, id: ModifierId ) {
+		self.id = Some( id );
+	}
+}
+
+impl PartialEq for Modifier {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		// `id` only identifies the modifier within a `CharStat`, it is not part of its' value
+		let Modifier { common, mod_type, custom_fn, id: _ } = self;
+		
+		*common == other.common && *mod_type == other.mod_type && *custom_fn == other.custom_fn
+	}
+}
+
+impl Display for Modifier {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		f.write_str( "Modifier = (" )?;
+		
+		if let Some( id ) = self.id {
+			write!( f, "id: {id}, " )?;
+		}
+		
+		write!( f, "{}, {} {}, stage: {}", self.mod_type, self.common.mode, self.common.value, self.common.stage )?;
+		
+		match &self.mod_type {
+			ModType::Expiring { exp_ts } => write!( f, ", expires: {exp_ts}" )?,
+			ModType::Stacked { conf } => write!( f, ", stack: {}/{}", conf.stack_value, conf.stack_max )?,
+			ModType::Persistent => {},
+		}
+		
+		if let Some( func ) = &self.custom_fn {
+			write!( f, ", fn: {}", func.get().type_name() )?;
+		}
+		
+		f.write_str( ")" )
+	}
 }
 
 // struct - Modifier
 //------------------------------------------------------------------------------
+// struct - ModifierId
+
+/// Id of a modifier, unique within one `CharStat`.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord )]
+pub struct ModifierId( pub( crate ) u64 );
+
+impl Display for ModifierId {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+// struct - ModifierId
+//------------------------------------------------------------------------------
+// struct - ModifierRef
+
+/// Modifier stored in `CharStat` with the timestamp of the stat, see `CharStat::modifiers`.
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct ModifierRef< 'a > {
+	modifier: &'a Modifier,
+	ts: u64,
+}
+
+impl< 'a > ModifierRef< 'a > {
+	#[inline]
+	pub( crate ) fn new ( modifier: &'a Modifier, ts: u64 ) -> Self {
+		ModifierRef { modifier, ts }
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> &'a Modifier {
+		self.modifier
+	}
+	
+	/// returns the time left until an expiring modifier expires, `None` for other types
+	#[inline]
+	pub fn remaining ( &self ) -> Option< u64 > {
+		self.modifier.expiration_ts().map( |exp_ts| exp_ts.saturating_sub( self.ts ) )
+	}
+}
+
+impl Deref for ModifierRef<'_> {
+	type Target = Modifier;
+	
+	#[inline]
+	fn deref( &self ) -> &Modifier {
+		self.modifier
+	}
+}
+
+// struct - ModifierRef
+//------------------------------------------------------------------------------
 // enum - ModType
 
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
@@ -182,7 +462,9 @@ impl Display for ModType {
 //------------------------------------------------------------------------------
 // struct - ModCommon
 
+/// Value, mode and stage of a modifier, deserialization runs the same checks as `ModCommon::new`.
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[cfg_attr( feature = "serde", serde( try_from = "ModCommonRepr" ) )]
 #[derive( Debug, Clone, Copy, PartialEq,  )]
 pub struct ModCommon {
 	value: f64,
@@ -194,15 +476,25 @@ pub struct ModCommon {
 impl ModCommon {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `new_val` is `f64::NAN` <br>
-	/// CsLogicIssue::InvalidModifierMode( ... ) when `stage` is `::ModMult` and `mode` is `::Mul` or `::Div`
+	/// `CsInvalidValue::Infinite` when `new_val` is infinite <br>
+	/// `CsInvalidValue::CannotBeZero` when `mode` is `::Div` and `value` is 0.0 <br>
+	/// CsLogicIssue::InvalidModifierMode( ... ) when `stage` is `::ModMult` and `mode` is `::Mul`, `::Div` or `::Custom`
 	#[inline]
 	pub fn new ( value: f64, mode: ModCalcMode, stage: ModCalcStage, ) -> Result< Self, CharStatError > {
 		if value.is_nan() {
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
 		
+		if value.is_infinite() {
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
+		
+		if mode == ModCalcMode::Div && value == 0.0 {
+			return Err( CsInvalidValue::CannotBeZero( "value".to_string() ).into() )
+		}
+		
 		if ModCalcStage::ModMult == stage {
-			if let ModCalcMode::Mul | ModCalcMode::Div = mode {
+			if let ModCalcMode::Mul | ModCalcMode::Div | ModCalcMode::Custom = mode {
 				
 				return Err( CsLogicIssue::InvalidModifierMode( mode, vec![ ModCalcMode::Add, ModCalcMode::Sub ] ).into() )
 			}
@@ -214,6 +506,39 @@ impl ModCommon {
 			stage,
 		})
 	}
+	
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.value
+	}
+	
+	#[inline]
+	pub fn mode ( &self ) -> ModCalcMode {
+		self.mode
+	}
+	
+	#[inline]
+	pub fn stage ( &self ) -> ModCalcStage {
+		self.stage
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Deserialize )]
+struct ModCommonRepr {
+	value: f64,
+	mode: ModCalcMode,
+	stage: ModCalcStage,
+}
+
+#[cfg( feature = "serde" )]
+impl TryFrom< ModCommonRepr > for ModCommon {
+	type Error = CharStatError;
+	
+	#[inline]
+	fn try_from( repr: ModCommonRepr ) -> Result< Self, Self::Error > {
+		ModCommon::new( repr.value, repr.mode, repr.stage )
+	}
 }
 
 // struct - ModCommon
@@ -253,6 +578,28 @@ impl ModStackConf {
 			stack_max,
 		} )
 	}
+	
+	/// timestamp of the last update of stacks
+	#[inline]
+	pub fn last_ts ( &self ) -> u64 {
+		self.last_ts
+	}
+	
+	/// time after which one stack is cleared
+	#[inline]
+	pub fn duration ( &self ) -> u64 {
+		self.duration
+	}
+	
+	#[inline]
+	pub fn stack_value ( &self ) -> u32 {
+		self.stack_value
+	}
+	
+	#[inline]
+	pub fn stack_max ( &self ) -> u32 {
+		self.stack_max
+	}
 }
 
 // struct - ModStackConf
@@ -262,7 +609,12 @@ impl ModStackConf {
 /// Calculation Mode:
 /// - Add | Sub => value of modifier is added / substracted from the total,
 /// - Mul => adds to the total a result of multiplying base by modifier,
-/// - Div => adds to the total a result of dividing base by modifier,
+/// - Div => adds to the total a result of dividing base by modifier, value cannot be 0.0
+/// - Custom => adds to the total a result of `ModifierFn` attached to the modifier,
+/// 
+/// Signs are not normalized: Add with a negative value equals Sub with a positive one,
+/// Mul and Div follow the sign of both the modifier and the stage input.
+/// Percent bounds of `ModConf` scale with the stage input, when a negative input flips them min and max are swapped.
 #[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
 #[derive( Debug, Clone, Copy, PartialEq, Eq )]
 pub enum ModCalcMode {
@@ -270,6 +622,7 @@ pub enum ModCalcMode {
 	Sub,
 	Mul,
 	Div,
+	Custom,
 }
 
 impl Display for ModCalcMode {
@@ -280,6 +633,7 @@ impl Display for ModCalcMode {
 			Self::Sub => "Sub",
 			Self::Mul => "Mul",
 			Self::Div => "Div",
+			Self::Custom => "Custom",
 		};
 		
 		tmp.fmt(f)
@@ -298,6 +652,9 @@ pub enum ModCalcStage {
 	Upgrade,
 	BasePlusUpgrade,
 	ModMult,
+	More,
+	/// stage of user defined `Pipeline`
+	Custom( u16 ),
 }
 
 impl Display for ModCalcStage {
@@ -308,6 +665,8 @@ impl Display for ModCalcStage {
 			Self::Upgrade => "Upgrade",
 			Self::BasePlusUpgrade => "BasePlusUpgrade",
 			Self::ModMult => "ModMult",
+			Self::More => "More",
+			Self::Custom( id ) => return write!( f, "Custom({id})" ),
 		};
 		
 		tmp.fmt(f)
@@ -320,6 +679,8 @@ impl Display for ModCalcStage {
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use super::*;
 	
 	#[test]
@@ -336,7 +697,72 @@ mod tests {
 		let stack = ModStackConf::new( 0, 160, 0, 2 ).unwrap();
 		let mod_3 = Modifier::new_stacked( common, stack );
 		
-		let _v = vec![ mod_1, mod_2, mod_3 ];
+		let _v = [ mod_1, mod_2, mod_3 ];
+	}
+	
+	#[test]
+	fn buff_and_debuff() {
+		let cases = [
+			( 0.5, ModCalcMode::Add, ModCalcStage::Base, true, false ),
+			( 0.5, ModCalcMode::Sub, ModCalcStage::Upgrade, false, true ),
+			( -0.5, ModCalcMode::Sub, ModCalcStage::ModMult, true, false ),
+			( 2.0, ModCalcMode::Div, ModCalcStage::Base, true, false ),
+			( 0.8, ModCalcMode::Mul, ModCalcStage::More, false, true ),
+			( 0.8, ModCalcMode::Div, ModCalcStage::More, true, false ),
+			( 0.0, ModCalcMode::Add, ModCalcStage::Base, false, false ),
+		];
+		
+		for ( value, mode, stage, is_buff, is_debuff ) in cases {
+			let modifier = Modifier::new_persistent( ModCommon::new( value, mode, stage ).unwrap() );
+			assert_eq!( ( modifier.is_buff(), modifier.is_debuff() ), ( is_buff, is_debuff ), "{value} {mode} {stage}" );
+			assert!( modifier.is_persistent() && !modifier.is_expiring() && !modifier.is_stacked() );
+			assert_eq!( modifier.id(), None );
+		}
+	}
+	
+	#[test]
+	fn setters() {
+		#[derive( Debug )]
+		struct Doubled;
+		
+		impl ModifierFn for Doubled {
+			fn contribution ( &self, stage_input: f64, ctx: &ModFnContext ) -> f64 {
+				ctx.value() * stage_input * 2.0
+			}
+			
+			fn type_name ( &self ) -> &'static str {
+				"doubled"
+			}
+		}
+		
+		let common = ModCommon::new( 2.0, ModCalcMode::Div, ModCalcStage::Base ).unwrap();
+		let mut modifier = Modifier::new_expiring( common, 10 );
+		
+		let expected: CharStatError = CsInvalidValue::CannotBeZero( "value".to_string() ).into();
+		assert_eq!( modifier.set_value( 0.0 ), Err( expected ) );
+		assert!( modifier.set_calc_stage( ModCalcStage::ModMult ).is_err() );
+		assert_eq!( modifier.common(), common );
+		
+		modifier.set_calc_mode( ModCalcMode::Add ).unwrap();
+		modifier.set_calc_stage( ModCalcStage::ModMult ).unwrap();
+		modifier.set_expiration_ts( 20 ).unwrap();
+		assert_eq!( modifier.common(), ModCommon::new( 2.0, ModCalcMode::Add, ModCalcStage::ModMult ).unwrap() );
+		assert_eq!( modifier.expiration_ts(), Some( 20 ) );
+		
+		let mut persistent = Modifier::new_persistent( common );
+		assert!( persistent.set_expiration_ts( 20 ).is_err() );
+		
+		// custom mode requires a function
+		let expected: CharStatError = CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into();
+		assert_eq!( persistent.set_calc_mode( ModCalcMode::Custom ), Err( expected.clone() ) );
+		assert_eq!( persistent.set_common( ModCommon::new( 1.0, ModCalcMode::Custom, ModCalcStage::Base ).unwrap() ), Err( expected ) );
+		assert_eq!( persistent.common(), common );
+		
+		let custom = ModCommon::new( 2.0, ModCalcMode::Custom, ModCalcStage::Base ).unwrap();
+		let mut with_fn = Modifier::new_persistent( custom ).with_fn( Doubled ).unwrap();
+		with_fn.set_calc_mode( ModCalcMode::Add ).unwrap();
+		assert!( with_fn.custom_fn().is_none() );
+		assert_eq!( with_fn.contribution( 10.0 ), 2.0 );
 	}
 	
 	#[test]
@@ -345,6 +771,22 @@ mod tests {
 		
 		let expected: CharStatError = CsInvalidValue::Nan( "value".to_string() ).into();
 		assert_eq!( common, Err( expected ) );
+		
+		let common = ModCommon::new( f64::NEG_INFINITY, ModCalcMode::Add, ModCalcStage::Base );
+		let expected: CharStatError = CsInvalidValue::Infinite( "value".to_string() ).into();
+		assert_eq!( common, Err( expected ) );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let common = ModCommon::new( 2.0, ModCalcMode::Div, ModCalcStage::Base ).unwrap();
+		let serialized = serde_json::to_string( &common ).unwrap();
+		assert_eq!( serde_json::from_str::< ModCommon >( &serialized ).unwrap(), common );
+		
+		// deserialization runs the same checks as the constructor
+		let zero_div: Result< ModCommon, _ > = serde_json::from_str( &serialized.replace( "2.0", "0.0" ) );
+		assert!( zero_div.is_err() );
 	}
 	
 	#[test]
@@ -0,0 +1,501 @@
+use std::fmt::{ Display, Formatter };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ Bounds, RoundingHelper, ModConf, ModConfCalc, ModCalcStage, Modifier, ModifierId, StageValues, CharStatError, CsInvalidValue, CsMissingComponent };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - PipelineCalc
+
+/// Values calculated by `Pipeline::calc`, output and modifier values of every stage.
+#[derive( Debug, Clone, PartialEq, Default )]
+pub( crate ) struct PipelineCalc {
+	pub( crate ) value: f64,
+	pub( crate ) stages: Vec< ( f64, Option< ModConfCalc > ) >,
+}
+
+// struct - PipelineCalc
+//------------------------------------------------------------------------------
+// enum - StageInput
+
+/// Value passed to modifiers of a `PipelineStage`.
+/// - Base => base with multiplier
+/// - Upgrade => upgrade, 0.0 when `UpgradeConf` is missing
+/// - BasePlusUpgrade => sum of both
+/// - Previous => running total of previous stages
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum StageInput {
+	Base,
+	Upgrade,
+	BasePlusUpgrade,
+	Previous,
+}
+
+impl Display for StageInput {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let tmp = match self {
+			Self::Base => "Base",
+			Self::Upgrade => "Upgrade",
+			Self::BasePlusUpgrade => "BasePlusUpgrade",
+			Self::Previous => "Previous",
+		};
+		
+		tmp.fmt(f)
+	}
+}
+
+// enum - StageInput
+//------------------------------------------------------------------------------
+// struct - PipelineStage
+
+/// A single, named stage of `Pipeline`.
+/// 
+/// Output of the stage is the running total of previous stages, plus input ( when `include_input` is true ),
+/// plus value of modifiers multiplied by modifier multiplier. Rounding and then bounds are applied to the output. <br>
+/// `include_input` is ignored for `StageInput::Previous`, as it is already part of the running total.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq,  )]
+pub struct PipelineStage {
+	name: String,
+	input: StageInput,
+	include_input: bool,
+	mods: Option< ModConf >,
+	bounds: Option< Bounds >,
+	rounding_fn: RoundingHelper,
+	value: f64,
+}
+
+impl PipelineStage {
+	#[inline]
+	pub fn new ( name: &str, input: StageInput, include_input: bool, mods: Option< ModConf >, bounds: Option< Bounds >, rounding_fn: RoundingHelper, ) -> Self {
+		PipelineStage {
+			name: name.to_string(),
+			input,
+			include_input,
+			mods,
+			bounds,
+			rounding_fn,
+			value: 0.0,
+		}
+	}
+	
+	#[inline]
+	pub fn name ( &self ) -> &str {
+		&self.name
+	}
+	
+	#[inline]
+	pub fn input ( &self ) -> StageInput {
+		self.input
+	}
+	
+	#[inline]
+	pub fn include_input ( &self ) -> bool {
+		self.include_input
+	}
+	
+	#[inline]
+	pub fn mods ( &self ) -> Option< &ModConf > {
+		self.mods.as_ref()
+	}
+	
+	#[inline]
+	pub fn bounds ( &self ) -> Option< &Bounds > {
+		self.bounds.as_ref()
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	/// returns the running total after this stage
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.value
+	}
+}
+
+// struct - PipelineStage
+//------------------------------------------------------------------------------
+// struct - Pipeline
+
+/// Ordered list of calculation stages, replaces the fixed modifier stages of `CharStat`.
+/// 
+/// Modifiers are dispatched to the stage whose `ModConf` has a matching `ModCalcStage`,
+/// `ModCalcStage::Custom( id )` can be used for stages other than the classic ones.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ Pipeline, PipelineStage, StageInput, ModConf, ModCalcStage, Bounds, RoundingHelper };
+/// 
+/// let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+/// let gear_flat = ModConf::new( ModCalcStage::Custom( 0 ), bounds, RoundingHelper::new_none(), false, false );
+/// let gear_percent = ModConf::new( ModCalcStage::Custom( 1 ), bounds, RoundingHelper::new_none(), false, false );
+/// 
+/// let mut pipeline = Pipeline::new();
+/// pipeline.push_stage( PipelineStage::new( "gear flat", StageInput::Base, true, Some( gear_flat ), None, RoundingHelper::new_none() ) ).unwrap();
+/// pipeline.push_stage( PipelineStage::new( "gear %", StageInput::Previous, false, Some( gear_percent ), None, RoundingHelper::new_none() ) ).unwrap();
+/// pipeline.push_stage( PipelineStage::new( "caps", StageInput::Previous, false, None, Some( bounds ), RoundingHelper::new_none() ) ).unwrap();
+/// 
+/// assert_eq!( pipeline.len(), 3 );
+/// ```
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq,  )]
+pub struct Pipeline {
+	value: f64,
+	stages: Vec< PipelineStage >,
+}
+
+impl Pipeline {
+	#[inline]
+	pub fn new () -> Self {
+		Pipeline {
+			value: 0.0,
+			stages: Vec::new(),
+		}
+	}
+	
+	/// Preset reproducing the default layout of `CharStat`: base, upgrade and base + upgrade stages,
+	/// each with the given `ModConf`, see also `CharStat::use_classic_pipeline`.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `ModCalcStage` of a `ModConf` does not match its' stage <br>
+	#[inline]
+	pub fn classic ( mod_of_base: Option< ModConf >, mod_of_upgrade: Option< ModConf >, mod_of_base_plus_upgrade: Option< ModConf > ) -> Result< Self, CharStatError > {
+		let confs = [ ( ModCalcStage::Base, &mod_of_base ), ( ModCalcStage::Upgrade, &mod_of_upgrade ), ( ModCalcStage::BasePlusUpgrade, &mod_of_base_plus_upgrade ) ];
+		
+		if confs.iter().any( |( stage, conf )| conf.as_ref().is_some_and( |tmp| tmp.stage() != *stage ) ) {
+			return Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() )
+		}
+		
+		let stages = vec![
+			PipelineStage::new( "base", StageInput::Base, true, mod_of_base, None, RoundingHelper::new_none() ),
+			PipelineStage::new( "upgrade", StageInput::Upgrade, true, mod_of_upgrade, None, RoundingHelper::new_none() ),
+			PipelineStage::new( "base_plus_upgrade", StageInput::BasePlusUpgrade, false, mod_of_base_plus_upgrade, None, RoundingHelper::new_none() ),
+		];
+		
+		Ok( Pipeline {
+			value: 0.0,
+			stages,
+		} )
+	}
+	
+	/// Appends a stage at the end of the pipeline.
+	/// 
+	/// # Errors
+	/// `CsInvalidValue::NotAllowed( "name" )` when stage with the same name already exists <br>
+	/// `CsInvalidValue::NotAllowed( "stage" )` when `ModCalcStage` of modifiers is `ModMult`, `More` or is already used by other stage <br>
+	#[inline]
+	pub fn push_stage ( &mut self, stage: PipelineStage ) -> Result<(), CharStatError > {
+		if self.stage( stage.name() ).is_some() {
+			return Err( CsInvalidValue::NotAllowed( "name".to_string() ).into() )
+		}
+		
+		if let Some( mods ) = &stage.mods {
+			let mod_stage = mods.stage();
+			
+			if let ModCalcStage::ModMult | ModCalcStage::More = mod_stage {
+				return Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() )
+			}
+			
+			if self.stage_index( mod_stage ).is_some() {
+				return Err( CsInvalidValue::NotAllowed( "stage".to_string() ).into() )
+			}
+		}
+		
+		self.stages.push( stage );
+		
+		Ok(())
+	}
+	
+	/// returns the output of the last stage
+	#[inline]
+	pub fn value ( &self ) -> f64 {
+		self.value
+	}
+	
+	#[inline]
+	pub fn stages ( &self ) -> &[ PipelineStage ] {
+		&self.stages
+	}
+	
+	#[inline]
+	pub fn stage ( &self, name: &str ) -> Option< &PipelineStage > {
+		self.stages.iter().find( |tmp| tmp.name == name )
+	}
+	
+	#[inline]
+	pub fn len ( &self ) -> usize {
+		self.stages.len()
+	}
+	
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.stages.is_empty()
+	}
+}
+
+impl Default for Pipeline {
+	#[inline]
+	fn default() -> Self { Pipeline::new() }
+}
+
+// pub-crate
+impl Pipeline {
+	/// # Errors
+	/// `CsMissingComponent::PipelineStage` when there is no stage for `modifier.calc_stage` <br>
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn append_mod ( &mut self, modifier: Modifier ) -> Result<(), CharStatError > {
+		let idx = self.stage_index( modifier.calc_stage() ).ok_or( CsMissingComponent::PipelineStage )?;
+		
+		if let Some( mods ) = &mut self.stages[ idx ].mods {
+			mods.push_mod( modifier );
+		}
+		
+		Ok(())
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn mods_mut ( &mut self, mod_stage: ModCalcStage ) -> Option< &mut ModConf > {
+		let idx = self.stage_index( mod_stage )?;
+		
+		self.stages[ idx ].mods.as_mut()
+	}
+	
+	/// replaces or removes ( `None` ) the modifier with `id` in the stage for `mod_stage`
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn swap_mod ( &mut self, mod_stage: ModCalcStage, id: ModifierId, new_val: Option< Modifier > ) -> bool {
+		let Some( idx ) = self.stage_index( mod_stage ) else {
+			return false
+		};
+		
+		self.stages[ idx ].mods.as_mut().is_some_and( |mods| mods.swap_mod( id, new_val ) )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn remove_expired ( &mut self, ts: u64 ) -> Vec< Modifier > {
+		let mut out = Vec::new();
+		
+		for stage in &mut self.stages {
+			if let Some( mods ) = &mut stage.mods {
+				out.extend( mods.remove_expired( ts ) );
+			}
+		}// for
+		
+		out
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn next_expiration ( &self ) -> Option< u64 > {
+		self.stages.iter().filter_map( |stage| stage.mods.as_ref()?.next_expiration() ).min()
+	}
+	
+	/// `ctx` receives value of every stage once it is calculated, so a stage can be bound only by the previous ones. <br>
+	/// Modifier values of Base, Upgrade and BasePlusUpgrade stages are stored in `ctx.mod_of_*`. <br>
+	/// Infinite values are clamped to `f64::MIN` / `f64::MAX`, the returned bool is true when that happened. <br>
+	/// Nothing is changed, the result is applied by `store`.
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn calc ( &self, base: f64, upgrade: f64, mod_mult: f64, ctx: &mut StageValues ) -> ( PipelineCalc, bool ) {
+		let mut out = PipelineCalc { value: 0.0, stages: Vec::with_capacity( self.stages.len() ) };
+		let mut overflow = false;
+		let mut total = 0.0;
+		
+		for stage in &self.stages {
+			let input = match stage.input {
+				StageInput::Base => base,
+				StageInput::Upgrade => upgrade,
+				StageInput::BasePlusUpgrade => base + upgrade,
+				StageInput::Previous => total,
+			};
+			
+			if stage.include_input && stage.input != StageInput::Previous {
+				total += input;
+			}
+			
+			let mods = stage.mods.as_ref().map( |mods| {
+				let mut calc = mods.calc( input );
+				calc.value = mods.dyn_bounded( calc.value, ctx );
+				
+				let value = calc.value * mod_mult;
+				overflow |= value.is_infinite();
+				let value = value.clamp( f64::MIN, f64::MAX );
+				total += value;
+				
+				match mods.stage() {
+					ModCalcStage::Base => ctx.mod_of_base = value,
+					ModCalcStage::Upgrade => ctx.mod_of_upgrade = value,
+					ModCalcStage::BasePlusUpgrade => ctx.mod_of_base_plus_upgrade = value,
+					_ => {},
+				}
+				
+				calc
+			} );
+			
+			total = stage.rounding_fn.do_rounding( total );
+			
+			if let Some( bounds ) = &stage.bounds {
+				total = total.clamp( bounds.min(), bounds.max() );
+			}
+			
+			overflow |= total.is_infinite();
+			total = total.clamp( f64::MIN, f64::MAX );
+			
+			out.stages.push( ( total, mods ) );
+			ctx.stages.push( ( stage.name.clone(), total ) );
+		}// for
+		
+		out.value = total;
+		( out, overflow )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn store ( &mut self, calc: &PipelineCalc ) {
+		for ( stage, ( value, mods ) ) in self.stages.iter_mut().zip( &calc.stages ) {
+			stage.value = *value;
+			
+			if let ( Some( stored ), Some( mods ) ) = ( &mut stage.mods, mods ) {
+				stored.store( *mods );
+			}
+		}// for
+		
+		self.value = calc.value;
+	}
+	
+	/// returns the values stored by the last `store`
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn stored ( &self ) -> PipelineCalc {
+		PipelineCalc {
+			value: self.value,
+			stages: self.stages.iter().map( |tmp| ( tmp.value, tmp.mods.as_ref().map( ModConf::stored ) ) ).collect(),
+		}
+	}
+	
+	/// compares everything except the calculated values
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn eq_config ( &self, other: &Self ) -> bool {
+		self.stages.len() == other.stages.len() && self.stages.iter().zip( &other.stages ).all( |( lhs, rhs )| {
+			let PipelineStage { name, input, include_input, mods, bounds, rounding_fn, value: _ } = lhs;
+			
+			*name == rhs.name && *input == rhs.input && *include_input == rhs.include_input && *bounds == rhs.bounds && *rounding_fn == rhs.rounding_fn &&
+			match ( mods, &rhs.mods ) {
+				( Some( lhs ), Some( rhs ) ) => lhs.eq_config( rhs ),
+				( None, None ) => true,
+				_ => false,
+			}
+		} )
+	}
+	
+	/// returns name and value of every stage
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn stage_values ( &self ) -> Vec< ( String, f64 ) > {
+		self.stages.iter().map( |tmp| ( tmp.name.clone(), tmp.value ) ).collect()
+	}
+}// pub-crate
+
+// priv
+impl Pipeline {
+	#[inline( always )]
+	#[doc( hidden )]
+	fn stage_index ( &self, mod_stage: ModCalcStage ) -> Option< usize > {
+		self.stages.iter().position( |tmp| tmp.mods.as_ref().is_some_and( |mods| mods.stage() == mod_stage ) )
+	}
+}// priv
+
+// struct - Pipeline
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	use crate::{ ModCommon, ModCalcMode };
+	
+	fn update ( pipeline: &mut Pipeline, base: f64, upgrade: f64, mod_mult: f64, ctx: &mut StageValues ) {
+		let ( calc, _ ) = pipeline.calc( base, upgrade, mod_mult, ctx );
+		pipeline.store( &calc );
+	}
+	
+	#[test]
+	fn basic_functional() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let mod_conf = | stage | Some( ModConf::new( stage, bounds, RoundingHelper::new_none(), false, false ) );
+		let mut pipeline = Pipeline::classic( mod_conf( ModCalcStage::Base ), None, mod_conf( ModCalcStage::BasePlusUpgrade ) ).unwrap();
+		
+		let common = ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Base ).unwrap();
+		pipeline.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::BasePlusUpgrade ).unwrap();
+		pipeline.append_mod( Modifier::new_expiring( common, 10 ) ).unwrap();
+		
+		// 10 + 5 + 2 + 1
+		update( &mut pipeline, 10.0, 2.0, 1.0, &mut StageValues::default() );
+		assert_eq!( pipeline.value(), 18.0 );
+		assert_eq!( pipeline.stage( "base" ).unwrap().value(), 15.0 );
+		
+		pipeline.remove_expired( 10 );
+		update( &mut pipeline, 10.0, 2.0, 1.0, &mut StageValues::default() );
+		assert_eq!( pipeline.value(), 17.0 );
+		
+		let common = ModCommon::new( 1.0, ModCalcMode::Add, ModCalcStage::Custom( 3 ) ).unwrap();
+		let expected = CsMissingComponent::PipelineStage.into();
+		assert_eq!( pipeline.append_mod( Modifier::new_persistent( common ) ), Err( expected ) );
+		
+		let expected = CsInvalidValue::NotAllowed( "stage".to_string() ).into();
+		assert_eq!( Pipeline::classic( mod_conf( ModCalcStage::Upgrade ), None, None ), Err( expected ) );
+	}
+	
+	#[test]
+	fn custom_stages() {
+		let bounds = Bounds::new_const( 0.0, 100.0 ).unwrap();
+		let cap = Bounds::new_const( 0.0, 25.0 ).unwrap();
+		let none = RoundingHelper::new_none();
+		let flat = ModConf::new( ModCalcStage::Custom( 0 ), bounds, none.clone(), false, false );
+		let percent = ModConf::new( ModCalcStage::Custom( 1 ), bounds, none.clone(), false, false );
+		
+		let mut pipeline = Pipeline::new();
+		pipeline.push_stage( PipelineStage::new( "gear flat", StageInput::Base, true, Some( flat.clone() ), None, none.clone() ) ).unwrap();
+		pipeline.push_stage( PipelineStage::new( "gear %", StageInput::Previous, false, Some( percent ), None, none.clone() ) ).unwrap();
+		pipeline.push_stage( PipelineStage::new( "caps", StageInput::Previous, false, None, Some( cap ), none.clone() ) ).unwrap();
+		
+		let err = CsInvalidValue::NotAllowed( "name".to_string() ).into();
+		assert_eq!( pipeline.push_stage( PipelineStage::new( "caps", StageInput::Base, false, None, None, none.clone() ) ), Err( err ) );
+		let err = CsInvalidValue::NotAllowed( "stage".to_string() ).into();
+		assert_eq!( pipeline.push_stage( PipelineStage::new( "flat 2", StageInput::Base, false, Some( flat ), None, none ) ), Err( err ) );
+		
+		let common = ModCommon::new( 2.0, ModCalcMode::Add, ModCalcStage::Custom( 0 ) ).unwrap();
+		pipeline.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		let common = ModCommon::new( 0.5, ModCalcMode::Mul, ModCalcStage::Custom( 1 ) ).unwrap();
+		pipeline.append_mod( Modifier::new_persistent( common ) ).unwrap();
+		
+		// percent applies to gear flat output: ( 10 + 2 ) * 1.5
+		update( &mut pipeline, 10.0, 0.0, 1.0, &mut StageValues::default() );
+		assert_eq!( pipeline.value(), 18.0 );
+		
+		update( &mut pipeline, 20.0, 0.0, 1.0, &mut StageValues::default() );
+		assert_eq!( pipeline.stage( "gear %" ).unwrap().value(), 33.0 );
+		assert_eq!( pipeline.value(), 25.0 );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
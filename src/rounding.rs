@@ -0,0 +1,598 @@
+use std::fmt::{ Debug, Display, Formatter };
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicU64, Ordering };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize, Serializer, Deserializer };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::shared::{ register_any, registered_any, handle_eq };
+
+#[cfg( feature = "serde" )]
+use crate::{ CharStatError, CsInvalidValue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// enum - RoundingFnEnum
+
+/// Rounding function of `RoundingHelper`.
+/// - AwayFromZero => `ceil` for positive and `floor` for negative values
+/// - Stochastic => rounds up with probability equal to the fractional part, draws come from a SplitMix64 generator seeded by `RoundingHelper`
+/// - Custom => `RoundingFn` attached with `RoundingHelper::new_custom`
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub enum RoundingFnEnum {
+	Round,
+	RoundTiesEven,
+	Floor,
+	Ceil,
+	Trunk,
+	None,
+	AwayFromZero,
+	Stochastic,
+	Custom,
+}
+
+impl RoundingFnEnum {
+	pub( crate ) fn do_rounding( self, val: f64 ) -> f64 {
+		match self {
+			RoundingFnEnum::Round => val.round(),
+			RoundingFnEnum::RoundTiesEven => val.round_ties_even(),
+			RoundingFnEnum::Floor => val.floor(),
+			RoundingFnEnum::Ceil => val.ceil(),
+			RoundingFnEnum::Trunk => val.trunc(),
+			RoundingFnEnum::AwayFromZero => val.abs().ceil().copysign( val ),
+			RoundingFnEnum::None | RoundingFnEnum::Stochastic | RoundingFnEnum::Custom => val,
+		}
+	}
+}
+
+// enum - RoundingFnEnum
+//------------------------------------------------------------------------------
+// trait - RoundingFn
+
+/// User provided rounding function, receives value already adjusted by offset and precision.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ RoundingFn, RoundingHelper };
+/// 
+/// // rounds to the nearest even number
+/// #[derive( Debug )]
+/// struct ToEven;
+/// 
+/// impl RoundingFn for ToEven {
+///     fn round ( &self, value: f64 ) -> f64 {
+///         ( value / 2.0 ).round() * 2.0
+///     }
+/// }
+/// 
+/// let rounding = RoundingHelper::new_custom( ToEven, None );
+/// assert_eq!( rounding.round( 4.9 ), 4.0 );
+/// ```
+pub trait RoundingFn: Debug + Send + Sync {
+	fn round ( &self, value: f64 ) -> f64;
+}
+
+/// Shared handle to a `RoundingFn`, compared by pointer or registry id.
+/// 
+/// With serde a registered function is stored as `{ "rounding_fn": id }`, serialization of a function which is not registered fails.
+/// Deserialization requires registering the function with the same id first.
+#[derive( Debug, Clone )]
+pub struct CustomRoundingFn {
+	id: Option< String >,
+	func: Arc< dyn RoundingFn >,
+}
+
+impl CustomRoundingFn {
+	/// function which is not registered, cannot be serialized
+	#[inline]
+	pub fn new ( func: impl RoundingFn + 'static ) -> Self {
+		CustomRoundingFn { id: None, func: Arc::new( func ) }
+	}
+	
+	/// Registers `func` under `id` and returns a handle to it.
+	/// Registering the same id again replaces the function for new handles, existing handles keep the old one.
+	#[inline]
+	pub fn register ( id: &str, func: impl RoundingFn + 'static ) -> Self {
+		let out = CustomRoundingFn { id: Some( id.to_string() ), func: Arc::new( func ) };
+		register_any( id, out.clone() );
+		
+		out
+	}
+	
+	/// returns a handle to the function registered under `id`
+	#[inline]
+	pub fn registered ( id: &str ) -> Option< Self > {
+		registered_any( id )
+	}
+	
+	/// id of a registered function
+	#[inline]
+	pub fn id ( &self ) -> Option< &str > {
+		self.id.as_deref()
+	}
+	
+	#[inline]
+	pub fn get ( &self ) -> &dyn RoundingFn {
+		self.func.as_ref()
+	}
+}
+
+impl< T: RoundingFn + 'static > From< T > for CustomRoundingFn {
+	#[inline]
+	fn from( func: T ) -> Self {
+		CustomRoundingFn::new( func )
+	}
+}
+
+impl PartialEq for CustomRoundingFn {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		handle_eq( &self.func, self.id(), &other.func, other.id() )
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Serialize, Deserialize )]
+struct CustomRoundingFnRepr {
+	rounding_fn: String,
+}
+
+#[cfg( feature = "serde" )]
+impl Serialize for CustomRoundingFn {
+	#[inline]
+	fn serialize< S: Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error > {
+		match &self.id {
+			Some( id ) => CustomRoundingFnRepr { rounding_fn: id.clone() }.serialize( serializer ),
+			None => Err( serde::ser::Error::custom( CsInvalidValue::NotAllowed( "rounding_fn".to_string() ) ) ),
+		}
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl< 'de > Deserialize< 'de > for CustomRoundingFn {
+	#[inline]
+	fn deserialize< D: Deserializer< 'de > >( deserializer: D ) -> Result< Self, D::Error > {
+		let repr = CustomRoundingFnRepr::deserialize( deserializer )?;
+		
+		CustomRoundingFn::registered( &repr.rounding_fn ).ok_or_else( || {
+			serde::de::Error::custom( CsInvalidValue::NotAllowed( "rounding_fn".to_string() ) )
+		})
+	}
+}
+
+// trait - RoundingFn
+//------------------------------------------------------------------------------
+// struct - DrawCounter
+
+/// Number of draws of stochastic rounding, the state of its' SplitMix64 generator.
+/// Atomic, so a `RoundingHelper` shared by several stats advances it through `&self`.
+#[derive( Debug, Default )]
+pub( crate ) struct DrawCounter( AtomicU64 );
+
+impl DrawCounter {
+	/// returns the number of previous draws and counts a new one
+	#[inline]
+	fn next ( &self ) -> u64 {
+		self.0.fetch_add( 1, Ordering::Relaxed )
+	}
+	
+	#[inline]
+	fn get ( &self ) -> u64 {
+		self.0.load( Ordering::Relaxed )
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[inline]
+	fn is_zero ( &self ) -> bool {
+		self.get() == 0
+	}
+}
+
+/// clone continues the sequence of the original
+impl Clone for DrawCounter {
+	#[inline]
+	fn clone( &self ) -> Self {
+		DrawCounter( AtomicU64::new( self.get() ) )
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl Serialize for DrawCounter {
+	#[inline]
+	fn serialize< S: Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error > {
+		serializer.serialize_u64( self.get() )
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl< 'de > Deserialize< 'de > for DrawCounter {
+	#[inline]
+	fn deserialize< D: Deserializer< 'de > >( deserializer: D ) -> Result< Self, D::Error > {
+		u64::deserialize( deserializer ).map( |tmp| DrawCounter( AtomicU64::new( tmp ) ) )
+	}
+}
+
+// struct - DrawCounter
+//------------------------------------------------------------------------------
+// struct - RoundingHelper
+
+/// Configuration of rounding.
+/// 
+/// Algorithm: `round_fn( ( value - offset ) / precision ) * precision + offset`. <br>
+/// When significant figures are set, precision is derived from the magnitude of the value instead.
+/// 
+/// Only a registered custom function can be serialized, see `CustomRoundingFn`.
+/// Deserialization of `Custom` without a function fails.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[cfg_attr( feature = "serde", serde( try_from = "RoundingHelperRepr" ) )]
+#[derive( Debug, Clone )]
+pub struct RoundingHelper {
+	pub( crate ) function: RoundingFnEnum,
+	pub( crate ) precision: Option< f64 >,
+	pub( crate ) offset: f64,
+	pub( crate ) significant_figures: Option< u32 >,
+	pub( crate ) seed: u64,
+	#[cfg_attr( feature = "serde", serde( skip_serializing_if = "DrawCounter::is_zero" ) )]
+	pub( crate ) draws: DrawCounter,
+	#[cfg_attr( feature = "serde", serde( skip_serializing_if = "Option::is_none" ) )]
+	pub( crate ) custom_fn: Option< CustomRoundingFn >,
+}
+
+impl RoundingHelper {
+	/// `precision` of 0.0, infinity or NaN is ignored.
+	#[inline]
+	pub fn new ( function: RoundingFnEnum, precision: Option< f64 >, ) -> Self {
+		RoundingHelper {
+			function,
+			precision: valid_precision( precision ),
+			offset: 0.0,
+			significant_figures: None,
+			seed: 0,
+			draws: DrawCounter::default(),
+			custom_fn: None,
+		}
+	}
+	
+	#[inline]
+	pub fn new_none() -> Self {
+		RoundingHelper::new( RoundingFnEnum::None, None )
+	}
+	
+	/// Stochastic rounding, every rounded value takes the next number of a SplitMix64 generator seeded with `seed`.
+	/// Helpers with the same seed and number of draws produce the same results, the number of draws is serialized.
+	#[inline]
+	pub fn new_stochastic ( seed: u64, precision: Option< f64 > ) -> Self {
+		let mut out = RoundingHelper::new( RoundingFnEnum::Stochastic, precision );
+		out.seed = seed;
+		
+		out
+	}
+	
+	/// `func` is a `RoundingFn` or a `CustomRoundingFn`, only a registered one can be serialized
+	#[inline]
+	pub fn new_custom ( func: impl Into< CustomRoundingFn >, precision: Option< f64 > ) -> Self {
+		let mut out = RoundingHelper::new( RoundingFnEnum::Custom, precision );
+		out.custom_fn = Some( func.into() );
+		
+		out
+	}
+	
+	/// Shifts the grid of multiples, precision of 5.0 with offset of 3.0 rounds to ..., -2, 3, 8, 13, ...
+	/// NaN is ignored.
+	#[inline]
+	#[must_use]
+	pub fn with_offset ( mut self, offset: f64 ) -> Self {
+		if !offset.is_nan() {
+			self.offset = offset;
+		}
+		
+		self
+	}
+	
+	/// Rounds to `figures` significant figures, replaces precision. 0 disables it.
+	#[inline]
+	#[must_use]
+	pub fn with_significant_figures ( mut self, figures: u32 ) -> Self {
+		self.significant_figures = if figures == 0 { None } else { Some( figures ) };
+		self
+	}
+	
+	#[inline]
+	pub fn function ( &self ) -> RoundingFnEnum {
+		self.function
+	}
+	
+	#[inline]
+	pub fn precision ( &self ) -> Option< f64 > {
+		self.precision
+	}
+	
+	#[inline]
+	pub fn offset ( &self ) -> f64 {
+		self.offset
+	}
+	
+	#[inline]
+	pub fn significant_figures ( &self ) -> Option< u32 > {
+		self.significant_figures
+	}
+	
+	#[inline]
+	pub fn seed ( &self ) -> u64 {
+		self.seed
+	}
+	
+	/// number of values rounded by stochastic rounding
+	#[inline]
+	pub fn draws ( &self ) -> u64 {
+		self.draws.get()
+	}
+	
+	#[inline]
+	pub fn custom_fn ( &self ) -> Option< &CustomRoundingFn > {
+		self.custom_fn.as_ref()
+	}
+	
+	#[inline]
+	pub fn round ( &self, value: f64 ) -> f64 {
+		self.do_rounding( value )
+	}
+	
+	pub( crate ) fn do_rounding( &self, value: f64 ) -> f64 {
+		if let RoundingFnEnum::None = self.function {
+			return value;
+		}
+		
+		let mut tmp = value - self.offset;
+		
+		let prec = match self.significant_figures {
+			Some(_) if tmp == 0.0 || !tmp.is_finite() => return value,
+			Some( figures ) => 10.0_f64.powf( tmp.abs().log10().floor() - f64::from( figures ) + 1.0 ),
+			None => self.precision.unwrap_or( 1.0 ),
+		};
+		
+		tmp /= prec;
+		
+		tmp = match self.function {
+			RoundingFnEnum::Stochastic => {
+				let floor = tmp.floor();
+				
+				if stochastic_draw( self.seed, self.draws.next() ) < tmp - floor { floor + 1.0 } else { floor }
+			},
+			RoundingFnEnum::Custom => match &self.custom_fn {
+				Some( func ) => func.get().round( tmp ),
+				None => tmp,
+			},
+			_ => self.function.do_rounding( tmp ),
+		};
+		
+		tmp * prec + self.offset
+	}
+}
+
+impl Display for RoundingHelper {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		write!( f, "RoundingHelper = ({:?}", self.function )?;
+		
+		match ( self.significant_figures, self.precision ) {
+			( Some( figures ), _ ) => write!( f, ", significant figures: {figures}" )?,
+			( None, Some( precision ) ) => write!( f, ", precision: {precision}" )?,
+			( None, None ) => {},
+		}
+		
+		if self.offset != 0.0 {
+			write!( f, ", offset: {}", self.offset )?;
+		}
+		
+		f.write_str( ")" )
+	}
+}
+
+/// Compares the configuration, the number of draws is ignored.
+impl PartialEq for RoundingHelper {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		let RoundingHelper { function, precision, offset, significant_figures, seed, draws: _, custom_fn } = self;
+		
+		*function == other.function && *precision == other.precision && *offset == other.offset &&
+		*significant_figures == other.significant_figures && *seed == other.seed && *custom_fn == other.custom_fn
+	}
+}
+
+impl Default for RoundingHelper {
+	#[inline]
+	fn default() -> Self {
+		RoundingHelper::new_none()
+	}
+}
+
+/// fields added later are optional
+#[cfg( feature = "serde" )]
+#[derive( Deserialize )]
+struct RoundingHelperRepr {
+	function: RoundingFnEnum,
+	precision: Option< f64 >,
+	#[serde( default )]
+	offset: f64,
+	#[serde( default )]
+	significant_figures: Option< u32 >,
+	#[serde( default )]
+	seed: u64,
+	#[serde( default )]
+	draws: DrawCounter,
+	#[serde( default )]
+	custom_fn: Option< CustomRoundingFn >,
+}
+
+#[cfg( feature = "serde" )]
+impl TryFrom< RoundingHelperRepr > for RoundingHelper {
+	type Error = CharStatError;
+	
+	#[inline]
+	fn try_from( repr: RoundingHelperRepr ) -> Result< Self, Self::Error > {
+		if repr.function == RoundingFnEnum::Custom && repr.custom_fn.is_none() {
+			return Err( CsInvalidValue::NotAllowed( "custom_fn".to_string() ).into() )
+		}
+		
+		Ok( RoundingHelper {
+			function: repr.function,
+			precision: valid_precision( repr.precision ),
+			offset: repr.offset,
+			significant_figures: repr.significant_figures,
+			seed: repr.seed,
+			draws: repr.draws,
+			custom_fn: repr.custom_fn,
+		} )
+	}
+}
+
+// priv
+/// precision of 0.0 would divide by zero, infinite one returns NaN
+#[inline]
+#[doc( hidden )]
+fn valid_precision ( precision: Option< f64 > ) -> Option< f64 > {
+	precision.filter( |tmp| tmp.is_finite() && *tmp != 0.0 )
+}
+
+/// output number `n` of the SplitMix64 generator seeded with `seed`, returns a number in range [ 0.0, 1.0 )
+#[inline( always )]
+#[doc( hidden )]
+fn stochastic_draw ( seed: u64, n: u64 ) -> f64 {
+	let mut z = seed.wrapping_add( n.wrapping_add( 1 ).wrapping_mul( 0x9E37_79B9_7F4A_7C15 ) );
+	z = ( z ^ ( z >> 30 ) ).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+	z = ( z ^ ( z >> 27 ) ).wrapping_mul( 0x94D0_49BB_1331_11EB );
+	z ^= z >> 31;
+	
+	// 52 random bits as mantissa of a number in [ 1.0, 2.0 )
+	f64::from_bits( 0x3FF0_0000_0000_0000 | ( z >> 12 ) ) - 1.0
+}
+
+// struct - RoundingHelper
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	
+	#[test]
+	fn basic_functional() {
+		let offset = RoundingHelper::new( RoundingFnEnum::Round, Some( 5.0 ) ).with_offset( 3.0 );
+		assert_eq!( offset.round( 9.0 ), 8.0 );
+		assert_eq!( offset.round( 11.0 ), 13.0 );
+		assert_eq!( offset.round( 0.0 ), -2.0 );
+		
+		let away = RoundingHelper::new( RoundingFnEnum::AwayFromZero, None );
+		assert_eq!( away.round( 1.1 ), 2.0 );
+		assert_eq!( away.round( -1.1 ), -2.0 );
+		
+		let sig = RoundingHelper::new( RoundingFnEnum::Round, Some( 0.1 ) ).with_significant_figures( 2 );
+		assert_eq!( sig.round( 123_456.0 ), 120_000.0 );
+		assert_eq!( sig.round( -0.012_34 ), -0.012 );
+		assert_eq!( sig.round( 0.0 ), 0.0 );
+	}
+	
+	#[test]
+	fn stochastic() {
+		let a = RoundingHelper::new_stochastic( 42, None );
+		let b = RoundingHelper::new_stochastic( 42, None );
+		
+		let mut ups = 0;
+		
+		// the same value is rounded up in about a quarter of draws
+		for _ in 0..1000 {
+			let tmp = a.round( 2.25 );
+			
+			// same seed, same sequence
+			assert_eq!( tmp, b.round( 2.25 ) );
+			assert!( tmp == 2.0 || tmp == 3.0 );
+			
+			if tmp > 2.25 {
+				ups += 1;
+			}
+		}
+		
+		// expected 250
+		assert!( ( 200..300 ).contains( &ups ) );
+		assert_eq!( a.draws(), 1000 );
+		
+		// other seed
+		let c = RoundingHelper::new_stochastic( 43, None );
+		let d = RoundingHelper::new_stochastic( 42, None );
+		assert!( ( 0..1000 ).any( |_| c.round( 0.5 ) != d.round( 0.5 ) ) );
+		
+		// clone continues the sequence, draws are ignored by comparison
+		let e = a.clone();
+		assert_eq!( e, d );
+		assert!( ( 0..100 ).all( |_| a.round( 0.5 ) == e.round( 0.5 ) ) );
+		
+		// whole numbers are never changed
+		assert_eq!( a.round( 3.0 ), 3.0 );
+		
+		// zero and infinite precision are ignored
+		assert_eq!( RoundingHelper::new( RoundingFnEnum::Floor, Some( 0.0 ) ).precision(), None );
+		assert_eq!( RoundingHelper::new( RoundingFnEnum::Floor, Some( f64::INFINITY ) ).round( 2.5 ), 2.0 );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		#[derive( Debug )]
+		struct Half;
+		
+		impl RoundingFn for Half {
+			fn round( &self, value: f64 ) -> f64 {
+				value / 2.0
+			}
+		}
+		
+		let rounding = RoundingHelper::new_stochastic( 7, Some( 0.5 ) ).with_offset( 0.25 ).with_significant_figures( 3 );
+		
+		let serialized = serde_json::to_string( &rounding ).unwrap();
+		let deserialized: RoundingHelper = serde_json::from_str( &serialized ).unwrap();
+		assert_eq!( rounding, deserialized );
+		assert!( !serialized.contains( "draws" ) );
+		
+		// the generator continues where it stopped
+		rounding.round( 1.5 );
+		let deserialized: RoundingHelper = serde_json::from_str( &serde_json::to_string( &rounding ).unwrap() ).unwrap();
+		assert_eq!( deserialized.draws(), 1 );
+		assert!( ( 0..100 ).all( |_| rounding.round( 1.5 ) == deserialized.round( 1.5 ) ) );
+		
+		let zero: RoundingHelper = serde_json::from_str( r#"{ "function": "Floor", "precision": 0.0 }"# ).unwrap();
+		assert_eq!( zero.precision(), None );
+		
+		// fields added later are optional
+		let old: RoundingHelper = serde_json::from_str( r#"{ "function": "Floor", "precision": null }"# ).unwrap();
+		assert_eq!( old, RoundingHelper::new( RoundingFnEnum::Floor, None ) );
+		
+		// custom function is stored by id
+		let unregistered = RoundingHelper::new_custom( Half, None );
+		assert!( serde_json::to_string( &unregistered ).is_err() );
+		
+		let custom = RoundingHelper::new_custom( CustomRoundingFn::register( "rounding_half", Half ), None );
+		let serialized = serde_json::to_string( &custom ).unwrap();
+		assert!( serialized.contains( r#""custom_fn":{"rounding_fn":"rounding_half"}"# ) );
+		
+		let deserialized: RoundingHelper = serde_json::from_str( &serialized ).unwrap();
+		assert_eq!( deserialized.round( 3.0 ), 1.5 );
+		assert_eq!( deserialized.custom_fn().and_then( CustomRoundingFn::id ), Some( "rounding_half" ) );
+		
+		// missing or unknown function is an error
+		assert!( serde_json::from_str::< RoundingHelper >( r#"{ "function": "Custom", "precision": null }"# ).is_err() );
+		assert!( serde_json::from_str::< RoundingHelper >(
+			r#"{ "function": "Custom", "precision": null, "custom_fn": { "rounding_fn": "unknown" } }"#
+		).is_err() );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,353 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStat, RoundingFnEnum, CharStatError, CsInvalidValue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// consts
+
+/// Proficiency bonus for character levels 1 to 20.
+pub const PROFICIENCY_TABLE: [ f64; 20 ] = [
+	2.0, 2.0, 2.0, 2.0,
+	3.0, 3.0, 3.0, 3.0,
+	4.0, 4.0, 4.0, 4.0,
+	5.0, 5.0, 5.0, 5.0,
+	6.0, 6.0, 6.0, 6.0,
+];
+
+const D20_MIN: u8 = 1;
+const D20_MAX: u8 = 20;
+
+// consts
+//------------------------------------------------------------------------------
+// fn - helpers
+
+/// Returns the ability modifier of a score: `floor( ( score - 10 ) / 2 )`.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ CharStat, BaseConf, Bounds, RoundingHelper, ruleset };
+/// 
+/// let bounds = Bounds::new_const( 1.0, 30.0 ).unwrap();
+/// let base = BaseConf::new( 9.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+/// let cs = CharStat::new_minimal( base );
+/// 
+/// assert_eq!( ruleset::ability_modifier( &cs ), -1.0 );
+/// ```
+#[inline]
+pub fn ability_modifier ( stat: &CharStat ) -> f64 {
+	score_modifier( stat.value() )
+}
+
+/// Same as `ability_modifier`, but takes the score directly.
+#[inline]
+pub fn score_modifier ( score: f64 ) -> f64 {
+	RoundingFnEnum::Floor.do_rounding( ( score - 10.0 ) / 2.0 )
+}
+
+/// Returns the proficiency bonus for `level`.
+/// 
+/// # Errors
+/// `CsInvalidValue::BelowMinimum` when `level` is 0 <br>
+/// `CsInvalidValue::AboveMaximum` when `level` is greater than 20 <br>
+#[inline]
+pub fn proficiency_bonus ( level: u32 ) -> Result< f64, CharStatError > {
+	if level == 0 {
+		return Err( CsInvalidValue::BelowMinimum( "level".to_string() ).into() )
+	}
+	
+	let idx = usize::try_from( level - 1 ).unwrap_or( usize::MAX );
+	
+	if let Some( bonus ) = PROFICIENCY_TABLE.get( idx ) {
+		return Ok( *bonus )
+	}
+	
+	Err( CsInvalidValue::AboveMaximum( "level".to_string() ).into() )
+}
+
+// fn - helpers
+//------------------------------------------------------------------------------
+// fn - checks
+
+/// Rolls d20 with `roller`, adds `modifier` and compares the total against `dc`.
+/// 
+/// `roller` is called once for `RollMode::Normal` and twice otherwise, every roll must be within 1..=20.
+/// 
+/// # Errors
+/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `roller` returns a value outside of 1..=20 <br>
+#[inline]
+pub fn d20_check< R > ( modifier: f64, dc: f64, mode: RollMode, mut roller: R ) -> Result< CheckResult, CharStatError >
+where
+	R: FnMut() -> u8,
+{
+	let first = check_roll( roller() )?;
+	
+	let natural = match mode {
+		RollMode::Normal => first,
+		RollMode::Advantage => first.max( check_roll( roller() )? ),
+		RollMode::Disadvantage => first.min( check_roll( roller() )? ),
+	};
+	
+	let critical = match natural {
+		D20_MAX => Critical::Success,
+		D20_MIN => Critical::Failure,
+		_ => Critical::None,
+	};
+	
+	let total = f64::from( natural ) + modifier;
+	
+	Ok( CheckResult {
+		natural,
+		modifier,
+		total,
+		dc,
+		success: total >= dc,
+		critical,
+	})
+}
+
+/// Saving throw: ability modifier of `stat`, plus proficiency bonus for `level` when `proficient`.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ CharStat, BaseConf, Bounds, RoundingHelper, ruleset::{ self, RollMode, Critical } };
+/// 
+/// let bounds = Bounds::new_const( 1.0, 30.0 ).unwrap();
+/// let base = BaseConf::new( 14.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+/// let dex = CharStat::new_minimal( base );
+/// 
+/// // +2 from dex, +2 from proficiency at level 1
+/// let mut rolls = [ 7, 11 ].into_iter();
+/// let res = ruleset::saving_throw( &dex, 1, true, 15.0, RollMode::Advantage, || rolls.next().unwrap() ).unwrap();
+/// 
+/// assert_eq!( res.natural(), 11 );
+/// assert_eq!( res.total(), 15.0 );
+/// assert!( res.success() );
+/// assert_eq!( res.critical(), Critical::None );
+/// ```
+/// 
+/// # Errors
+/// Errors from `proficiency_bonus` and `d20_check` <br>
+#[inline]
+pub fn saving_throw< R > ( stat: &CharStat, level: u32, proficient: bool, dc: f64, mode: RollMode, roller: R ) -> Result< CheckResult, CharStatError >
+where
+	R: FnMut() -> u8,
+{
+	let proficiency = if proficient {
+		Proficiency::Proficient
+	} else {
+		Proficiency::None
+	};
+	
+	skill_check( stat, level, proficiency, dc, mode, roller )
+}
+
+/// Skill check: ability modifier of `stat`, plus proficiency bonus for `level` scaled by `proficiency`.
+/// 
+/// # Errors
+/// Errors from `proficiency_bonus` and `d20_check` <br>
+#[inline]
+pub fn skill_check< R > ( stat: &CharStat, level: u32, proficiency: Proficiency, dc: f64, mode: RollMode, roller: R ) -> Result< CheckResult, CharStatError >
+where
+	R: FnMut() -> u8,
+{
+	let bonus = proficiency.apply( proficiency_bonus( level )? );
+	
+	d20_check( ability_modifier( stat ) + bonus, dc, mode, roller )
+}
+
+#[inline( always )]
+#[doc( hidden )]
+fn check_roll ( roll: u8 ) -> Result< u8, CharStatError > {
+	if roll < D20_MIN {
+		return Err( CsInvalidValue::BelowMinimum( "roll".to_string() ).into() )
+	}
+	
+	if roll > D20_MAX {
+		return Err( CsInvalidValue::AboveMaximum( "roll".to_string() ).into() )
+	}
+	
+	Ok( roll )
+}
+
+// fn - checks
+//------------------------------------------------------------------------------
+// enum - RollMode
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum RollMode {
+	Normal,
+	/// roll twice, keep higher
+	Advantage,
+	/// roll twice, keep lower
+	Disadvantage,
+}
+
+// enum - RollMode
+//------------------------------------------------------------------------------
+// enum - Proficiency
+
+/// How much of the proficiency bonus is added to a check.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum Proficiency {
+	None,
+	/// half of the bonus, rounded down
+	Half,
+	Proficient,
+	/// double the bonus
+	Expertise,
+}
+
+impl Proficiency {
+	#[inline]
+	pub fn apply ( self, bonus: f64 ) -> f64 {
+		match self {
+			Self::None => 0.0,
+			Self::Half => RoundingFnEnum::Floor.do_rounding( bonus / 2.0 ),
+			Self::Proficient => bonus,
+			Self::Expertise => bonus * 2.0,
+		}
+	}
+}
+
+// enum - Proficiency
+//------------------------------------------------------------------------------
+// enum - Critical
+
+/// Natural 20 is `Success`, natural 1 is `Failure`. <br>
+/// It does not affect `CheckResult::success`, whether a crit means auto success is up to the caller.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum Critical {
+	None,
+	Success,
+	Failure,
+}
+
+// enum - Critical
+//------------------------------------------------------------------------------
+// struct - CheckResult
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct CheckResult {
+	natural: u8,
+	modifier: f64,
+	total: f64,
+	dc: f64,
+	success: bool,
+	critical: Critical,
+}
+
+impl CheckResult {
+	/// the d20 roll that was kept
+	#[inline]
+	pub fn natural ( &self ) -> u8 {
+		self.natural
+	}
+	
+	#[inline]
+	pub fn modifier ( &self ) -> f64 {
+		self.modifier
+	}
+	
+	/// `natural` + `modifier`
+	#[inline]
+	pub fn total ( &self ) -> f64 {
+		self.total
+	}
+	
+	#[inline]
+	pub fn dc ( &self ) -> f64 {
+		self.dc
+	}
+	
+	/// `total` >= `dc`
+	#[inline]
+	pub fn success ( &self ) -> bool {
+		self.success
+	}
+	
+	#[inline]
+	pub fn critical ( &self ) -> Critical {
+		self.critical
+	}
+}
+
+// struct - CheckResult
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	use crate::{ BaseConf, Bounds, RoundingHelper };
+	
+	fn stat( score: f64 ) -> CharStat {
+		let bounds = Bounds::new_const( 1.0, 30.0 ).unwrap();
+		let base = BaseConf::new( score, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+		
+		CharStat::new_minimal( base )
+	}
+	
+	#[test]
+	fn modifiers_and_proficiency() {
+		let expected = [ ( 1.0, -5.0 ), ( 8.0, -1.0 ), ( 9.0, -1.0 ), ( 10.0, 0.0 ), ( 11.0, 0.0 ), ( 15.0, 2.0 ), ( 20.0, 5.0 ), ( 30.0, 10.0 ) ];
+		
+		for ( score, modifier ) in expected {
+			assert_eq!( ability_modifier( &stat( score ) ), modifier );
+		}
+		
+		assert_eq!( proficiency_bonus( 1 ), Ok( 2.0 ) );
+		assert_eq!( proficiency_bonus( 4 ), Ok( 2.0 ) );
+		assert_eq!( proficiency_bonus( 5 ), Ok( 3.0 ) );
+		assert_eq!( proficiency_bonus( 17 ), Ok( 6.0 ) );
+		assert_eq!( proficiency_bonus( 20 ), Ok( 6.0 ) );
+		
+		let expected: CharStatError = CsInvalidValue::BelowMinimum( "level".to_string() ).into();
+		assert_eq!( proficiency_bonus( 0 ), Err( expected ) );
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "level".to_string() ).into();
+		assert_eq!( proficiency_bonus( 21 ), Err( expected ) );
+		
+		assert_eq!( Proficiency::Half.apply( 3.0 ), 1.0 );
+		assert_eq!( Proficiency::Expertise.apply( 3.0 ), 6.0 );
+	}
+	
+	#[test]
+	fn checks() {
+		let wis = stat( 12.0 );
+		
+		// +1 wis, +3 * 2 expertise at level 5
+		let res = skill_check( &wis, 5, Proficiency::Expertise, 20.0, RollMode::Normal, || 13 ).unwrap();
+		assert_eq!( res.total(), 20.0 );
+		assert!( res.success() );
+		
+		let mut rolls = [ 20, 1 ].into_iter();
+		let res = saving_throw( &wis, 1, false, 10.0, RollMode::Disadvantage, || rolls.next().unwrap() ).unwrap();
+		assert_eq!( res.natural(), 1 );
+		assert_eq!( res.critical(), Critical::Failure );
+		assert!( !res.success() );
+		
+		let mut rolls = [ 1, 20 ].into_iter();
+		let res = saving_throw( &wis, 1, false, 25.0, RollMode::Advantage, || rolls.next().unwrap() ).unwrap();
+		assert_eq!( res.critical(), Critical::Success );
+		assert!( !res.success() );
+		
+		let expected: CharStatError = CsInvalidValue::AboveMaximum( "roll".to_string() ).into();
+		assert_eq!( d20_check( 0.0, 10.0, RollMode::Normal, || 21 ), Err( expected ) );
+		let expected: CharStatError = CsInvalidValue::BelowMinimum( "roll".to_string() ).into();
+		assert_eq!( d20_check( 0.0, 10.0, RollMode::Normal, || 0 ), Err( expected ) );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,322 @@
+use std::any::{ Any, TypeId };
+use std::collections::HashMap;
+use std::fmt::{ Debug, Formatter };
+use std::ops::Deref;
+use std::sync::{ Arc, OnceLock, RwLock };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize, Serializer, Deserializer };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+#[cfg( feature = "serde" )]
+use crate::CsInvalidValue;
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - Shared
+
+#[derive( Clone )]
+struct SharedInner< T > {
+	id: Option< String >,
+	value: T,
+}
+
+/// Immutable configuration shared by many components, e.g. bounds of a stat used by every NPC.
+/// 
+/// Clones point to the same value, a mutation copies it first ( copy-on-write ) and the copy is no longer registered.
+/// Components accept `T` wherever they accept `Shared< T >`, such value is not shared.
+/// 
+/// Only configuration is shared, `T` must not hold per-entity state.
+/// 
+/// With serde a registered value is stored as `{ "shared": id }`, other values as plain `T`, same as before `Shared` was introduced.
+/// Deserialization of a reference requires registering the value with the same id first.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ Shared, BaseConf, Bounds, RoundingHelper };
+/// 
+/// let bounds = Shared::register( "npc_strength", Bounds::new( 0.0, 20.0, true, true ).unwrap() );
+/// let mut first = BaseConf::new( 10.0, true, bounds.clone(), RoundingHelper::new_none(), None ).unwrap();
+/// let second = BaseConf::new( 12.0, true, bounds, RoundingHelper::new_none(), None ).unwrap();
+/// 
+/// // only `first` is changed
+/// first.set_bounds_max( 15.0 ).unwrap();
+/// assert_eq!( first.bounds_max(), 15.0 );
+/// assert_eq!( second.bounds_max(), 20.0 );
+/// assert_eq!( Shared::< Bounds >::registered( "npc_strength" ).unwrap().max(), 20.0 );
+/// ```
+pub struct Shared< T >( Arc< SharedInner< T > > );
+
+impl< T > Shared< T > {
+	/// value which is not registered
+	#[inline]
+	pub fn new ( value: T ) -> Self {
+		Shared( Arc::new( SharedInner { id: None, value } ) )
+	}
+	
+	/// id of a registered value
+	#[inline]
+	pub fn id ( &self ) -> Option< &str > {
+		self.0.id.as_deref()
+	}
+	
+	/// returns true when both point to the same value
+	#[inline]
+	pub fn ptr_eq ( &self, other: &Self ) -> bool {
+		Arc::ptr_eq( &self.0, &other.0 )
+	}
+}
+
+impl< T: Clone + PartialEq > Shared< T > {
+	/// Calls `f` with a copy of the value, which replaces the shared value only when it was changed.
+	#[inline]
+	pub fn update ( &mut self, f: impl FnOnce( &mut T ) ) {
+		let mut tmp = self.0.value.clone();
+		f( &mut tmp );
+		
+		self.replace( tmp );
+	}
+	
+	/// Same as `update`, the value is not changed when `f` fails.
+	/// 
+	/// # Errors
+	/// any error returned by `f` <br>
+	#[inline]
+	pub fn try_update < R, E > ( &mut self, f: impl FnOnce( &mut T ) -> Result< R, E > ) -> Result< R, E > {
+		let mut tmp = self.0.value.clone();
+		let out = f( &mut tmp )?;
+		
+		self.replace( tmp );
+		
+		Ok( out )
+	}
+	
+	#[inline]
+	#[doc( hidden )]
+	fn replace ( &mut self, value: T ) {
+		if value != self.0.value {
+			*self = Shared::new( value );
+		}
+	}
+}
+
+impl< T: Send + Sync + 'static > Shared< T > {
+	/// Registers `value` under `id` and returns a handle to it.
+	/// Registering the same id again replaces the value for new handles, existing handles keep the old one.
+	#[inline]
+	pub fn register ( id: &str, value: T ) -> Self {
+		let out = Shared( Arc::new( SharedInner { id: Some( id.to_string() ), value } ) );
+		register_any( id, out.clone() );
+		
+		out
+	}
+	
+	/// returns a handle to the value registered under `id`
+	#[inline]
+	pub fn registered ( id: &str ) -> Option< Self > {
+		registered_any( id )
+	}
+}
+
+impl< T > Clone for Shared< T > {
+	#[inline]
+	fn clone( &self ) -> Self {
+		Shared( Arc::clone( &self.0 ) )
+	}
+}
+
+impl< T > Deref for Shared< T > {
+	type Target = T;
+	
+	#[inline]
+	fn deref( &self ) -> &T {
+		&self.0.value
+	}
+}
+
+impl< T > From< T > for Shared< T > {
+	#[inline]
+	fn from( value: T ) -> Self {
+		Shared::new( value )
+	}
+}
+
+impl< T: PartialEq > PartialEq for Shared< T > {
+	#[inline]
+	fn eq( &self, other: &Self ) -> bool {
+		Arc::ptr_eq( &self.0, &other.0 ) || self.0.value == other.0.value
+	}
+}
+
+impl< T: Debug > Debug for Shared< T > {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		self.0.value.fmt( f )
+	}
+}
+
+impl< T: Default > Default for Shared< T > {
+	#[inline]
+	fn default() -> Self {
+		Shared::new( T::default() )
+	}
+}
+
+// struct - Shared
+//------------------------------------------------------------------------------
+// serde
+
+#[cfg( feature = "serde" )]
+#[derive( Serialize, Deserialize )]
+#[serde( deny_unknown_fields )]
+struct SharedId< I > {
+	shared: I,
+}
+
+/// plain `T` keeps loading saves written before `Shared` existed
+#[cfg( feature = "serde" )]
+#[derive( Deserialize )]
+#[serde( untagged )]
+enum SharedRepr< T > {
+	Shared( SharedId< String > ),
+	Inline( T ),
+}
+
+#[cfg( feature = "serde" )]
+impl< T: Serialize > Serialize for Shared< T > {
+	#[inline]
+	fn serialize< S: Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error > {
+		match &self.0.id {
+			Some( id ) => SharedId { shared: id.as_str() }.serialize( serializer ),
+			None => self.0.value.serialize( serializer ),
+		}
+	}
+}
+
+#[cfg( feature = "serde" )]
+impl< 'de, T: Deserialize<'de> + Send + Sync + 'static > Deserialize<'de> for Shared< T > {
+	#[inline]
+	fn deserialize< D: Deserializer<'de> >( deserializer: D ) -> Result< Self, D::Error > {
+		match SharedRepr::< T >::deserialize( deserializer )? {
+			SharedRepr::Shared( SharedId { shared } ) => Shared::registered( &shared ).ok_or_else( || {
+				serde::de::Error::custom( CsInvalidValue::NotAllowed( "shared".to_string() ) )
+			}),
+			SharedRepr::Inline( value ) => Ok( Shared::new( value ) ),
+		}
+	}
+}
+
+// serde
+//------------------------------------------------------------------------------
+// registry
+
+type Registry = HashMap< ( TypeId, String ), Box< dyn Any + Send + Sync > >;
+
+fn registry () -> &'static RwLock< Registry > {
+	static REGISTRY: OnceLock< RwLock< Registry > > = OnceLock::new();
+	
+	REGISTRY.get_or_init( || RwLock::new( HashMap::new() ) )
+}
+
+/// Stores `value` under `id`, values of different types do not collide.
+#[inline]
+#[doc( hidden )]
+pub( crate ) fn register_any < T: Send + Sync + 'static > ( id: &str, value: T ) {
+	let mut map = registry().write().unwrap_or_else( std::sync::PoisonError::into_inner );
+	
+	map.insert( ( TypeId::of::< T >(), id.to_string() ), Box::new( value ) );
+}
+
+/// returns a copy of the value of type `T` stored under `id`
+#[inline]
+#[doc( hidden )]
+pub( crate ) fn registered_any < T: Clone + 'static > ( id: &str ) -> Option< T > {
+	let map = registry().read().unwrap_or_else( std::sync::PoisonError::into_inner );
+	
+	map.get( &( TypeId::of::< T >(), id.to_string() ) )?.downcast_ref::< T >().cloned()
+}
+
+// registry
+//------------------------------------------------------------------------------
+// handle equality
+
+/// Equality of handles to trait objects, which cannot be compared by value. <br>
+/// Handles are equal when they point to the same object or when both have the same registry key.
+#[inline]
+#[doc( hidden )]
+pub( crate ) fn handle_eq < T: ?Sized, K: PartialEq + ?Sized > ( a: &Arc< T >, a_key: Option< &K >, b: &Arc< T >, b_key: Option< &K > ) -> bool {
+	Arc::ptr_eq( a, b ) || ( a_key.is_some() && a_key == b_key )
+}
+
+// handle equality
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use crate::{ Shared, Bounds, RoundingHelper, CharStatError, CsLogicIssue };
+	
+	#[test]
+	fn basic_functional() {
+		let mut first = Shared::register( "shared_tests_basic", Bounds::new_mut( 0.0, 10.0 ).unwrap() );
+		let second = first.clone();
+		assert!( first.ptr_eq( &second ) );
+		assert_eq!( first.id(), Some( "shared_tests_basic" ) );
+		
+		// failed and unchanged updates keep the value shared
+		let expected: CharStatError = CsLogicIssue::MinGreaterThanMax.into();
+		assert_eq!( first.try_update( |tmp| tmp.set_min( 20.0 ) ), Err( expected ) );
+		first.update( |tmp| tmp.set_max( 10.0 ).unwrap() );
+		assert!( first.ptr_eq( &second ) );
+		
+		first.update( |tmp| tmp.set_max( 5.0 ).unwrap() );
+		assert!( !first.ptr_eq( &second ) );
+		assert_eq!( first.id(), None );
+		assert_eq!( first.max(), 5.0 );
+		assert_eq!( second.max(), 10.0 );
+		
+		assert!( Shared::< Bounds >::registered( "shared_tests_basic" ).unwrap().ptr_eq( &second ) );
+		assert!( Shared::< Bounds >::registered( "missing" ).is_none() );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let bounds = Bounds::new_const( 1.0, 2.0 ).unwrap();
+		let registered = Shared::register( "shared_tests_serde", bounds );
+		let inline = Shared::new( bounds );
+		
+		let json = serde_json::to_string( &( registered.clone(), inline.clone() ) ).unwrap();
+		assert_eq!( json, r#"[{"shared":"shared_tests_serde"},{"v_min":1.0,"v_max":2.0,"is_min_mut":false,"is_max_mut":false}]"# );
+		
+		let out: ( Shared< Bounds >, Shared< Bounds > ) = serde_json::from_str( &json ).unwrap();
+		assert!( out.0.ptr_eq( &registered ) );
+		assert_eq!( out.1, inline );
+		assert_eq!( out.1.id(), None );
+		
+		let err = serde_json::from_str::< Shared< Bounds > >( r#"{"shared":"missing"}"# ).unwrap_err();
+		assert!( err.to_string().contains( "shared" ) );
+		assert!( serde_json::from_str::< Shared< Bounds > >( r#"{"v_min":"one"}"# ).is_err() );
+	}
+	
+	#[test]
+	fn shared_rounding() {
+		// handles sharing stochastic rounding draw from one generator
+		let first = Shared::new( RoundingHelper::new_stochastic( 3, None ) );
+		let second = first.clone();
+		let single = RoundingHelper::new_stochastic( 3, None );
+		
+		let values: Vec< f64 > = ( 0..100 ).map( |i| if i % 2 == 0 { first.round( 0.5 ) } else { second.round( 0.5 ) } ).collect();
+		let again: Vec< f64 > = ( 0..100 ).map( |_| single.round( 0.5 ) ).collect();
+		assert_eq!( values, again );
+		assert_eq!( first.draws(), 100 );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
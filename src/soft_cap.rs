@@ -0,0 +1,288 @@
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ CharStatError, CsInvalidValue, CsLogicIssue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// enum - SoftCapCurve
+
+/// Diminishing returns curve, values at or below the threshold are not changed and capped value is never above input.
+/// - Linear => excess above threshold is multiplied by factor, e.g. 50% above 75
+/// - Hyperbolic => `threshold + ( max - threshold ) * e / ( e + k )` where `e` is excess above threshold, approaches max
+/// - Asymptotic => exponential approach from threshold to max, slope is 1.0 at the threshold
+/// - Power => `threshold + ( 1 + e )^exponent - 1` where `e` is excess above threshold
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub enum SoftCapCurve {
+	Linear{ threshold: f64, factor: f64 },
+	Hyperbolic{ threshold: f64, max: f64, k: f64 },
+	Asymptotic{ threshold: f64, max: f64 },
+	Power{ threshold: f64, exponent: f64 },
+}
+
+// enum - SoftCapCurve
+//------------------------------------------------------------------------------
+// struct - SoftCap
+
+/// Soft cap of `ModConf` or `CharStat` total, applied before rounding and bounds.
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::SoftCap;
+/// 
+/// // full value up to 75, then 50%
+/// let cap = SoftCap::linear( 75.0, 0.5 ).unwrap();
+/// assert_eq!( cap.apply( 50.0 ), 50.0 );
+/// assert_eq!( cap.apply( 95.0 ), 85.0 );
+/// 
+/// // half of the remaining range to 100 when excess is equal to k
+/// let cap = SoftCap::hyperbolic( 50.0, 100.0, 50.0 ).unwrap();
+/// assert_eq!( cap.apply( 100.0 ), 75.0 );
+/// ```
+/// 
+/// Deserialization runs the same checks as the constructors.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[cfg_attr( feature = "serde", serde( try_from = "SoftCapRepr" ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct SoftCap {
+	curve: SoftCapCurve,
+}
+
+impl SoftCap {
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any argument is `f64::NAN` <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `factor` is not within [ 0.0, 1.0 ] <br>
+	#[inline]
+	pub fn linear ( threshold: f64, factor: f64 ) -> Result< Self, CharStatError > {
+		check_nan( threshold, "threshold" )?;
+		check_nan( factor, "factor" )?;
+		
+		if factor < 0.0 {
+			return Err( CsInvalidValue::BelowMinimum( "factor".to_string() ).into() )
+		}
+		
+		if factor > 1.0 {
+			return Err( CsInvalidValue::AboveMaximum( "factor".to_string() ).into() )
+		}
+		
+		Ok( SoftCap{ curve: SoftCapCurve::Linear{ threshold, factor } } )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any argument is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `threshold` >= `max` <br>
+	/// `CsInvalidValue::BelowMinimum` when `k` < `max - threshold`, slope at the threshold would be above 1.0 <br>
+	#[inline]
+	pub fn hyperbolic ( threshold: f64, max: f64, k: f64 ) -> Result< Self, CharStatError > {
+		check_nan( threshold, "threshold" )?;
+		check_nan( max, "max" )?;
+		check_nan( k, "k" )?;
+		
+		if threshold >= max {
+			return Err( CsLogicIssue::MinGreaterThanMax.into() )
+		}
+		
+		if k < max - threshold {
+			return Err( CsInvalidValue::BelowMinimum( "k".to_string() ).into() )
+		}
+		
+		Ok( SoftCap{ curve: SoftCapCurve::Hyperbolic{ threshold, max, k } } )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any argument is `f64::NAN` <br>
+	/// `CsLogicIssue::MinGreaterThanMax` when `threshold` >= `max` <br>
+	#[inline]
+	pub fn asymptotic ( threshold: f64, max: f64 ) -> Result< Self, CharStatError > {
+		check_nan( threshold, "threshold" )?;
+		check_nan( max, "max" )?;
+		
+		if threshold >= max {
+			return Err( CsLogicIssue::MinGreaterThanMax.into() )
+		}
+		
+		Ok( SoftCap{ curve: SoftCapCurve::Asymptotic{ threshold, max } } )
+	}
+	
+	/// # Errors
+	/// `CsInvalidValue::Nan` when any argument is `f64::NAN` <br>
+	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `exponent` is not within ( 0.0, 1.0 ] <br>
+	#[inline]
+	pub fn power ( threshold: f64, exponent: f64 ) -> Result< Self, CharStatError > {
+		check_nan( threshold, "threshold" )?;
+		check_nan( exponent, "exponent" )?;
+		
+		if exponent <= 0.0 {
+			return Err( CsInvalidValue::BelowMinimum( "exponent".to_string() ).into() )
+		}
+		
+		if exponent > 1.0 {
+			return Err( CsInvalidValue::AboveMaximum( "exponent".to_string() ).into() )
+		}
+		
+		Ok( SoftCap{ curve: SoftCapCurve::Power{ threshold, exponent } } )
+	}
+	
+	#[inline]
+	pub fn curve ( &self ) -> SoftCapCurve {
+		self.curve
+	}
+	
+	#[inline]
+	pub fn apply ( &self, value: f64 ) -> f64 {
+		match self.curve {
+			SoftCapCurve::Linear{ threshold, .. } |
+			SoftCapCurve::Hyperbolic{ threshold, .. } |
+			SoftCapCurve::Asymptotic{ threshold, .. } |
+			SoftCapCurve::Power{ threshold, .. } if value <= threshold => value,
+			SoftCapCurve::Linear{ threshold, factor } => threshold + ( value - threshold ) * factor,
+			SoftCapCurve::Hyperbolic{ threshold, max, k } => {
+				let excess = value - threshold;
+				
+				threshold + ( max - threshold ) * excess / ( excess + k )
+			},
+			SoftCapCurve::Asymptotic{ threshold, max } => {
+				let range = max - threshold;
+				
+				threshold + range * ( 1.0 - ( -( value - threshold ) / range ).exp() )
+			},
+			SoftCapCurve::Power{ threshold, exponent } => threshold + ( 1.0 + value - threshold ).powf( exponent ) - 1.0,
+		}
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Deserialize )]
+struct SoftCapRepr {
+	curve: SoftCapCurve,
+}
+
+#[cfg( feature = "serde" )]
+impl TryFrom< SoftCapRepr > for SoftCap {
+	type Error = CharStatError;
+	
+	#[inline]
+	fn try_from( repr: SoftCapRepr ) -> Result< Self, Self::Error > {
+		match repr.curve {
+			SoftCapCurve::Linear{ threshold, factor } => SoftCap::linear( threshold, factor ),
+			SoftCapCurve::Hyperbolic{ threshold, max, k } => SoftCap::hyperbolic( threshold, max, k ),
+			SoftCapCurve::Asymptotic{ threshold, max } => SoftCap::asymptotic( threshold, max ),
+			SoftCapCurve::Power{ threshold, exponent } => SoftCap::power( threshold, exponent ),
+		}
+	}
+}
+
+// priv
+#[inline( always )]
+#[doc( hidden )]
+fn check_nan ( value: f64, name: &str ) -> Result<(), CharStatError > {
+	if value.is_nan() {
+		return Err( CsInvalidValue::Nan( name.to_string() ).into() )
+	}
+	
+	Ok(())
+}
+
+// struct - SoftCap
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	
+	#[test]
+	fn basic_functional() {
+		let linear = SoftCap::linear( 75.0, 0.5 ).unwrap();
+		assert_eq!( linear.apply( -10.0 ), -10.0 );
+		assert_eq!( linear.apply( 175.0 ), 125.0 );
+		
+		let hyperbolic = SoftCap::hyperbolic( 50.0, 100.0, 50.0 ).unwrap();
+		assert_eq!( hyperbolic.apply( 10.0 ), 10.0 );
+		assert_eq!( hyperbolic.apply( 50.0 ), 50.0 );
+		assert_eq!( hyperbolic.apply( 100.0 ), 75.0 );
+		
+		let asymptotic = SoftCap::asymptotic( 50.0, 60.0 ).unwrap();
+		assert_eq!( asymptotic.apply( 50.0 ), 50.0 );
+		assert!( asymptotic.apply( 1_000.0 ) <= 60.0 );
+		assert!( asymptotic.apply( 51.0 ) > 50.9 );
+		
+		let power = SoftCap::power( 10.0, 0.5 ).unwrap();
+		assert_eq!( power.apply( 25.0 ), 13.0 );
+		assert!( power.apply( 10.5 ) <= 10.5 );
+	}
+	
+	#[test]
+	fn never_above_input() {
+		let caps = [
+			SoftCap::linear( 10.0, 0.5 ).unwrap(),
+			SoftCap::linear( 10.0, 1.0 ).unwrap(),
+			SoftCap::hyperbolic( 10.0, 20.0, 10.0 ).unwrap(),
+			SoftCap::hyperbolic( 10.0, 100.0, 200.0 ).unwrap(),
+			SoftCap::asymptotic( 10.0, 20.0 ).unwrap(),
+			SoftCap::power( 10.0, 0.5 ).unwrap(),
+			SoftCap::power( 10.0, 1.0 ).unwrap(),
+		];
+		
+		for cap in caps {
+			let mut prev = f64::NEG_INFINITY;
+			
+			for i in -100..=1_000 {
+				let value = f64::from( i ) * 0.125;
+				let capped = cap.apply( value );
+				
+				assert!( capped <= value, "{cap:?} raised {value} to {capped}" );
+				assert!( capped >= prev, "{cap:?} is not monotonic at {value}" );
+				
+				prev = capped;
+			}// for
+		}// for
+	}
+	
+	#[test]
+	fn invalid_args() {
+		let expected = CsInvalidValue::AboveMaximum( "factor".to_string() ).into();
+		assert_eq!( SoftCap::linear( 1.0, 1.5 ), Err( expected ) );
+		
+		let expected = CsInvalidValue::BelowMinimum( "k".to_string() ).into();
+		assert_eq!( SoftCap::hyperbolic( 0.0, 1.0, 0.5 ), Err( expected ) );
+		assert_eq!( SoftCap::hyperbolic( 1.0, 1.0, 1.0 ), Err( CsLogicIssue::MinGreaterThanMax.into() ) );
+		
+		assert_eq!( SoftCap::asymptotic( 1.0, 1.0 ), Err( CsLogicIssue::MinGreaterThanMax.into() ) );
+		
+		let expected = CsInvalidValue::Nan( "exponent".to_string() ).into();
+		assert_eq!( SoftCap::power( 1.0, f64::NAN ), Err( expected ) );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let cap = SoftCap::hyperbolic( 50.0, 100.0, 50.0 ).unwrap();
+		let json = serde_json::to_string( &cap ).unwrap();
+		assert_eq!( serde_json::from_str::< SoftCap >( &json ).unwrap(), cap );
+		
+		// curves are validated like by the constructors
+		let invalid = [
+			( r#"{"curve":{"Linear":{"threshold":1.0,"factor":1.5}}}"#, "factor" ),
+			( r#"{"curve":{"Hyperbolic":{"threshold":50.0,"max":100.0,"k":1.0}}}"#, "k" ),
+			( r#"{"curve":{"Power":{"threshold":1.0,"exponent":0.0}}}"#, "exponent" ),
+		];
+		
+		for ( json, field ) in invalid {
+			let err = serde_json::from_str::< SoftCap >( json ).unwrap_err();
+			assert!( err.to_string().contains( field ) );
+		}// for
+		
+		assert!( serde_json::from_str::< SoftCap >( r#"{"curve":{"Asymptotic":{"threshold":1.0,"max":1.0}}}"# ).is_err() );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,316 @@
+use std::fmt::{ Display, Formatter };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::{ BoundSpec, Modifier, ModifierId, StageValues, CharStatError, CsInvalidValue };
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - ThresholdId
+
+/// Handle returned by `CharStat::add_threshold`.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq, Hash )]
+pub struct ThresholdId( u32 );
+
+impl ThresholdId {
+	#[inline]
+	pub( crate ) fn new ( id: u32 ) -> Self {
+		ThresholdId( id )
+	}
+}
+
+impl Display for ThresholdId {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+// struct - ThresholdId
+//------------------------------------------------------------------------------
+// struct - ThresholdSpec
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum ThresholdDirection {
+	/// active while the value is lower than the level
+	Below,
+	/// active while the value is greater than the level
+	Above,
+}
+
+/// Level watched by `CharStat`, e.g. "HP below 25% of max".
+/// - level => `BoundSpec` resolved every recalculation, `PercentOf` watches a fraction of a stage, `Source` a value of another stat
+/// - hysteresis => distance past the level the value has to return to before the threshold becomes inactive, in units of the value
+/// 
+/// # Examples
+/// ```rust
+/// use char_stat::{ ThresholdSpec, ThresholdDirection, BoundSpec, StageRef };
+/// 
+/// // active below 25% of base + upgrade, inactive again 5 points above the level
+/// let spec = ThresholdSpec::new( BoundSpec::percent_of( StageRef::BasePlusUpgrade, 0.25 ), ThresholdDirection::Below, 5.0 ).unwrap();
+/// assert_eq!( spec.direction(), ThresholdDirection::Below );
+/// 
+/// assert!( ThresholdSpec::new( BoundSpec::Const( 1.0 ), ThresholdDirection::Above, -1.0 ).is_err() );
+/// ```
+/// 
+/// Deserialization runs the same checks as `ThresholdSpec::new`.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[cfg_attr( feature = "serde", serde( try_from = "ThresholdSpecRepr" ) )]
+#[derive( Debug, Clone, PartialEq )]
+pub struct ThresholdSpec {
+	level: BoundSpec,
+	direction: ThresholdDirection,
+	hysteresis: f64,
+}
+
+impl ThresholdSpec {
+	/// # Errors
+	/// `CsInvalidValue::Nan` when `hysteresis` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `hysteresis` is infinite <br>
+	/// `CsInvalidValue::BelowMinimum` when `hysteresis` is negative <br>
+	#[inline]
+	pub fn new ( level: BoundSpec, direction: ThresholdDirection, hysteresis: f64 ) -> Result< Self, CharStatError > {
+		if hysteresis.is_nan() {
+			return Err( CsInvalidValue::Nan( "hysteresis".to_string() ).into() )
+		}
+		
+		if hysteresis.is_infinite() {
+			return Err( CsInvalidValue::Infinite( "hysteresis".to_string() ).into() )
+		}
+		
+		if hysteresis < 0.0 {
+			return Err( CsInvalidValue::BelowMinimum( "hysteresis".to_string() ).into() )
+		}
+		
+		Ok( ThresholdSpec { level, direction, hysteresis } )
+	}
+	
+	/// threshold without hysteresis active below `level`
+	#[inline]
+	pub fn below ( level: BoundSpec ) -> Self {
+		ThresholdSpec { level, direction: ThresholdDirection::Below, hysteresis: 0.0 }
+	}
+	
+	/// threshold without hysteresis active above `level`
+	#[inline]
+	pub fn above ( level: BoundSpec ) -> Self {
+		ThresholdSpec { level, direction: ThresholdDirection::Above, hysteresis: 0.0 }
+	}
+	
+	#[inline]
+	pub fn level ( &self ) -> &BoundSpec {
+		&self.level
+	}
+	
+	#[inline]
+	pub fn direction ( &self ) -> ThresholdDirection {
+		self.direction
+	}
+	
+	#[inline]
+	pub fn hysteresis ( &self ) -> f64 {
+		self.hysteresis
+	}
+	
+	/// returns the new state, NaN level keeps the current one
+	#[inline]
+	#[doc( hidden )]
+	fn is_active ( &self, value: f64, ctx: &StageValues, is_active: bool ) -> bool {
+		let level = self.level.resolve( ctx );
+		
+		if level.is_nan() {
+			return is_active
+		}
+		
+		match ( self.direction, is_active ) {
+			( ThresholdDirection::Below, false ) => value < level,
+			( ThresholdDirection::Below, true ) => value < level + self.hysteresis,
+			( ThresholdDirection::Above, false ) => value > level,
+			( ThresholdDirection::Above, true ) => value > level - self.hysteresis,
+		}
+	}
+}
+
+#[cfg( feature = "serde" )]
+#[derive( Deserialize )]
+struct ThresholdSpecRepr {
+	level: BoundSpec,
+	direction: ThresholdDirection,
+	hysteresis: f64,
+}
+
+#[cfg( feature = "serde" )]
+impl TryFrom< ThresholdSpecRepr > for ThresholdSpec {
+	type Error = CharStatError;
+	
+	#[inline]
+	fn try_from( repr: ThresholdSpecRepr ) -> Result< Self, Self::Error > {
+		ThresholdSpec::new( repr.level, repr.direction, repr.hysteresis )
+	}
+}
+
+// struct - ThresholdSpec
+//------------------------------------------------------------------------------
+// struct - StatReport
+
+/// Threshold which became active ( `is_entered` ) or inactive.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub struct ThresholdCrossing {
+	pub id: ThresholdId,
+	pub is_entered: bool,
+}
+
+/// Returned by mutations of `CharStat`, holds thresholds crossed by the mutation.
+/// `CharStat::set_ts` also reports the expired modifiers and the change of the value they caused,
+/// `CharStat::append_modifier` the id of the appended modifier.
+/// Empty report does not allocate.
+#[derive( Debug, Clone, PartialEq, Default )]
+pub struct StatReport {
+	pub crossed: Vec< ThresholdCrossing >,
+	pub expired: Vec< Modifier >,
+	/// change of the value caused by `expired`
+	pub delta: f64,
+	/// id assigned by `CharStat::append_modifier`
+	pub added: Option< ModifierId >,
+}
+
+impl StatReport {
+	/// returns true when no threshold was crossed and no modifier expired
+	#[inline]
+	pub fn is_empty ( &self ) -> bool {
+		self.crossed.is_empty() && self.expired.is_empty()
+	}
+	
+	/// returns true when threshold `id` became active
+	#[inline]
+	pub fn has_entered ( &self, id: ThresholdId ) -> bool {
+		self.crossed.iter().any( |tmp| tmp.id == id && tmp.is_entered )
+	}
+	
+	/// returns true when threshold `id` became inactive
+	#[inline]
+	pub fn has_left ( &self, id: ThresholdId ) -> bool {
+		self.crossed.iter().any( |tmp| tmp.id == id && !tmp.is_entered )
+	}
+}
+
+// struct - StatReport
+//------------------------------------------------------------------------------
+// struct - Thresholds
+
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq )]
+struct Threshold {
+	id: ThresholdId,
+	spec: ThresholdSpec,
+	is_active: bool,
+}
+
+/// Thresholds of one `CharStat` and their state.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, PartialEq, Default )]
+pub( crate ) struct Thresholds {
+	list: Vec< Threshold >,
+}
+
+// pub-crate
+impl Thresholds {
+	/// adds `spec` with the state of `value`, without reporting it
+	#[inline]
+	pub( crate ) fn add ( &mut self, id: ThresholdId, spec: ThresholdSpec, value: f64, ctx: &StageValues ) {
+		let is_active = spec.is_active( value, ctx, false );
+		self.list.push( Threshold { id, spec, is_active } );
+	}
+	
+	#[inline]
+	pub( crate ) fn remove ( &mut self, id: ThresholdId ) -> Option< ThresholdSpec > {
+		let idx = self.list.iter().position( |tmp| tmp.id == id )?;
+		
+		Some( self.list.remove( idx ).spec )
+	}
+	
+	#[inline]
+	pub( crate ) fn is_active ( &self, id: ThresholdId ) -> Option< bool > {
+		self.list.iter().find( |tmp| tmp.id == id ).map( |tmp| tmp.is_active )
+	}
+	
+	#[inline]
+	pub( crate ) fn is_empty ( &self ) -> bool {
+		self.list.is_empty()
+	}
+	
+	/// updates the state of every threshold, changes are pushed to `out`
+	#[inline]
+	pub( crate ) fn evaluate ( &mut self, value: f64, ctx: &StageValues, out: &mut Vec< ThresholdCrossing > ) {
+		for tmp in &mut self.list {
+			let is_active = tmp.spec.is_active( value, ctx, tmp.is_active );
+			
+			if is_active != tmp.is_active {
+				tmp.is_active = is_active;
+				out.push( ThresholdCrossing { id: tmp.id, is_entered: is_active } );
+			}
+		}// for
+	}
+}
+
+// struct - Thresholds
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	use crate::{ StageValues, BoundSpec, StageRef, ThresholdSpec, ThresholdDirection, ThresholdCrossing };
+	use super::{ Thresholds, ThresholdId };
+	
+	#[test]
+	fn hysteresis() {
+		let ctx = StageValues { base: 100.0, ..StageValues::default() };
+		let mut thresholds = Thresholds::default();
+		let ( below, above ) = ( ThresholdId::new( 0 ), ThresholdId::new( 1 ) );
+		thresholds.add( below, ThresholdSpec::new( BoundSpec::percent_of( StageRef::Base, 0.25 ), ThresholdDirection::Below, 5.0 ).unwrap(), 50.0, &ctx );
+		thresholds.add( above, ThresholdSpec::above( BoundSpec::Const( 40.0 ) ), 50.0, &ctx );
+		assert_eq!( thresholds.is_active( below ), Some( false ) );
+		assert_eq!( thresholds.is_active( above ), Some( true ) );
+		
+		let mut out = Vec::new();
+		for value in [ 30.0, 24.0, 20.0, 28.0, 29.0, 30.0, 24.0 ] {
+			thresholds.evaluate( value, &ctx, &mut out );
+		}
+		
+		assert_eq!( out, vec![
+			ThresholdCrossing { id: above, is_entered: false },
+			ThresholdCrossing { id: below, is_entered: true },
+			ThresholdCrossing { id: below, is_entered: false },
+			ThresholdCrossing { id: below, is_entered: true },
+		] );
+		
+		assert!( thresholds.remove( above ).is_some() );
+		assert_eq!( thresholds.is_active( above ), None );
+		assert!( !thresholds.is_empty() );
+	}
+	
+	#[cfg( feature = "serde" )]
+	#[test]
+	fn test_serde() {
+		let spec = ThresholdSpec::new( BoundSpec::Const( 10.0 ), ThresholdDirection::Below, 5.0 ).unwrap();
+		let json = serde_json::to_string( &spec ).unwrap();
+		assert_eq!( serde_json::from_str::< ThresholdSpec >( &json ).unwrap(), spec );
+		
+		// hysteresis is validated
+		let negative = json.replace( r#""hysteresis":5.0"#, r#""hysteresis":-1.0"# );
+		assert_ne!( negative, json );
+		let err = serde_json::from_str::< ThresholdSpec >( &negative ).unwrap_err();
+		assert!( err.to_string().contains( "hysteresis" ) );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
@@ -0,0 +1,218 @@
+use std::fmt::{ Display, Formatter };
+
+#[cfg( feature = "serde" )]
+use serde::{ Serialize, Deserialize };
+
+// --Imports
+//------------------------------------------------------------------------------
+// --Modules
+
+use crate::Bounds;
+
+// --Modules
+//------------------------------------------------------------------------------
+// struct - TotalBounds
+
+/// Bounds applied to the total value of `CharStat`, after every other stage except total rounding.
+/// Min/max can be interpreted as a percent of the base value ( with multiplier, without modifiers ).
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct TotalBounds {
+	bounds: Bounds,
+	is_min_percent: bool,
+	is_max_percent: bool,
+}
+
+impl TotalBounds {
+	#[inline]
+	pub fn new ( bounds: Bounds, is_min_percent: bool, is_max_percent: bool ) -> Self {
+		TotalBounds {
+			bounds,
+			is_min_percent,
+			is_max_percent,
+		}
+	}
+	
+	/// min/max are used as is
+	#[inline]
+	pub fn new_absolute ( bounds: Bounds ) -> Self {
+		TotalBounds::new( bounds, false, false )
+	}
+	
+	/// both min and max are a percent of base
+	#[inline]
+	pub fn new_percent_of_base ( bounds: Bounds ) -> Self {
+		TotalBounds::new( bounds, true, true )
+	}
+	
+	#[inline]
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+	
+	#[inline]
+	pub fn is_min_percent ( &self ) -> bool {
+		self.is_min_percent
+	}
+	
+	#[inline]
+	pub fn is_max_percent ( &self ) -> bool {
+		self.is_max_percent
+	}
+	
+	/// returns effective ( min, max ) for the given base value
+	#[inline]
+	pub fn effective ( &self, base: f64 ) -> ( f64, f64 ) {
+		// negative base flips percent bounds, absolute bound wins over a crossing percent one
+		self.bounds.scaled( base, self.is_min_percent, self.is_max_percent )
+	}
+	
+	#[inline]
+	pub fn apply ( &self, value: f64, base: f64 ) -> f64 {
+		let ( eff_min, eff_max ) = self.effective( base );
+		
+		value.clamp( eff_min, eff_max )
+	}
+}
+
+// struct - TotalBounds
+//------------------------------------------------------------------------------
+// struct - ValueBreakdown
+
+/// Snapshot of every calculation stage of `CharStat`, see `CharStat::breakdown`.
+#[cfg_attr( feature = "serde", derive( Serialize, Deserialize ) )]
+#[derive( Debug, Clone, Copy, PartialEq )]
+pub struct ValueBreakdown {
+	pub( crate ) mod_mult: f64,
+	pub( crate ) base: f64,
+	pub( crate ) upgrade: f64,
+	pub( crate ) mod_of_base: f64,
+	pub( crate ) mod_of_upgrade: f64,
+	pub( crate ) mod_of_base_plus_upgrade: f64,
+	pub( crate ) sum: f64,
+	pub( crate ) more: f64,
+	pub( crate ) mod_soft_cap_loss: f64,
+	pub( crate ) total_soft_cap_loss: f64,
+	pub( crate ) total: f64,
+}
+
+impl ValueBreakdown {
+	/// modifier multiplier, 1.0 when missing
+	#[inline]
+	pub fn mod_mult ( &self ) -> f64 {
+		self.mod_mult
+	}
+	
+	/// base with multiplier
+	#[inline]
+	pub fn base ( &self ) -> f64 {
+		self.base
+	}
+	
+	#[inline]
+	pub fn upgrade ( &self ) -> f64 {
+		self.upgrade
+	}
+	
+	/// value of modifiers of base, already multiplied by `mod_mult`
+	#[inline]
+	pub fn mod_of_base ( &self ) -> f64 {
+		self.mod_of_base
+	}
+	
+	/// value of modifiers of upgrade, already multiplied by `mod_mult`
+	#[inline]
+	pub fn mod_of_upgrade ( &self ) -> f64 {
+		self.mod_of_upgrade
+	}
+	
+	/// value of modifiers of base + upgrade, already multiplied by `mod_mult`
+	#[inline]
+	pub fn mod_of_base_plus_upgrade ( &self ) -> f64 {
+		self.mod_of_base_plus_upgrade
+	}
+	
+	/// sum of every additive stage, before "more" modifiers, total rounding and bounds
+	#[inline]
+	pub fn sum ( &self ) -> f64 {
+		self.sum
+	}
+	
+	/// product of "more" modifiers, 1.0 when missing
+	#[inline]
+	pub fn more ( &self ) -> f64 {
+		self.more
+	}
+	
+	/// amount removed by soft caps of modifier stages, already multiplied by `mod_mult`
+	#[inline]
+	pub fn mod_soft_cap_loss ( &self ) -> f64 {
+		self.mod_soft_cap_loss
+	}
+	
+	/// amount removed by soft cap of the total
+	#[inline]
+	pub fn total_soft_cap_loss ( &self ) -> f64 {
+		self.total_soft_cap_loss
+	}
+	
+	/// final value, same as `CharStat::value`
+	#[inline]
+	pub fn total ( &self ) -> f64 {
+		self.total
+	}
+}
+
+impl Display for ValueBreakdown {
+	#[inline]
+	fn fmt( &self, f: &mut Formatter<'_> ) -> std::fmt::Result {
+		writeln!( f, "base: {}", self.base )?;
+		writeln!( f, "upgrade: {}", self.upgrade )?;
+		writeln!( f, "mod of base: {}", self.mod_of_base )?;
+		writeln!( f, "mod of upgrade: {}", self.mod_of_upgrade )?;
+		writeln!( f, "mod of base + upgrade: {}", self.mod_of_base_plus_upgrade )?;
+		writeln!( f, "mod mult: x{}", self.mod_mult )?;
+		writeln!( f, "sum: {}", self.sum )?;
+		writeln!( f, "more: x{}", self.more )?;
+		writeln!( f, "soft cap loss ( modifiers ): {}", self.mod_soft_cap_loss )?;
+		writeln!( f, "soft cap loss ( total ): {}", self.total_soft_cap_loss )?;
+		write!( f, "total: {}", self.total )
+	}
+}
+
+// struct - ValueBreakdown
+//------------------------------------------------------------------------------
+// --Tests
+
+#[cfg( test )]
+mod tests {
+	#![allow( clippy::float_cmp )]
+	
+	use super::*;
+	
+	#[test]
+	fn basic_functional() {
+		let bounds = Bounds::new_const( 0.5, 2.0 ).unwrap();
+		
+		let absolute = TotalBounds::new_absolute( bounds );
+		assert_eq!( absolute.apply( 10.0, 4.0 ), 2.0 );
+		assert_eq!( absolute.apply( 0.0, 4.0 ), 0.5 );
+		
+		let percent = TotalBounds::new_percent_of_base( bounds );
+		assert_eq!( percent.apply( 10.0, 4.0 ), 8.0 );
+		assert_eq!( percent.apply( 0.0, 4.0 ), 2.0 );
+		
+		let mixed = TotalBounds::new( bounds, false, true );
+		assert_eq!( mixed.effective( 10.0 ), ( 0.5, 20.0 ) );
+		
+		// percent max below the absolute min, absolute min wins
+		assert_eq!( mixed.effective( 0.2 ), ( 0.5, 0.5 ) );
+		assert_eq!( mixed.apply( 10.0, -4.0 ), 0.5 );
+		
+		// negative base flips the range instead of panicking
+		assert_eq!( percent.effective( -4.0 ), ( -8.0, -2.0 ) );
+	}
+}
+
+// --Tests
+//------------------------------------------------------------------------------
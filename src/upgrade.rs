@@ -5,7 +5,7 @@ use serde::{ Serialize, Deserialize };
 //------------------------------------------------------------------------------
 // --Modules
 
-use super::{ Bounds, RoundingHelper, CharStatError, CsInvalidValue };
+use super::{ Shared, Bounds, RoundingHelper, CharStatError, CsInvalidValue, DynBounds, StageValues };
 
 // --Modules
 //------------------------------------------------------------------------------
@@ -16,16 +16,22 @@ use super::{ Bounds, RoundingHelper, CharStatError, CsInvalidValue };
 #[derive( Debug, Clone, PartialEq,  )]
 pub struct UpgradeConf {
 	value: f64,
-	bounds: Bounds,
-	rounding_fn: RoundingHelper,
+	bounds: Shared< Bounds >,
+	rounding_fn: Shared< RoundingHelper >,
+	#[cfg_attr( feature = "serde", serde( default ) )]
+	dyn_bounds: Option< DynBounds >,
 }
 
 impl UpgradeConf {
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `bounds` <br>
 	#[inline]
-	pub fn new ( mut value: f64, bounds: Bounds, rounding_fn: RoundingHelper, ) -> Result< Self, CharStatError > {
+	pub fn new ( mut value: f64, bounds: impl Into< Shared< Bounds > >, rounding_fn: impl Into< Shared< RoundingHelper > >, ) -> Result< Self, CharStatError > {
+		let bounds: Shared< Bounds > = bounds.into();
+		let rounding_fn: Shared< RoundingHelper > = rounding_fn.into();
+		
 		UpgradeConf::check_inval( value, &bounds )?;
 		
 		value = rounding_fn.do_rounding( value );
@@ -34,13 +40,18 @@ impl UpgradeConf {
 			value,
 			bounds,
 			rounding_fn,
+			dyn_bounds: None,
 		})
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	#[inline]
-	pub fn new_clamping ( mut value: f64, bounds: Bounds, rounding_fn: RoundingHelper, ) -> Result< Self, CharStatError > {
+	pub fn new_clamping ( mut value: f64, bounds: impl Into< Shared< Bounds > >, rounding_fn: impl Into< Shared< RoundingHelper > >, ) -> Result< Self, CharStatError > {
+		let bounds: Shared< Bounds > = bounds.into();
+		let rounding_fn: Shared< RoundingHelper > = rounding_fn.into();
+		
 		UpgradeConf::check_nan( value )?;
 		
 		value = rounding_fn.do_rounding( value );
@@ -50,11 +61,13 @@ impl UpgradeConf {
 			value,
 			bounds,
 			rounding_fn,
+			dyn_bounds: None,
 		})
 	}
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	/// `CsInvalidValue::BelowMinimum` or `CsInvalidValue::AboveMaximum` when `value` is not within `self.bounds` <br>
 	#[inline]
 	pub fn set_value ( &mut self, mut value: f64 ) -> Result<(), CharStatError > {
@@ -69,6 +82,7 @@ impl UpgradeConf {
 	
 	/// # Errors
 	/// `CsInvalidValue::Nan` when `value` is `f64::NAN` <br>
+	/// `CsInvalidValue::Infinite` when `value` is infinite <br>
 	#[inline]
 	pub fn set_value_clamping ( &mut self, mut value: f64 ) -> Result<(), CharStatError > {
 		UpgradeConf::check_nan( value )?;
@@ -84,6 +98,36 @@ impl UpgradeConf {
 	pub fn value ( &self ) -> f64 {
 		self.value
 	}
+	
+	#[inline]
+	pub fn bounds ( &self ) -> &Bounds {
+		&self.bounds
+	}
+	
+	#[inline]
+	pub fn rounding ( &self ) -> &RoundingHelper {
+		&self.rounding_fn
+	}
+	
+	/// Dynamic bounds are resolved by `CharStat` during recalculation, the stored value is not changed.
+	#[inline]
+	pub fn set_dyn_bounds ( &mut self, dyn_bounds: Option< DynBounds > ) {
+		self.dyn_bounds = dyn_bounds;
+	}
+	
+	#[inline]
+	pub fn dyn_bounds ( &self ) -> Option< &DynBounds > {
+		self.dyn_bounds.as_ref()
+	}
+	
+	/// value clamped by dynamic bounds
+	#[inline]
+	pub fn effective_value ( &self, ctx: &StageValues ) -> f64 {
+		match &self.dyn_bounds {
+			Some( dyn_bounds ) => dyn_bounds.apply( self.value, ctx ),
+			None => self.value,
+		}
+	}
 }
 
 // bounds
@@ -94,7 +138,7 @@ impl UpgradeConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_min_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_min ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_min( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_min( new_val ) )?;
 		
 		Ok(())
 	}
@@ -105,19 +149,19 @@ impl UpgradeConf {
 	/// `CsLogicIssue::FieldIsConst` when `self.bounds.is_max_mut` is false <br>
 	#[inline]
 	pub fn set_bounds_max ( &mut self, new_val: f64 ) -> Result<(), CharStatError > {
-		self.bounds.set_max( new_val )?;
+		self.bounds.try_update( |tmp| tmp.set_max( new_val ) )?;
 		
 		Ok(())
 	}
 	
 	#[inline]
 	pub fn set_bounds_min_const ( &mut self ) {
-		self.bounds.set_min_const();
+		self.bounds.update( Bounds::set_min_const );
 	}
 	
 	#[inline]
 	pub fn set_bounds_max_const ( &mut self ) {
-		self.bounds.set_max_const();
+		self.bounds.update( Bounds::set_max_const );
 	}
 	
 	#[inline]
@@ -131,6 +175,21 @@ impl UpgradeConf {
 	}
 }// bounds
 
+// pub-crate
+impl UpgradeConf {
+	/// constructor for a value already checked, without rounding
+	#[inline]
+	#[doc( hidden )]
+	pub( crate ) fn new_unchecked ( value: f64, bounds: Bounds ) -> Self {
+		UpgradeConf {
+			value,
+			bounds: Shared::new( bounds ),
+			rounding_fn: Shared::new( RoundingHelper::new_none() ),
+			dyn_bounds: None,
+		}
+	}
+}// pub-crate
+
 //priv
 impl UpgradeConf {
 	#[inline( always )]
@@ -140,6 +199,10 @@ impl UpgradeConf {
 			
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
 		if value < bounds.min() {
 			
 			return Err( CsInvalidValue::BelowMinimum( "value".to_string() ).into() )
@@ -159,6 +222,10 @@ impl UpgradeConf {
 			
 			return Err( CsInvalidValue::Nan( "value".to_string() ).into() )
 		}
+		if value.is_infinite() {
+			
+			return Err( CsInvalidValue::Infinite( "value".to_string() ).into() )
+		}
 		
 		Ok(())
 	}
@@ -170,6 +237,8 @@ impl UpgradeConf {
 
 #[cfg( test )]
 mod tests {
+	#![allow( clippy::float_cmp )]
+	
 	use super::*;
 	
 	#[test]
@@ -192,10 +261,10 @@ mod tests {
 		let rounding_fn = RoundingHelper::new_none();
 		let bounds = Bounds::new_const( 0.0, 1.0 ).unwrap();
 		
-		let upgrade = UpgradeConf::new( f64::NAN, bounds.clone(), rounding_fn.clone() );
+		let upgrade = UpgradeConf::new( f64::NAN, bounds, rounding_fn.clone() );
 		assert_eq!( upgrade, Err( expected.clone() ) );
 		
-		let upgrade = UpgradeConf::new_clamping( f64::NAN, bounds.clone(), rounding_fn.clone() );
+		let upgrade = UpgradeConf::new_clamping( f64::NAN, bounds, rounding_fn.clone() );
 		assert_eq!( upgrade, Err( expected ) );
 	}
 }